@@ -3,12 +3,16 @@
  * C FFI Bindings
  *
  * Thread Safety: MemvidHandle is NOT thread-safe. Use from a single thread
- * or provide external synchronization.
+ * or provide external synchronization. MemvidSharedHandle (see
+ * memvid_open_shared()) may be shared across threads.
  *
  * Memory Ownership:
  * - Handles: Caller owns, must call memvid_close()
  * - Returned strings: Caller owns, must call memvid_string_free()
+ * - Returned byte buffers: Caller owns, must call memvid_bytes_free()
  * - MemvidError.message: FFI owns, call memvid_error_free()
+ * - Returned strings and buffers use a host allocator if one is installed
+ *   with memvid_set_allocator()
  */
 
 #ifndef MEMVID_FFI_H
@@ -18,6 +22,9 @@
 #include <stdbool.h>
 #include <stdint.h>
 #include <stdlib.h>
+#ifdef _WIN32
+#include <wchar.h>
+#endif
 
 #ifdef __cplusplus
 extern "C" {
@@ -136,6 +143,22 @@ typedef enum MemvidErrorCode {
     MemvidErrorCode_JsonParse = 102,
     /** Invalid handle (FFI-specific) */
     MemvidErrorCode_InvalidHandle = 103,
+    /** JSON serialization error; a library bug, not bad input (FFI-specific) */
+    MemvidErrorCode_JsonSerialize = 104,
+    /** Operation cancelled by a host callback (FFI-specific) */
+    MemvidErrorCode_Cancelled = 105,
+    /** Caller-provided buffer too small; retry with the reported size (FFI-specific) */
+    MemvidErrorCode_BufferTooSmall = 106,
+    /** Frame to restore is not deleted (FFI-specific) */
+    MemvidErrorCode_FrameNotDeleted = 107,
+    /** Mutation attempted on a read-only handle (FFI-specific) */
+    MemvidErrorCode_ReadOnly = 108,
+    /** Host synthesis callback failed (FFI-specific) */
+    MemvidErrorCode_SynthesisFailed = 109,
+    /** Text result contains an interior null byte; read it as bytes instead (FFI-specific) */
+    MemvidErrorCode_InteriorNul = 110,
+    /** Host allocator set with memvid_set_allocator returned NULL (FFI-specific) */
+    MemvidErrorCode_AllocationFailed = 111,
     /** Unknown error */
     MemvidErrorCode_Unknown = 255,
 } MemvidErrorCode;
@@ -147,6 +170,27 @@ typedef enum MemvidErrorCode {
  */
 typedef struct MemvidHandle MemvidHandle;
 
+/**
+ * Opaque, thread-safe handle to a Memvid instance.
+ *
+ * The handle must be freed with memvid_close_shared().
+ */
+typedef struct MemvidSharedHandle MemvidSharedHandle;
+
+/**
+ * Opaque handle to a running watcher.
+ *
+ * The watcher must be stopped and freed with memvid_watch_stop().
+ */
+typedef struct MemvidWatcher MemvidWatcher;
+
+/**
+ * Cursor over the frames of a handle.
+ *
+ * The cursor must be freed with memvid_frames_iter_free().
+ */
+typedef struct MemvidFrameIter MemvidFrameIter;
+
 /**
  * Error structure returned via out-parameter.
  *
@@ -160,6 +204,18 @@ typedef struct MemvidError {
     char *message;
 } MemvidError;
 
+/**
+ * One item of a batch put (see memvid_put_batch()).
+ */
+typedef struct MemvidPutItem {
+    /** Pointer to content bytes (may be NULL if len is 0) */
+    const uint8_t *data;
+    /** Length of content in bytes */
+    size_t len;
+    /** JSON string with put options, or NULL for a plain put */
+    const char *options_json;
+} MemvidPutItem;
+
 /**
  * Memory statistics.
  *
@@ -210,6 +266,26 @@ typedef struct MemvidStats {
     uint64_t remaining_capacity_bytes;
 } MemvidStats;
 
+/**
+ * ABI version of this header. Compare with MemvidVersionInfo.abi_version
+ * to reject a library built with different struct layouts.
+ */
+#define MEMVID_ABI_VERSION 1
+
+/**
+ * Structured library version.
+ */
+typedef struct MemvidVersionInfo {
+    /** Major version */
+    uint32_t major;
+    /** Minor version */
+    uint32_t minor;
+    /** Patch version */
+    uint32_t patch;
+    /** ABI version, bumped whenever a struct layout changes */
+    uint32_t abi_version;
+} MemvidVersionInfo;
+
 /* ============================================================================
  * Version and Feature Functions
  * ============================================================================ */
@@ -229,9 +305,30 @@ const char *memvid_version(void);
  *         - Bit 0 (0x01): lex - Lexical search
  *         - Bit 1 (0x02): vec - Vector search
  *         - Bit 2 (0x04): clip - CLIP embeddings
+ *         - Bit 3 (0x08): temporal_track - Temporal queries
+ *         - Bit 4 (0x10): logic_mesh - Logic mesh entity graph
  */
 uint32_t memvid_features(void);
 
+/**
+ * Get the library version as numbers, plus the ABI version.
+ *
+ * @param out  Out-parameter for the version
+ *
+ * @return 1 on success, 0 if out is NULL.
+ */
+int memvid_version_info(MemvidVersionInfo *out);
+
+/**
+ * Get the JSON Schema (draft-07) of a request type.
+ *
+ * @param kind  "search", "ask", "put_options", "timeline", or "doctor"
+ *
+ * @return Static JSON string, or NULL if kind is NULL or unknown.
+ *         Do not free this string.
+ */
+const char *memvid_request_schema(const char *kind);
+
 /* ============================================================================
  * Lifecycle Functions
  * ============================================================================ */
@@ -247,6 +344,60 @@ uint32_t memvid_features(void);
  */
 MemvidHandle *memvid_create(const char *path, MemvidError *error);
 
+/**
+ * Create a new Memvid memory with options (JSON configuration).
+ *
+ * NULL or empty options_json behaves exactly like memvid_create().
+ * memvid-core's create path takes no configuration, so options are checked
+ * against what it produces: capacity and tier above the default come from
+ * signed tickets (apply one after creating), encrypted memories are not
+ * supported, and language must name the lexical analyzer core builds (see
+ * memvid_search()).
+ *
+ * Options JSON:
+ * {
+ *   "capacity_bytes": 1073741824,  // optional, must match the default
+ *   "tier": "free",                // optional: "free", "dev", "enterprise"
+ *   "language": "en",              // optional, default lexical analyzer
+ *   "encryption_key": "base64..."  // optional, not supported
+ * }
+ *
+ * @param path          Filesystem path for the memory (UTF-8 encoded, null-terminated)
+ * @param options_json  JSON options, or NULL
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Handle on success, NULL on failure (InvalidTier for an unknown
+ *         tier, TicketRequired if capacity or tier differs from the
+ *         default, FeatureUnavailable if encryption_key is set or
+ *         language names an analyzer core does not build).
+ *         Caller owns the returned handle. Must call memvid_close() to free.
+ */
+MemvidHandle *memvid_create_with_options(const char *path,
+                                         const char *options_json,
+                                         MemvidError *error);
+
+/**
+ * Create a new, empty Memvid memory with the settings of an existing one.
+ *
+ * Reads only the template's header and stats, never its frames. The new
+ * memory gets the template's format and capacity. memvid-core's create path
+ * takes no configuration (codec and index settings are fixed by the build,
+ * and extra capacity comes from tickets bound to one memory), so a template
+ * whose capacity differs from a fresh memory's fails with TicketRequired
+ * and no file is left behind.
+ *
+ * @param path           Filesystem path for the new memory
+ * @param template_path  Path to an existing memory to copy settings from
+ * @param error          Out-parameter for error information (may be NULL)
+ *
+ * @return Handle on success, NULL on failure (Io if the template cannot be
+ *         read, InvalidHeader if it is not a compatible .mv2 file).
+ *         Caller owns the returned handle. Must call memvid_close() to free.
+ */
+MemvidHandle *memvid_create_from_template(const char *path,
+                                          const char *template_path,
+                                          MemvidError *error);
+
 /**
  * Open an existing Memvid memory.
  *
@@ -258,19 +409,246 @@ MemvidHandle *memvid_create(const char *path, MemvidError *error);
  */
 MemvidHandle *memvid_open(const char *path, MemvidError *error);
 
+/**
+ * Open an existing Memvid memory with options (JSON configuration).
+ *
+ * NULL or empty options_json behaves exactly like memvid_open(). Accepts
+ * the same encryption_key as memvid_create_with_options(), and likewise
+ * rejects it: memvid-core has no encrypted open path.
+ *
+ * Options JSON:
+ * {
+ *   "encryption_key": "base64..."  // optional, not supported
+ * }
+ *
+ * @param path          Filesystem path to existing memory (UTF-8 encoded, null-terminated)
+ * @param options_json  JSON options, or NULL
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Handle on success, NULL on failure (FeatureUnavailable if
+ *         encryption_key is set).
+ *         Caller owns the returned handle. Must call memvid_close() to free.
+ */
+MemvidHandle *memvid_open_with_options(const char *path,
+                                       const char *options_json,
+                                       MemvidError *error);
+
+/**
+ * Open an existing Memvid memory for reading only.
+ *
+ * Does not take the write lock, so several processes can read a memory
+ * while another writes to it. Search, stats, timeline, and frame retrieval
+ * work as usual; puts, commits, deletes, restores, and frame updates fail
+ * with ReadOnly.
+ *
+ * @param path   Filesystem path to existing memory (UTF-8 encoded, null-terminated)
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return Handle on success, NULL on failure.
+ *         Caller owns the returned handle. Must call memvid_close() to free.
+ */
+MemvidHandle *memvid_open_readonly(const char *path, MemvidError *error);
+
+#ifdef _WIN32
+/**
+ * Create a new Memvid memory at a UTF-16 path (Windows only).
+ *
+ * Same as memvid_create(), but the path is null-terminated UTF-16, so
+ * Windows paths round-trip without a lossy conversion to UTF-8.
+ *
+ * @param path   Filesystem path for the memory (UTF-16, null-terminated)
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return Handle on success, NULL on failure.
+ *         Caller owns the returned handle. Must call memvid_close() to free.
+ */
+MemvidHandle *memvid_create_w(const wchar_t *path, MemvidError *error);
+
+/**
+ * Open an existing Memvid memory at a UTF-16 path (Windows only).
+ *
+ * Same as memvid_open(), but the path is null-terminated UTF-16.
+ *
+ * @param path   Filesystem path to existing memory (UTF-16, null-terminated)
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return Handle on success, NULL on failure.
+ *         Caller owns the returned handle. Must call memvid_close() to free.
+ */
+MemvidHandle *memvid_open_w(const wchar_t *path, MemvidError *error);
+#endif
+
+/**
+ * Reload a memory from disk on an existing handle.
+ *
+ * Reopens the file behind the handle, so a long-lived reader picks up
+ * frames committed by other processes since it was opened. A writable
+ * handle commits its staged changes first. Options, read-only mode, and
+ * the registered embedder are kept.
+ *
+ * Consistency: after a successful refresh the handle sees every commit that
+ * completed before the call, never a partial one. Between refreshes it
+ * keeps reading the snapshot it last loaded.
+ *
+ * Frame cursors and search "next_cursor" tokens issued before the refresh
+ * are invalidated and fail with InvalidCursor. Timeline cursors stay valid.
+ *
+ * Errors: Locked if a writable handle cannot take the write lock back. If
+ * the file cannot be reopened, the handle fails every later call with
+ * InvalidHandle and must be closed.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_refresh(MemvidHandle *handle, MemvidError *error);
+
+/**
+ * Lock or unlock a handle against writes.
+ *
+ * A lock on this handle only, not a memvid-core state: memvid-core has no
+ * public seal operation, and locking does not satisfy a core operation that
+ * fails with RequiresSealed.
+ *
+ * Locking commits everything staged and syncs the file to disk, leaving
+ * nothing pending in the write-ahead log. While locked, every put
+ * (memvid_put_bytes() and its variants, memvid_put_batch(),
+ * memvid_put_file(), memvid_put_image()), delete, restore, and update,
+ * memvid_import_jsonl(), memvid_commit() and its variants, and
+ * memvid_vacuum() fail with ReadOnly. Reads, search, memvid_refresh(), and
+ * memvid_close() are unaffected. Locking a locked handle or unlocking an
+ * unlocked one does nothing.
+ *
+ * Errors: ReadOnly when locking a read-only handle; Io if the file cannot
+ * be synced. The handle stays unlocked if the commit or sync fails.
+ *
+ * @param handle  Valid Memvid handle
+ * @param locked  Nonzero to lock, 0 to unlock
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_set_write_locked(MemvidHandle *handle, int locked, MemvidError *error);
+
 /**
  * Close and free a Memvid handle.
  *
- * After this call, the handle is invalid and must not be used.
+ * After this call, the handle is invalid and must not be used. A handle
+ * that is not live (see memvid_handle_is_valid()) is ignored.
  *
  * @param handle  Handle to close (safe to pass NULL)
  */
 void memvid_close(MemvidHandle *handle);
 
+/**
+ * Check whether a handle pointer looks usable.
+ *
+ * Returns 0 for NULL and for a closed handle; every call taking a handle
+ * makes the same check and fails with InvalidHandle. The check reads a tag
+ * stored in the handle, so it catches reuse after close only while the
+ * freed memory has not been reused, and cannot recognize arbitrary garbage
+ * pointers. It is a debugging aid for bindings, not a guarantee.
+ *
+ * @param handle  Handle to check (may be NULL)
+ *
+ * @return 1 if the handle is live, 0 otherwise.
+ */
+int memvid_handle_is_valid(const MemvidHandle *handle);
+
+/* ============================================================================
+ * Shared Handle Functions
+ * ============================================================================ */
+
+/**
+ * Open an existing Memvid memory as a thread-safe shared handle.
+ *
+ * Locking contract: every memvid_shared_* function may be called
+ * concurrently from any thread. Each call takes the handle's lock, so calls
+ * run one at a time, reads included: memvid-core does not promise its read
+ * API is safe to run in parallel, and its search needs mutable access.
+ *
+ * A shared handle cannot be passed to the MemvidHandle functions.
+ *
+ * @param path   Filesystem path to existing memory (UTF-8 encoded, null-terminated)
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return Shared handle on success, NULL on failure.
+ *         Caller owns the returned handle. Must call memvid_close_shared() to free.
+ */
+MemvidSharedHandle *memvid_open_shared(const char *path, MemvidError *error);
+
+/**
+ * Close and free a shared handle.
+ *
+ * No other thread may be using the handle.
+ *
+ * @param shared  Handle to close (safe to pass NULL)
+ */
+void memvid_close_shared(MemvidSharedHandle *shared);
+
+/**
+ * Get memory statistics. Same as memvid_stats().
+ */
+int memvid_shared_stats(const MemvidSharedHandle *shared,
+                        MemvidStats *stats,
+                        MemvidError *error);
+
+/**
+ * Get the number of frames. Same as memvid_frame_count().
+ */
+uint64_t memvid_shared_frame_count(const MemvidSharedHandle *shared,
+                                   MemvidError *error);
+
+/**
+ * Search the memory. Same as memvid_search().
+ */
+char *memvid_shared_search(const MemvidSharedHandle *shared,
+                           const char *request_json,
+                           MemvidError *error);
+
+/**
+ * Add content with options. Same as
+ * memvid_put_bytes_with_options().
+ */
+uint64_t memvid_shared_put_bytes_with_options(const MemvidSharedHandle *shared,
+                                              const uint8_t *data,
+                                              size_t len,
+                                              const char *options_json,
+                                              MemvidError *error);
+
+/**
+ * Commit pending changes. Same as memvid_commit().
+ */
+int memvid_shared_commit(const MemvidSharedHandle *shared, MemvidError *error);
+
 /* ============================================================================
  * Mutation Functions
  * ============================================================================ */
 
+/**
+ * Check whether content is already stored.
+ *
+ * Looks for an active frame whose payload has the same BLAKE3 hash as data,
+ * so a host can skip a put of unchanged content. Every put stores its
+ * payload's hash in the frame's extra_metadata["content_blake3"], so no
+ * payload is read: the first call on a handle indexes the stored hashes,
+ * and later puts and deletes through the handle keep the index current.
+ * Staged puts count, including no_raw ones. Frames put by a version of this
+ * library that did not store hashes are never found.
+ *
+ * @param handle  Valid Memvid handle
+ * @param data    Pointer to content bytes
+ * @param len     Length of content in bytes
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return 1 if a frame with the same content exists, 0 if not or on failure.
+ */
+int memvid_contains_hash(MemvidHandle *handle,
+                         const uint8_t *data,
+                         size_t len,
+                         MemvidError *error);
+
 /**
  * Add content to the memory.
  *
@@ -310,9 +688,29 @@ uint64_t memvid_put_bytes(MemvidHandle *handle,
  *   "auto_tag": true,
  *   "extract_dates": true,
  *   "extract_triplets": true,
+ *   "verbatim": false,
  *   "no_raw": false,
- *   "dedup": false
+ *   "dedup": false,
+ *   "idempotency_key": "ingest-42",
+ *   "sidecar_base64": "iVBORw0KGgo="
  * }
+ *
+ * verbatim stores the content without enrichment, so memvid_frame_content()
+ * returns exactly what was put. It turns off auto_tag, extract_dates, and
+ * extract_triplets, and for UTF-8 content uses the content itself as
+ * search_text, bypassing text extraction and the metadata it adds to the
+ * frame text. Non-UTF-8 content still goes through extraction; read its
+ * exact bytes with memvid_frame_payload(). Combining verbatim with any of
+ * those options enabled, or with search_text, fails with SchemaValidation.
+ *
+ * A put whose idempotency_key was already used in this memory creates nothing
+ * and returns the earlier result: the frame ID of the committed frame carrying
+ * the key, or the value returned by the original put if not yet committed.
+ * Deleting that frame frees the key.
+ *
+ * sidecar_base64 attaches an opaque binary blob to the frame, readable with
+ * memvid_frame_sidecar(). It is limited to 16 KiB decoded; invalid base64 or
+ * a larger blob fails with SchemaValidation.
  */
 uint64_t memvid_put_bytes_with_options(MemvidHandle *handle,
                                        const uint8_t *data,
@@ -321,120 +719,1068 @@ uint64_t memvid_put_bytes_with_options(MemvidHandle *handle,
                                        MemvidError *error);
 
 /**
- * Commit pending changes to disk.
+ * Add content with options, reporting idempotency-key deduplication.
  *
- * @param handle  Valid Memvid handle
- * @param error   Out-parameter for error information (may be NULL)
+ * Same as memvid_put_bytes_with_options(), but writes 1 to deduplicated when
+ * the idempotency_key was already used and no frame was created, 0 otherwise.
  *
- * @return 1 on success, 0 on failure.
+ * @param handle        Valid Memvid handle
+ * @param data          Pointer to content bytes
+ * @param len           Length of content in bytes
+ * @param options_json  JSON string with PutOptions (NULL for defaults)
+ * @param deduplicated  Out-parameter for the deduplication flag (may be NULL)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Frame ID on success, 0 on failure.
  */
-int memvid_commit(MemvidHandle *handle, MemvidError *error);
+uint64_t memvid_put_bytes_idempotent(MemvidHandle *handle,
+                                     const uint8_t *data,
+                                     size_t len,
+                                     const char *options_json,
+                                     int *deduplicated,
+                                     MemvidError *error);
 
-/* ============================================================================
- * Search Functions
- * ============================================================================ */
+/**
+ * Add content with options, reporting every frame the put created.
+ *
+ * Same as memvid_put_bytes_with_options(), but also writes a JSON object
+ * with the parent frame and the chunk frames it was split into (frames whose
+ * parent_id is the parent). Content too small to chunk has no chunk frames.
+ * For a put deduplicated by idempotency_key, the earlier put's frames are
+ * reported.
+ *
+ * memvid-core adds chunk frames to its frame table only on commit, so the
+ * put is committed before its chunks are listed. To avoid committing
+ * anything else, the call fails with FeatureUnavailable, staging nothing,
+ * while other changes are staged or indexing is paused.
+ *
+ * Result JSON:
+ *   {"parent_id": 12, "chunk_ids": [13, 14, 15], "chunk_count": 3}
+ *
+ * @param handle           Valid Memvid handle
+ * @param data             Pointer to content bytes
+ * @param len              Length of content in bytes
+ * @param options_json     JSON string with PutOptions (NULL for defaults)
+ * @param out_result_json  Out-parameter for the result JSON
+ * @param error            Out-parameter for error information (may be NULL)
+ *
+ * @return Parent frame ID on success, 0 on failure.
+ *         Caller must free *out_result_json with memvid_string_free().
+ */
+uint64_t memvid_put_bytes_detailed(MemvidHandle *handle,
+                                   const uint8_t *data,
+                                   size_t len,
+                                   const char *options_json,
+                                   char **out_result_json,
+                                   MemvidError *error);
 
 /**
- * Search the memory.
+ * Estimate what a put would add, without changing the memory.
+ *
+ * The content is put with the same options (merged over the default put
+ * options) and committed into a scratch memory in a fresh temp directory,
+ * removed afterwards, so memvid-core's chunking, compression and the
+ * registered embedder decide the result. The memory's own file and
+ * directory are never touched. A put its idempotency_key would deduplicate
+ * is estimated at zero frames. Works on read-only handles.
+ *
+ * estimated_stored_bytes is how much memvid-core's size_bytes (see
+ * memvid_stats()) grew when the put was committed to the empty scratch
+ * memory: compressed payloads plus index and manifest entries.
+ * would_exceed_capacity compares it with the remaining capacity, so a put
+ * estimated just under the limit may still fail with CapacityExceeded.
  *
  * @param handle        Valid Memvid handle
- * @param request_json  JSON string with search parameters
+ * @param data          Pointer to content bytes
+ * @param len           Length of content in bytes
+ * @param options_json  JSON put options, or NULL for defaults
  * @param error         Out-parameter for error information (may be NULL)
  *
- * @return JSON string with search results on success, NULL on failure.
+ * @return JSON string on success, NULL on failure.
  *         Caller must free with memvid_string_free().
  *
- * Request JSON Schema:
- * {
- *   "query": "search text",
- *   "top_k": 10,
- *   "offset": 0,
- *   "track": "optional-track",
- *   "mode": "lex|vec|hybrid"
- * }
- *
  * Response JSON Schema:
  * {
- *   "hits": [
- *     {
- *       "frame_id": 1,
- *       "score": 0.95,
- *       "snippet": "matched text...",
- *       "uri": "optional-uri",
- *       "title": "optional-title"
- *     }
- *   ],
- *   "total": 100
+ *   "estimated_frames": 4,
+ *   "estimated_stored_bytes": 18432,
+ *   "would_exceed_capacity": false
  * }
  */
-char *memvid_search(MemvidHandle *handle,
-                    const char *request_json,
-                    MemvidError *error);
+char *memvid_estimate_put(MemvidHandle *handle,
+                          const uint8_t *data,
+                          size_t len,
+                          const char *options_json,
+                          MemvidError *error);
 
 /**
- * Free a string returned by memvid functions.
+ * Add the contents of a file with options (JSON configuration).
  *
- * @param str  String to free (safe to pass NULL)
+ * The file is memory-mapped rather than read into a buffer, so large files
+ * are paged in by the OS instead of being copied into memory first. The
+ * file must not be modified while the call runs.
+ *
+ * @param handle        Valid Memvid handle
+ * @param path          Path of the file to add (null-terminated UTF-8 string)
+ * @param options_json  JSON string with put options (NULL for defaults)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Frame ID on success, 0 on failure (NullPointer if path is NULL,
+ *         Io with the OS error text if the file cannot be opened).
  */
-void memvid_string_free(char *str);
-
-/* ============================================================================
- * State Query Functions
- * ============================================================================ */
+uint64_t memvid_put_file(MemvidHandle *handle,
+                         const char *path,
+                         const char *options_json,
+                         MemvidError *error);
 
 /**
- * Get memory statistics.
+ * Add many items in one call.
  *
- * @param handle  Valid Memvid handle
- * @param stats   Out-parameter for statistics (must not be NULL)
- * @param error   Out-parameter for error information (may be NULL)
+ * Each item is put exactly as memvid_put_bytes() (NULL options_json) or
+ * memvid_put_bytes_with_options() would, in order. Nothing is committed.
+ * On the first failing item the error is set and the batch stops; earlier
+ * items stay put (uncommitted) and are reported through out_count and the
+ * returned array, so the caller can retry from item *out_count.
  *
- * @return 1 on success, 0 on failure.
+ * @param handle     Valid Memvid handle
+ * @param items      Array of count items
+ * @param count      Number of items
+ * @param out_count  Out-parameter for the number of items put (must not be NULL)
+ * @param error      Out-parameter for error information (may be NULL)
+ *
+ * @return Array of *out_count frame IDs parallel to items (all of them on
+ *         success), or NULL if no items were put.
+ *         Caller must free with memvid_u64_array_free(ptr, *out_count).
  */
-int memvid_stats(MemvidHandle *handle, MemvidStats *stats, MemvidError *error);
+uint64_t *memvid_put_batch(MemvidHandle *handle,
+                           const MemvidPutItem *items,
+                           size_t count,
+                           size_t *out_count,
+                           MemvidError *error);
 
 /**
- * Get the number of frames in the memory.
+ * Free an array of u64 values returned by the FFI layer.
+ *
+ * @param ptr  Array to free (safe to pass NULL)
+ * @param len  Length reported alongside ptr
+ */
+void memvid_u64_array_free(uint64_t *ptr, size_t len);
+
+/**
+ * Commit pending changes to disk.
  *
  * @param handle  Valid Memvid handle
  * @param error   Out-parameter for error information (may be NULL)
  *
- * @return Frame count on success, 0 on error
- *         (check error->code to distinguish from an empty memory).
+ * @return 1 on success, 0 on failure.
  */
-uint64_t memvid_frame_count(MemvidHandle *handle, MemvidError *error);
-
-/* ============================================================================
- * Frame Retrieval Functions
- * ============================================================================ */
+int memvid_commit(MemvidHandle *handle, MemvidError *error);
 
 /**
- * Get frame metadata by ID.
+ * Commit pending changes, reporting what the commit wrote.
  *
- * @param handle    Valid Memvid handle
- * @param frame_id  Frame identifier (0-indexed)
- * @param error     Out-parameter for error information (may be NULL)
+ * Same as memvid_commit(), but also writes a JSON object describing the
+ * commit, so a host can enforce a disk quota without a memvid_stats() round
+ * trip. memvid-core does not report the bytes a commit writes, so
+ * bytes_written is the growth of the file (0 if it did not grow);
+ * wal_truncated is true if the commit checkpointed the WAL, shrinking it.
+ * frames_added counts the puts committed, not their chunk frames.
  *
- * @return JSON string with frame metadata on success, NULL on failure.
- *         Caller must free with memvid_string_free().
+ * Result JSON:
+ *   {"frames_added": 3, "bytes_written": 65536, "wal_truncated": true}
+ *
+ * @param handle           Valid Memvid handle
+ * @param out_result_json  Out-parameter for the result JSON
+ * @param error            Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ *         Caller must free *out_result_json with memvid_string_free().
  */
-char *memvid_frame_by_id(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+int memvid_commit_detailed(MemvidHandle *handle, char **out_result_json, MemvidError *error);
 
 /**
- * Get frame metadata by URI.
+ * Fsync the bytes already written to the memory file, without committing.
+ *
+ * memvid-core has no durability step short of a commit and does not expose
+ * its WAL writer, so WAL entries core still buffers in memory are not
+ * flushed; only bytes already in the file are synced. Staged changes are
+ * durable only once memvid_commit() returns. Nothing is checkpointed, so
+ * staged puts stay invisible to search until memvid_commit(). A no-op on a
+ * read-only handle.
  *
  * @param handle  Valid Memvid handle
- * @param uri     Frame URI (null-terminated UTF-8 string)
  * @param error   Out-parameter for error information (may be NULL)
  *
- * @return JSON string with frame metadata on success, NULL on failure.
- *         Caller must free with memvid_string_free().
+ * @return 1 on success, 0 on failure (Io if the file cannot be synced).
+ */
+int memvid_flush(MemvidHandle *handle, MemvidError *error);
+
+/**
+ * Commit progress callback.
+ *
+ * phase is 0 (about to commit) or 1 (commit finished); done and total count
+ * staged puts. Returning nonzero from phase 0 cancels the commit; the
+ * return value of phase 1 is ignored.
+ */
+typedef int (*MemvidCommitProgressFn)(uint32_t phase, uint64_t done, uint64_t total, void *user_data);
+
+/**
+ * Commit pending changes to disk, reporting progress to a callback.
+ *
+ * memvid-core flushes the WAL, merges the index, and writes the manifest in
+ * a single call with no intermediate progress, so the callback brackets it:
+ * phase 0 with done = 0 before, phase 1 with done = total after success.
+ * total is the number of puts staged on this handle since the last commit.
+ * Cancelling in phase 0 fails with Cancelled and leaves the puts staged.
+ *
+ * The callback runs on the calling thread. Calls back into handle from the
+ * callback fail with InvalidHandle.
+ *
+ * @param handle       Valid Memvid handle
+ * @param progress_fn  Progress callback (must not be NULL)
+ * @param user_data    Opaque pointer passed through to progress_fn
+ * @param error        Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure or cancellation.
+ */
+int memvid_commit_with_progress(MemvidHandle *handle,
+                                MemvidCommitProgressFn progress_fn,
+                                void *user_data,
+                                MemvidError *error);
+
+/**
+ * Pause or resume indexing for a burst of writes.
+ *
+ * memvid-core indexes frames when they are committed and has no separate
+ * indexing switch, so pausing holds back the commits the handle makes on
+ * its own: autocommit (memvid_set_autocommit()) does not run while paused.
+ * Puts are staged as usual and, like any uncommitted put, are invisible to
+ * search, which reports "index_stale": true. Resuming commits the staged
+ * puts, building their index entries in one catch-up pass.
+ *
+ * memvid_commit() stays allowed while paused; since memvid-core cannot
+ * commit without indexing, it indexes everything staged so far.
+ *
+ * @param handle  Valid Memvid handle
+ * @param paused  Nonzero to pause, 0 to resume
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure (indexing stays paused if the
+ *         catch-up commit fails).
+ */
+int memvid_set_indexing_paused(MemvidHandle *handle, int paused, MemvidError *error);
+
+/**
+ * Commit automatically once enough puts are staged.
+ *
+ * After a put brings the puts staged since the last commit to every_n_puts,
+ * the handle commits inline. No autocommit runs while indexing is paused.
+ *
+ * A failed autocommit does not fail the put that triggered it; the commit
+ * error is returned by the next put, which stages nothing. The threshold
+ * puts stay staged for retry by the following put or memvid_commit().
+ *
+ * @param handle        Valid Memvid handle
+ * @param every_n_puts  Pending puts that trigger a commit; 0 disables (default)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_set_autocommit(MemvidHandle *handle, uint64_t every_n_puts, MemvidError *error);
+
+/**
+ * Set options applied to every put on the handle.
+ *
+ * memvid_put_bytes(), memvid_put_bytes_with_options(),
+ * memvid_put_bytes_idempotent(), memvid_put_bytes_detailed(),
+ * memvid_put_batch(), memvid_put_file(), and memvid_put_image() merge these
+ * under their own options: a top-level field set in a call's options_json
+ * replaces the default's (null clears it), so a call's "tags" or "labels"
+ * replace the defaults' rather than adding to them. JSONL import and merge
+ * ignore the defaults.
+ *
+ * Errors: JsonParse if options_json is not a JSON object of put options;
+ * SchemaValidation for invalid options, including an "idempotency_key"
+ * (every put would share it).
+ *
+ * @param handle        Valid Memvid handle
+ * @param options_json  JSON put options (as memvid_put_bytes_with_options()),
+ *                      or NULL to clear
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure (the previous defaults stay).
+ */
+int memvid_set_default_put_options(MemvidHandle *handle,
+                                   const char *options_json,
+                                   MemvidError *error);
+
+/* ============================================================================
+ * Search Functions
+ * ============================================================================ */
+
+/**
+ * Search the memory.
+ *
+ * @param handle        Valid Memvid handle
+ * @param request_json  JSON string with search parameters
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with search results on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Request JSON Schema:
+ * {
+ *   "query": "search text",
+ *   "top_k": 10,
+ *   "snippet_chars": 200,
+ *   "uri": "mv2://optional/filter",
+ *   "scope": "mv2://scope/prefix",
+ *   "cursor": "pagination_token",
+ *   "field_boosts": {"title": 3.0, "body": 1.0},
+ *   "compress_context": false,
+ *   "timeout_ms": 500,
+ *   "include_tracks": ["email", "chat"],
+ *   "exclude_tracks": ["logs"],
+ *   "tags": {"topic": "rust"},
+ *   "labels": ["reviewed"],
+ *   "min_score": 0.2,
+ *   "pinned_frames": [12, 7],
+ *   "pin_unmatched": false,
+ *   "count_unfiltered": false,
+ *   "byte_offsets": false,
+ *   "mode": "lex",
+ *   "query_embedding": [0.12, -0.03, 0.88],
+ *   "as_of_frame": null,
+ *   "as_of_ts": null,
+ *   "no_sketch": false,
+ *   "language": "en",
+ *   "titles_only": false,
+ *   "context_budget_chars": 8000
+ * }
+ *
+ * titles_only (or "snippet_chars": 0) is a fast path for typeahead: no
+ * snippet text is extracted, and every hit has an empty "text", a "range"
+ * of [0, 0], no highlights, and a null payload_range; "context" is empty.
+ * IDs, titles, URIs, scores, and metadata are unaffected.
+ *
+ * language names the analyzer the query is meant for. memvid-core builds
+ * its lexical index with one fixed analyzer (English, "en"), so any other
+ * language fails with InvalidQuery, listing the available analyzers,
+ * rather than silently searching with the default.
+ *
+ * context_budget_chars caps "context" at that many characters. Hit texts
+ * are joined in rank order and cut on hit boundaries: the first hit that
+ * would overflow and every lower-ranked hit are left out of "context" (they
+ * stay in "hits"). Only a top hit longer than the whole budget is cut
+ * mid-text. "context_truncated" reports whether anything was left out.
+ *
+ * as_of_frame and as_of_ts run the search against the memory as it was at
+ * that frame ID or Unix timestamp (seconds), as in memvid_ask(). Vector
+ * hits are limited to frames with an ID and timestamp up to the bounds.
+ *
+ * no_sketch disables memvid-core's sketch-based candidate prefilter, so
+ * lexical retrieval scores the full index; slower, for ground-truth
+ * comparisons such as recall benchmarks.
+ *
+ * mode: "lex" (default), "sem", or "hybrid". sem and hybrid require the vec
+ * feature (VecNotEnabled otherwise). Without query_embedding, the query is
+ * embedded by the embedder registered with memvid_register_embedder(); with
+ * neither, sem fails with InvalidQuery. sem returns the nearest active
+ * frames with the start of the frame text, "matches": 0, score
+ * 1 / (1 + distance), and no next_cursor. hybrid with an embedding fuses lexical and vector hits by
+ * reciprocal rank (1 / (60 + rank) per engine); without one it runs
+ * lexically. "engine" reports what ran: the lexical engine's name,
+ * "Vector", or "Hybrid(<lexical>+Vector)". count_unfiltered always counts
+ * lexical hits.
+ *
+ * include_tracks keeps only hits on the listed tracks (hits without a track
+ * are dropped); exclude_tracks drops hits on the listed tracks. Tracks are
+ * filtered by the FFI layer over a pool of top_k * 4 hits, so total_hits
+ * counts matches within that pool and context is rebuilt from kept hits.
+ *
+ * tags keeps only hits carrying all of the given tags, in the same
+ * {"key": "value"} form as put options; labels keeps only hits with at least
+ * one of the listed labels. Like tracks, they are filtered by the FFI layer
+ * over the top_k * 4 pool.
+ *
+ * min_score drops hits scoring below the threshold (after field_boosts);
+ * hits without a score are kept. It filters after ranking without fetching
+ * more, so fewer than top_k hits may return. A pinned frame below the
+ * threshold is only shown with pin_unmatched.
+ *
+ * pinned_frames moves the listed frames to the top of the hits, in list
+ * order, with remaining slots filled normally. By default a pinned frame is
+ * only shown if it matches the query (within the top_k * 4 pool, after track
+ * filters). With "pin_unmatched": true every active pinned frame is shown
+ * regardless of query and filters; unmatched ones carry the start of the
+ * frame text, "matches": 0 and a null score. Pinned hits count toward top_k.
+ *
+ * count_unfiltered runs the query a second time without uri, scope, track,
+ * tag, and label filters and reports that run's total_hits as "unfiltered_total"
+ * (e.g. "12 of 340 matches"). It is null unless requested with at least one
+ * of those filters set, and is skipped when timeout_ms expires.
+ *
+ * byte_offsets adds "payload_range", the hit's range as [start, end) byte
+ * offsets into the stored payload. memvid-core keeps no mapping from
+ * extracted text back to original bytes, so it is only available when the
+ * payload is UTF-8 text identical to the frame text; otherwise (e.g. PDF),
+ * and without byte_offsets, payload_range is null.
+ *
+ * "index_stale" is true while puts are staged on this handle since its last
+ * commit, whether or not indexing is paused (see
+ * memvid_set_indexing_paused()); those puts are missing from the hits.
+ *
+ * compress_context is only supported by memvid_search_bytes(); setting it
+ * here fails with InvalidQuery.
+ *
+ * timeout_ms is checked cooperatively once retrieval returns (memvid-core
+ * retrieval cannot be interrupted). On expiry "timed_out" is true and
+ * optional post-processing is skipped: field_boosts, highlights,
+ * count_unfiltered and byte_offsets. Track, tag and label filters,
+ * min_score (against unboosted scores) and pinned_frames still apply.
+ *
+ * field_boosts keys: "title", "body", "uri", "tags", "labels". Boosts are
+ * applied by the FFI layer over a pool of top_k * 4 hits: each score is
+ * multiplied by the largest boost among fields containing every query term.
+ *
+ * "highlights" lists [start, end) character offsets within the hit's text
+ * of words equal to a query term, ignoring case. memvid-core does not
+ * expose its match positions, so terms are located in the snippet by the
+ * FFI layer; stemmed or fuzzy matches are not highlighted. Hits without
+ * keyword matches (vector and pinned hits) have an empty array.
+ *
+ * "cursor" takes the "next_cursor" of an earlier response on the same
+ * handle. Cursors are invalidated by memvid_refresh() and memvid_vacuum();
+ * a stale or malformed cursor fails with InvalidCursor. With track, tag or
+ * label filters, field_boosts, or pinned_frames, pages are cut by the FFI
+ * layer from a pool grown to cover every page so far, and context holds
+ * only the returned hits; keep those parameters the same across pages.
+ *
+ * Response JSON Schema:
+ * {
+ *   "query": "search text",
+ *   "elapsed_ms": 42,
+ *   "total_hits": 100,
+ *   "hits": [
+ *     {
+ *       "rank": 1,
+ *       "frame_id": 42,
+ *       "uri": "mv2://doc.txt",
+ *       "title": "Document Title",
+ *       "text": "...matching text...",
+ *       "range": [100, 150],
+ *       "payload_range": null,
+ *       "matches": 3,
+ *       "highlights": [[3, 11]],
+ *       "score": 0.95,
+ *       "tags": ["tag1"],
+ *       "labels": ["label1"],
+ *       "track": "email"
+ *     }
+ *   ],
+ *   "context": "combined context text",
+ *   "next_cursor": "token_or_null",
+ *   "engine": "Tantivy",
+ *   "timed_out": false,
+ *   "unfiltered_total": null,
+ *   "index_stale": false,
+ *   "context_truncated": false
+ * }
+ */
+char *memvid_search(MemvidHandle *handle,
+                    const char *request_json,
+                    MemvidError *error);
+
+/**
+ * Search the memory, returning a binary buffer.
+ *
+ * Accepts the same request as memvid_search(). With "compress_context": true
+ * the combined context and each hit's text are zstd-compressed.
+ *
+ * @param handle        Valid Memvid handle
+ * @param request_json  JSON string with search parameters
+ * @param out_len       Out-parameter for the buffer length in bytes
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Buffer on success, NULL on failure.
+ *         Caller must free with memvid_bytes_free(buf, *out_len).
+ *
+ * Buffer format:
+ *   offset 0      u32 little-endian envelope length N
+ *   offset 4      N bytes of envelope JSON (UTF-8)
+ *   offset 4 + N  payload, through the end of the buffer
+ *
+ * The envelope is the memvid_search() response with "context" and each
+ * hit's "text" set to "", plus a "payload" object:
+ * {
+ *   "codec": "zstd",
+ *   "uncompressed_len": 5120,
+ *   "context": [0, 2048],
+ *   "texts": [[2048, 1536], [3584, 1536]]
+ * }
+ *
+ * Decoding: for codec "zstd" the payload is a single zstd frame that
+ * decompresses to exactly uncompressed_len bytes (ZSTD_decompress into a
+ * buffer of that size); for codec "none" it is the raw bytes. "context" and
+ * texts[i] are [offset, len] byte ranges of UTF-8 text in the decoded
+ * payload; texts[i] belongs to hits[i].
+ */
+uint8_t *memvid_search_bytes(MemvidHandle *handle,
+                             const char *request_json,
+                             size_t *out_len,
+                             MemvidError *error);
+
+/**
+ * Search the memory, writing the response JSON into a caller buffer.
+ *
+ * Same request and JSON as memvid_search(), without allocating the result.
+ * The JSON is written null-terminated and *needed_len is set to its size
+ * including the terminator. If that exceeds out_buf_len, nothing is written
+ * and the call fails with BufferTooSmall; grow the buffer to *needed_len
+ * and call again. Pass out_buf = NULL with out_buf_len = 0 to query the
+ * size. Each call runs the search anew.
+ *
+ * @param handle        Valid Memvid handle
+ * @param request_json  JSON string with search parameters
+ * @param out_buf       Caller-owned buffer (may be NULL if out_buf_len is 0)
+ * @param out_buf_len   Size of out_buf in bytes
+ * @param needed_len    Out-parameter for the response size, including the terminator
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure (BufferTooSmall if out_buf is too small).
+ */
+int memvid_search_into(MemvidHandle *handle,
+                       const char *request_json,
+                       char *out_buf,
+                       size_t out_buf_len,
+                       size_t *needed_len,
+                       MemvidError *error);
+
+/**
+ * Search hit callback.
+ *
+ * Receives one hit as a JSON object (same shape as an element of
+ * memvid_search()'s "hits"), valid only during the call.
+ * Return 0 to continue, nonzero to stop.
+ */
+typedef int (*MemvidSearchHitFn)(const char *hit_json, void *user_data);
+
+/**
+ * Search the memory, passing each hit to a callback.
+ *
+ * Accepts the same request as memvid_search(), but serializes hits one at a
+ * time and passes each to hit_fn in rank order, so large result sets can be
+ * processed incrementally. Returning nonzero from hit_fn stops early; that
+ * is not an error.
+ *
+ * hit_fn runs synchronously on the calling thread, before this function
+ * returns. It must not call back into the handle: such calls fail with
+ * InvalidHandle.
+ *
+ * @param handle        Valid Memvid handle
+ * @param request_json  JSON string with SearchRequest
+ * @param hit_fn        Callback invoked once per hit (must not be NULL)
+ * @param user_data     Opaque pointer passed through to hit_fn
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success (including an early stop), 0 on failure
+ *         (InvalidQuery if compress_context is set).
+ */
+int memvid_search_stream(MemvidHandle *handle,
+                         const char *request_json,
+                         MemvidSearchHitFn hit_fn,
+                         void *user_data,
+                         MemvidError *error);
+
+/**
+ * Search at two points in time and return the difference.
+ *
+ * Runs the search as of as_of_a and as of as_of_b and compares hit sets by
+ * frame ID. Only the top top_k hits of each run are compared. A hit in b
+ * whose URI matched a different frame in a (an upsert) is reported in
+ * "changed" rather than as an add/remove pair.
+ *
+ * Both snapshots run through the same filtering, scoring and pinning as
+ * memvid_search(), so its other request fields apply to each. "cursor" and
+ * "compress_context" fail with InvalidQuery.
+ *
+ * @param handle        Valid Memvid handle
+ * @param request_json  JSON string with search parameters plus as_of_a/as_of_b
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with the diff on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Request JSON Schema:
+ * {
+ *   "query": "search terms",
+ *   "top_k": 100,
+ *   "as_of_a": {"ts": 1700000000},
+ *   "as_of_b": {"frame": 420}
+ * }
+ *
+ * Response JSON Schema:
+ * {
+ *   "query": "search terms",
+ *   "as_of_a": {...}, "as_of_b": {...},
+ *   "added": [hit, ...],
+ *   "removed": [hit, ...],
+ *   "changed": [{"before": hit, "after": hit}],
+ *   "unchanged": [12, 40]
+ * }
+ */
+char *memvid_search_diff(MemvidHandle *handle,
+                         const char *request_json,
+                         MemvidError *error);
+
+/**
+ * Run several searches in one call.
+ *
+ * Each element of requests_json is a memvid_search() request, run
+ * independently and in order. The response array is parallel to it: each
+ * slot holds that request's memvid_search() response, or, if the request
+ * failed, {"error": {"code": 46, "name": "InvalidQuery", "message": "..."}}
+ * with the MemvidErrorCode it would have failed with on its own. A failed
+ * slot does not stop the others.
+ *
+ * Errors: JsonParse if requests_json is not a JSON array.
+ *
+ * @param handle         Valid Memvid handle
+ * @param requests_json  JSON array of search requests
+ * @param error          Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array of responses on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_search_multi(MemvidHandle *handle,
+                          const char *requests_json,
+                          MemvidError *error);
+
+/**
+ * Free a string returned by memvid functions.
+ *
+ * @param str  String to free (safe to pass NULL)
+ */
+void memvid_string_free(char *str);
+
+/**
+ * Free a byte buffer returned by memvid functions.
+ *
+ * @param ptr  Buffer to free (safe to pass NULL)
+ * @param len  Length reported alongside the buffer
+ */
+void memvid_bytes_free(uint8_t *ptr, size_t len);
+
+/* ============================================================================
+ * State Query Functions
+ * ============================================================================ */
+
+/**
+ * Get memory statistics.
+ *
+ * @param handle  Valid Memvid handle
+ * @param stats   Out-parameter for statistics (must not be NULL)
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_stats(MemvidHandle *handle, MemvidStats *stats, MemvidError *error);
+
+/**
+ * Get memory statistics as JSON.
+ *
+ * Serializes memvid-core's full Stats, including fields not mirrored in
+ * MemvidStats, so new core metrics appear without an ABI change. Keys are
+ * core's field names (those in MemvidStats match); ignore unknown keys.
+ * Prefer memvid_stats() on hot paths.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return JSON object on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_stats_json(MemvidHandle *handle, MemvidError *error);
+
+/**
+ * Get the number of frames in the memory.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return Frame count on success, 0 on error
+ *         (check error->code to distinguish from an empty memory).
+ *         Prefer memvid_frame_count_checked(), whose return is unambiguous.
+ */
+uint64_t memvid_frame_count(MemvidHandle *handle, MemvidError *error);
+
+/**
+ * Get the number of frames in the memory, with an explicit success flag.
+ *
+ * @param handle     Valid Memvid handle
+ * @param out_count  Out-parameter for the frame count
+ * @param error      Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_frame_count_checked(MemvidHandle *handle, uint64_t *out_count, MemvidError *error);
+
+/**
+ * Check that a handle can serve reads.
+ *
+ * A cheap readiness probe: confirms the handle is open, then re-reads the
+ * file header from disk, so storage that became inaccessible (e.g. an
+ * unmounted volume or a deleted file) is detected. Unlike memvid_stats(),
+ * it does not touch the indexes.
+ *
+ * Errors: InvalidHandle for a closed handle; Io if the file can no longer
+ * be read; InvalidHeader if it no longer starts with a valid header.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return 1 if the handle can serve reads, 0 otherwise.
+ */
+int memvid_ping(MemvidHandle *handle, MemvidError *error);
+
+/**
+ * Get a change marker for the memory.
+ *
+ * Returns the latest WAL sequence number seen on this handle: at least the
+ * highest value returned by a mutation through it (memvid_delete_frame(),
+ * memvid_update_frame(), ...), and strictly greater after every later put,
+ * delete, update, refresh, or vacuum. Before any change it is the handle's
+ * generation at open, 0. Compare values across polls to detect changes.
+ *
+ * memvid-core does not expose its WAL position, so only changes made or
+ * observed by this handle count: writes by another process show up after
+ * memvid_refresh(). Values are not comparable across handles.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return Sequence number on success, 0 on failure (check error).
+ */
+uint64_t memvid_current_sequence(MemvidHandle *handle, MemvidError *error);
+
+/**
+ * Get stats snapshots at commit points.
+ *
+ * memvid-core does not retain commit history, so only the current snapshot
+ * is available: the result is always a single-element array describing the
+ * memory as of its last write. "seq" is null (core does not expose commit
+ * sequence numbers) and "timestamp" is the file's modification time.
+ * since_seq does not filter the current snapshot.
+ *
+ * @param handle     Valid Memvid handle
+ * @param since_seq  Only return snapshots after this sequence number
+ * @param error      Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Response JSON Schema:
+ * [{"seq": null, "timestamp": 1700000000, "frame_count": 42, "size_bytes": 1048576}]
+ */
+char *memvid_stats_history(MemvidHandle *handle, uint64_t since_seq, MemvidError *error);
+
+/**
+ * List the tags and labels used by active frames.
+ *
+ * Counts, for each tag and label, the active frames carrying it; deleted
+ * frames are skipped. Tags are counted by name, as frames store them.
+ * Scans every frame. Works on read-only handles.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return JSON object on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Response JSON Schema (names sorted, values are frame counts):
+ * {
+ *   "tags": {"ffi": 3, "rust": 7},
+ *   "labels": {"draft": 2, "reviewed": 5}
+ * }
+ */
+char *memvid_list_tags(MemvidHandle *handle, MemvidError *error);
+
+/* ============================================================================
+ * Frame Retrieval Functions
+ * ============================================================================ */
+
+/**
+ * Get frame metadata by ID.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with frame metadata on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frame_by_id(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
+/**
+ * Get extended frame metadata by ID, including deletion audit data.
+ *
+ * Works on deleted frames. Returns all memvid_frame_by_id() fields plus:
+ *   "extra_metadata": {"key": "value"},
+ *   "delete_reason": "retention policy",
+ *   "deleted_at": 1234567890
+ *
+ * delete_reason and deleted_at are null unless the frame was deleted with
+ * memvid_delete_frame_with_reason().
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with frame metadata on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frame_by_id_ex(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
+/**
+ * Check whether an active frame exists.
+ *
+ * Reads the frame's table-of-contents entry only, without touching its
+ * payload or serializing metadata, so it is cheap enough for validating
+ * many references. Tombstoned frames count as missing; use
+ * memvid_frame_exists_ex() to include them.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return 1 if the frame exists and is active, 0 if not, -1 on failure.
+ */
+int memvid_frame_exists(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
+/**
+ * Check whether a frame exists, optionally counting tombstoned frames.
+ *
+ * Same as memvid_frame_exists(); with include_deleted nonzero, deleted
+ * frames count as existing too.
+ *
+ * @param handle           Valid Memvid handle
+ * @param frame_id         Frame identifier (0-indexed)
+ * @param include_deleted  Nonzero to also report tombstoned frames
+ * @param error            Out-parameter for error information (may be NULL)
+ *
+ * @return 1 if the frame exists, 0 if not, -1 on failure.
+ */
+int memvid_frame_exists_ex(MemvidHandle *handle,
+                           uint64_t frame_id,
+                           int include_deleted,
+                           MemvidError *error);
+
+/**
+ * Get frame metadata by URI.
+ *
+ * @param handle  Valid Memvid handle
+ * @param uri     Frame URI (null-terminated UTF-8 string)
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with frame metadata on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
  */
 char *memvid_frame_by_uri(MemvidHandle *handle, const char *uri, MemvidError *error);
 
+/**
+ * List the active frames on a track, oldest first.
+ *
+ * Scans every frame's metadata (memvid-core keeps no index by track).
+ * Frames are ordered by timestamp, then ID.
+ *
+ * @param handle  Valid Memvid handle
+ * @param track   Track name (UTF-8 encoded, null-terminated)
+ * @param limit   Maximum number of frames to return (0 for no limit)
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array of frame metadata (as in memvid_frame_by_id()) on
+ *         success, NULL on failure (InvalidQuery if track is NULL or empty).
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frames_by_track(MemvidHandle *handle,
+                             const char *track,
+                             uint32_t limit,
+                             MemvidError *error);
+
+/**
+ * List the active frames within a time range, oldest first.
+ *
+ * Uses the time index, like memvid_timeline(), but returns full frame
+ * metadata instead of previews. Frames are ordered by timestamp, then ID.
+ * A range with since after until, or without frames, gives an empty array.
+ *
+ * @param handle  Valid Memvid handle
+ * @param since   Timestamp lower bound (inclusive, Unix seconds)
+ * @param until   Timestamp upper bound (inclusive, Unix seconds)
+ * @param limit   Maximum number of frames to return (0 for no limit)
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array of frame metadata (as in memvid_frame_by_id()) on
+ *         success, NULL on failure. Caller must free with memvid_string_free().
+ */
+char *memvid_frames_in_range(MemvidHandle *handle,
+                             int64_t since,
+                             int64_t until,
+                             uint32_t limit,
+                             MemvidError *error);
+
+/**
+ * Get the metadata of several frames in one call.
+ *
+ * Returns one entry per ID, in the order given. Missing and tombstoned
+ * frames give null entries rather than failing the call.
+ *
+ * @param handle  Valid Memvid handle
+ * @param ids     Array of frame identifiers (may be NULL if count is 0)
+ * @param count   Number of IDs in ids
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array of frame metadata (as in memvid_frame_by_id()) or null,
+ *         one per ID, on success; NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frames_by_ids(MemvidHandle *handle,
+                           const uint64_t *ids,
+                           size_t count,
+                           MemvidError *error);
+
+/**
+ * List the chunk frames of a document, in chunk order.
+ *
+ * Scans every frame's metadata (memvid-core keeps no parent-to-child index).
+ * Chunks are listed whatever their status, ordered by chunk_index. Chunks
+ * of a staged put are listed once it is committed.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Parent frame identifier (0-indexed)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array of frame metadata (as in memvid_frame_by_id()) on
+ *         success, empty for a frame without chunks, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frame_children(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
+/**
+ * Get the parent document of a chunk frame.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with the parent's metadata (as in memvid_frame_by_id()),
+ *         or NULL with Ok if the frame has no parent. NULL with an error code
+ *         on failure. Caller must free with memvid_string_free().
+ */
+char *memvid_frame_parent(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
+/**
+ * Get the raw payload bytes of a frame.
+ *
+ * Unlike memvid_frame_content(), the payload is returned with an explicit
+ * length, so content with embedded null bytes round-trips intact.
+ * Compressed payloads are returned decompressed.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param out_ptr   Out-parameter for the payload buffer
+ * @param out_len   Out-parameter for the payload length in bytes
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ *         Caller must free *out_ptr with memvid_bytes_free(*out_ptr, *out_len).
+ */
+int memvid_frame_payload(MemvidHandle *handle,
+                         uint64_t frame_id,
+                         uint8_t **out_ptr,
+                         size_t *out_len,
+                         MemvidError *error);
+
+/**
+ * Get a byte range of a frame's text content.
+ *
+ * Lets a host page through a long frame instead of receiving its whole text
+ * in one allocation. Offsets are byte offsets into the UTF-8 text returned
+ * by memvid_frame_content(), so a range may start or end inside a
+ * multi-byte character. A range starting at or past the end gives a
+ * zero-length buffer with Ok. memvid-core decodes frame text as a whole,
+ * so the library still holds the full text briefly; only the range is
+ * copied out.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param offset    Byte offset of the first byte to return
+ * @param max_len   Maximum number of bytes to return
+ * @param out_ptr   Out-parameter for the range buffer
+ * @param out_len   Out-parameter for the range length in bytes
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ *         Caller must free *out_ptr with memvid_bytes_free(*out_ptr, *out_len).
+ */
+int memvid_frame_content_range(MemvidHandle *handle,
+                               uint64_t frame_id,
+                               uint64_t offset,
+                               size_t max_len,
+                               uint8_t **out_ptr,
+                               size_t *out_len,
+                               MemvidError *error);
+
+/**
+ * Get the binary sidecar attached to a frame.
+ *
+ * Returns the bytes stored with the "sidecar_base64" put option. A frame
+ * without a sidecar succeeds with *out_ptr = NULL and *out_len = 0.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param out_ptr   Out-parameter for the sidecar buffer
+ * @param out_len   Out-parameter for the sidecar length in bytes
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ *         Caller must free a non-NULL *out_ptr with memvid_bytes_free(*out_ptr, *out_len).
+ */
+int memvid_frame_sidecar(MemvidHandle *handle,
+                         uint64_t frame_id,
+                         uint8_t **out_ptr,
+                         size_t *out_len,
+                         MemvidError *error);
+
+/**
+ * Get how a frame's payload is stored.
+ *
+ * Read-only diagnostic for finding documents that compress poorly; sizes
+ * come from the frame's manifest entry. codec is "zstd" or "plain"; ratio
+ * is stored_bytes / payload_length (lower compresses better, 1.0 for plain).
+ *
+ * JSON Schema:
+ * {
+ *   "payload_length": 4096,
+ *   "stored_bytes": 1024,
+ *   "codec": "zstd",
+ *   "ratio": 0.25
+ * }
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frame_storage_info(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
 /**
  * Get frame text content by ID.
  *
+ * Errors: InteriorNul if the text contains a null byte; read it with
+ * memvid_frame_payload() instead.
+ *
  * @param handle    Valid Memvid handle
  * @param frame_id  Frame identifier (0-indexed)
  * @param error     Out-parameter for error information (may be NULL)
@@ -444,56 +1790,480 @@ char *memvid_frame_by_uri(MemvidHandle *handle, const char *uri, MemvidError *er
  */
 char *memvid_frame_content(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
 
+/**
+ * Get frame text content by URI.
+ *
+ * Looks up the frame as memvid_frame_by_uri() does. For a chunked document
+ * this returns the parent frame's full content, not its chunks joined; a
+ * chunk frame matched by URI resolves to its parent.
+ *
+ * Errors: FrameNotFoundByUri if no frame has the URI; InteriorNul if the
+ * text contains a null byte.
+ *
+ * @param handle  Valid Memvid handle
+ * @param uri     Frame URI (UTF-8, null-terminated)
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return Frame text content on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frame_content_by_uri(MemvidHandle *handle, const char *uri, MemvidError *error);
+
 /**
  * Soft-delete a frame.
  *
- * @param handle    Valid Memvid handle
- * @param frame_id  Frame identifier (0-indexed)
- * @param error     Out-parameter for error information (may be NULL)
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return WAL sequence number on success, 0 on failure.
+ */
+uint64_t memvid_delete_frame(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
+/**
+ * Soft-delete every frame with a URI.
+ *
+ * Tombstones each active frame whose URI is uri, together with its chunk
+ * frames. Finding them scans all frames. The deletes are staged together and
+ * take effect on memvid_commit(); if one fails, those before it stay staged.
+ *
+ * @param handle  Valid Memvid handle
+ * @param uri     URI to delete (null-terminated UTF-8 string)
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return Highest WAL sequence number of the deletes on success, 0 on failure
+ *         (FrameNotFoundByUri if no active frame has the URI).
+ */
+uint64_t memvid_delete_frame_by_uri(MemvidHandle *handle, const char *uri, MemvidError *error);
+
+/**
+ * Soft-delete a frame, recording why.
+ *
+ * Also stages an audit frame holding the reason, readable with
+ * memvid_frame_by_id_ex() on the deleted frame. memvid-core tombstones carry
+ * no metadata, so the audit frame is an ordinary frame on the "_deletions"
+ * track (URI mv2://_deleted/<id>, empty payload, not lexically searchable):
+ * it counts toward stats, appears in the timeline, and is kept when vacuum
+ * removes the deleted frame.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Frame identifier (0-indexed)
+ * @param reason    Deletion reason (null-terminated UTF-8 string)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return WAL sequence number of the delete on success, 0 on failure.
+ */
+uint64_t memvid_delete_frame_with_reason(MemvidHandle *handle,
+                                         uint64_t frame_id,
+                                         const char *reason,
+                                         MemvidError *error);
+
+/**
+ * Restore a soft-deleted frame.
+ *
+ * memvid-core has no way to clear a tombstone, so the frame is restored as
+ * a copy: a new frame with the deleted frame's content and metadata, plus
+ * extra_metadata["restored_from"] holding the deleted frame's ID. The
+ * deleted frame stays tombstoned. Content is re-chunked as on a put, so
+ * restore a chunked document through its parent frame. An idempotency key
+ * carried over now resolves to the copy.
+ *
+ * The copy is staged like a put and takes effect on memvid_commit(). The
+ * content must still be stored, so restore before vacuum removes it.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Identifier of the deleted frame (0-indexed)
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return ID of the restored frame on success, 0 on failure
+ *         (FrameNotDeleted if the frame is not deleted).
+ */
+uint64_t memvid_restore_frame(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
+
+/**
+ * Update a frame's metadata without re-inserting its content.
+ *
+ * Sets any of title, tags, labels, and kind; each given field replaces the
+ * stored value and the rest are kept. The frame keeps its ID and content.
+ * The update is staged like a put and takes effect on memvid_commit().
+ *
+ * Options JSON:
+ *   {"title": "...", "tags": {"key": "value"}, "labels": ["..."], "kind": "..."}
+ *
+ * @param handle        Valid Memvid handle
+ * @param frame_id      Frame identifier (0-indexed)
+ * @param options_json  JSON object with the fields to change
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return WAL sequence number on success, 0 on failure (InvalidQuery if the
+ *         JSON has any other field, such as content or search_text).
+ */
+uint64_t memvid_update_frame(MemvidHandle *handle,
+                             uint64_t frame_id,
+                             const char *options_json,
+                             MemvidError *error);
+
+/**
+ * Create a cursor over all frames, in ID order.
+ *
+ * The cursor covers the frame IDs that exist when it is created; frames
+ * added later are not visited. Each frame is read when the cursor reaches
+ * it, so a frame deleted in the meantime reflects that (and is skipped
+ * unless include_deleted is set). IDs are taken from core's frame table, so
+ * gaps left by memvid_vacuum() are not an issue.
+ *
+ * The cursor borrows handle: the handle must outlive it, and the cursor
+ * must be used on the handle's thread.
+ *
+ * @param handle           Valid Memvid handle
+ * @param include_deleted  Nonzero to also yield tombstoned frames
+ * @param error            Out-parameter for error information (may be NULL)
+ *
+ * @return Cursor on success, NULL on failure.
+ *         Caller owns the cursor. Must call memvid_frames_iter_free() to free.
+ */
+MemvidFrameIter *memvid_frames_iter_new(MemvidHandle *handle,
+                                        int include_deleted,
+                                        MemvidError *error);
+
+/**
+ * Advance a frame cursor.
+ *
+ * @param iter   Cursor from memvid_frames_iter_new()
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return JSON with the next frame's metadata (same schema as
+ *         memvid_frame_by_id()), or NULL with MemvidErrorCode_Ok at the end.
+ *         NULL with an error code on failure (InvalidCursor if the handle
+ *         was refreshed or vacuumed after the cursor was created).
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_frames_iter_next(MemvidFrameIter *iter, MemvidError *error);
+
+/**
+ * Free a frame cursor.
+ *
+ * @param iter  Cursor to free (safe to pass NULL)
+ */
+void memvid_frames_iter_free(MemvidFrameIter *iter);
+
+/* ============================================================================
+ * Vector Similarity Functions
+ * ============================================================================ */
+
+/**
+ * Find frames whose content is near-identical to a given frame.
+ *
+ * Compares the source frame's embedding against the vector index and returns
+ * every other frame with cosine similarity >= similarity_threshold, most
+ * similar first. There is no top_k: the candidate pool grows until the
+ * cutoff is reached. Requires the vec feature.
+ *
+ * @param handle                Valid Memvid handle
+ * @param frame_id              Source frame (excluded from the results)
+ * @param similarity_threshold  Minimum cosine similarity, in [-1.0, 1.0]
+ * @param error                 Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with near-duplicates on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Errors: VecNotEnabled without the vec feature; InvalidFrame if the source
+ * frame has no embedding; InvalidQuery for an out-of-range threshold.
+ *
+ * Response JSON Schema:
+ * {
+ *   "source_frame_id": 42,
+ *   "similarity_threshold": 0.95,
+ *   "duplicates": [
+ *     {"frame_id": 57, "uri": "mv2://doc-v2.txt", "title": null, "similarity": 0.98}
+ *   ]
+ * }
+ */
+char *memvid_find_near_duplicates(MemvidHandle *handle,
+                                  uint64_t frame_id,
+                                  float similarity_threshold,
+                                  MemvidError *error);
+
+/**
+ * Find the frames most similar to a given frame.
+ *
+ * Looks up the source frame's stored embedding and returns its nearest
+ * neighbors in the vector index, scored by cosine similarity, most similar
+ * first. Needs no embedder. Requires the vec feature.
+ *
+ * @param handle    Valid Memvid handle
+ * @param frame_id  Source frame (excluded from the results)
+ * @param top_k     Maximum number of frames to return
+ * @param error     Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array on success (empty if the source frame has no
+ *         embedding), NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Errors: VecNotEnabled without the vec feature; InvalidQuery if top_k is 0.
+ *
+ * Response JSON Schema:
+ * [
+ *   {"frame_id": 57, "score": 0.91}
+ * ]
+ */
+char *memvid_similar_frames(MemvidHandle *handle,
+                            uint64_t frame_id,
+                            uint32_t top_k,
+                            MemvidError *error);
+
+/**
+ * Search the CLIP index with an image.
+ *
+ * Decodes the image (PNG or JPEG), embeds it with the CLIP model, and
+ * returns the nearest frames in the CLIP index. The model is loaded on the
+ * first call and kept for the life of the process; its files must already
+ * be downloaded. Requires the clip feature.
+ *
+ * @param handle      Valid Memvid handle
+ * @param image_data  Encoded image bytes
+ * @param image_len   Length of image_data
+ * @param top_k       Maximum number of frames to return
+ * @param error       Out-parameter for error information (may be NULL)
+ *
+ * @return JSON array of hits on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Errors: ClipNotEnabled without the clip feature; ExtractionFailed if the
+ * image cannot be decoded; InvalidQuery if top_k is 0.
+ *
+ * Response JSON Schema (score is cosine similarity):
+ * [
+ *   {"frame_id": 7, "uri": "mv2://screenshots/login.png", "score": 0.83}
+ * ]
+ */
+char *memvid_clip_search(MemvidHandle *handle,
+                         const uint8_t *image_data,
+                         size_t image_len,
+                         uint32_t top_k,
+                         MemvidError *error);
+
+/**
+ * Add an image and index its CLIP embedding.
+ *
+ * Decodes the image (PNG or JPEG), embeds it with the CLIP model (loaded as
+ * for memvid_clip_search()), stores the encoded bytes as the frame's
+ * payload, and adds the embedding to the CLIP index. Options are those of
+ * memvid_put_bytes_with_options(); "kind" defaults to the image's MIME
+ * type, and the payload is kept out of the lexical index unless
+ * "search_text" (e.g. a caption) is given. A put deduplicated by
+ * idempotency_key returns the earlier frame and adds no embedding. The
+ * frame is staged until memvid_commit().
+ *
+ * Errors: ClipNotEnabled without the clip feature; ExtractionFailed if the
+ * image cannot be decoded; ReadOnly for a read-only handle.
+ *
+ * @param handle        Valid Memvid handle
+ * @param image_data    Encoded image bytes
+ * @param image_len     Length of image_data
+ * @param options_json  JSON put options (may be NULL for defaults)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Frame ID on success, 0 on failure.
+ */
+uint64_t memvid_put_image(MemvidHandle *handle,
+                          const uint8_t *image_data,
+                          size_t image_len,
+                          const char *options_json,
+                          MemvidError *error);
+
+/* ============================================================================
+ * Timeline Functions
+ * ============================================================================ */
+
+/**
+ * Query the timeline (chronological frame list).
+ *
+ * Entries are ordered by timestamp, then frame ID. To page through a long
+ * history, set "limit" and pass each response's "next_cursor" back as
+ * "cursor" with otherwise identical query parameters. "next_cursor" is set
+ * whenever a page is full, so the last page may be empty; it is null on a
+ * page shorter than "limit" and when no "limit" is given.
+ * "preview_chars" sets the preview length, cut at a character boundary;
+ * without it, previews are core's default. Core's timeline query has no
+ * preview length, so shorter previews are cut from core's, but each entry
+ * whose core preview is shorter than "preview_chars" decodes its frame's
+ * full text: asking for more than core's default costs a frame read per
+ * entry.
+ * Errors: InvalidQuery if "cursor" is malformed.
+ *
+ * @param handle      Valid Memvid handle
+ * @param query_json  JSON string with query parameters (NULL for defaults)
+ * @param error       Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with timeline entries on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Query JSON Schema:
+ * {
+ *   "limit": 100,
+ *   "since": 1234567890,
+ *   "until": 1234567899,
+ *   "reverse": false,
+ *   "cursor": "1234567890:42",
+ *   "preview_chars": 40
+ * }
+ *
+ * Response JSON Schema:
+ * {
+ *   "entries": [
+ *     {"frame_id": 1, "timestamp": 1234567890, "preview": "...",
+ *      "uri": "file://doc.txt", "child_frames": [2, 3]}
+ *   ],
+ *   "count": 1,
+ *   "next_cursor": "1234567890:1"
+ * }
+ */
+char *memvid_timeline(MemvidHandle *handle, const char *query_json, MemvidError *error);
+
+/* ============================================================================
+ * Verification Functions
+ * ============================================================================ */
+
+/**
+ * Verify file integrity.
+ *
+ * Levels:
+ *   - 0 (shallow): memvid-core shallow verification; opens the file and
+ *     checks structure and index metadata without decoding index contents.
+ *   - 1 (deep): shallow checks plus full decodes of every index. Slowest.
+ *   - 2 (quick): without opening the file as a memory, reads the header,
+ *     the manifest (TOC) and the commit footer, and runs exactly:
+ *       "HeaderMagic"      - the magic bytes match;
+ *       "HeaderVersion"    - memvid-core's header codec accepts the header
+ *                            (skipped if the magic is wrong);
+ *       "ManifestChecksum" - the commit footer decodes, the manifest ends
+ *                            where it starts, and the manifest hash matches
+ *                            the footer's (skipped if the header does not
+ *                            decode).
+ *     Frame payloads and index contents are not read.
+ *
+ * 0 and 1 keep their meaning from the former boolean deep flag; other
+ * nonzero values also select deep.
+ *
+ * @param path   Path to the .mv2 file (null-terminated UTF-8 string)
+ * @param level  Verification level (see above)
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with verification report on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_verify(const char *path, int level, MemvidError *error);
+
+#ifdef _WIN32
+/**
+ * Verify file integrity at a UTF-16 path (Windows only).
+ *
+ * Same as memvid_verify(), but the path is null-terminated UTF-16.
+ *
+ * @param path   Path to the .mv2 file (UTF-16, null-terminated)
+ * @param level  Verification level (as in memvid_verify())
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with verification report on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_verify_w(const wchar_t *path, int level, MemvidError *error);
+#endif
+
+/**
+ * Verify file integrity, running only selected checks.
+ *
+ * memvid-core runs its checks all at once, so checks are selected in groups
+ * matched by name:
+ *   - "header": HeaderMagic, HeaderVersion, and ManifestChecksum, as in
+ *     memvid_verify() level 2. Alone, it never opens the file as a memory.
+ *   - "checksum", "toc", "lex_index", "vec_index", "time_index": core checks
+ *     whose names contain Checksum, Toc, LexIndex, VecIndex, or TimeIndex.
+ *     Any of these runs core shallow verification, or deep with "deep".
  *
- * @return WAL sequence number on success, 0 on failure.
+ * The report lists only checks in the selected groups. A group with no
+ * matching check (e.g. index decodes without "deep") appears once under its
+ * group name with status "skipped".
+ *
+ * Options JSON (NULL for all groups, shallow):
+ *   {"checks": ["header", "checksum", "lex_index"], "deep": false}
+ *
+ * @param path          Path to the .mv2 file
+ * @param options_json  JSON options (may be NULL)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return JSON report (same schema as memvid_verify()) on success, NULL on
+ *         failure (InvalidQuery naming an unknown check).
+ *         Caller must free with memvid_string_free().
  */
-uint64_t memvid_delete_frame(MemvidHandle *handle, uint64_t frame_id, MemvidError *error);
-
-/* ============================================================================
- * Timeline Functions
- * ============================================================================ */
+char *memvid_verify_with_options(const char *path, const char *options_json, MemvidError *error);
 
 /**
- * Query the timeline (chronological frame list).
+ * Read the on-disk format version and check compatibility.
  *
- * @param handle      Valid Memvid handle
- * @param query_json  JSON string with query parameters (NULL for defaults)
- * @param error       Out-parameter for error information (may be NULL)
+ * Only the file header is read, so this is cheap and works on files this
+ * library cannot open.
  *
- * @return JSON string with timeline entries on success, NULL on failure.
+ * @param path   Path to the .mv2 file (null-terminated UTF-8 string)
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with format information on success, NULL on failure.
  *         Caller must free with memvid_string_free().
  *
- * Query JSON Schema:
+ * Response JSON Schema:
  * {
- *   "limit": 100,
- *   "since": 1234567890,
- *   "until": 1234567899,
- *   "reverse": false
+ *   "file_path": "/path/to/file.mv2",
+ *   "format_version": "2.1",
+ *   "min_reader_version": "2.0",
+ *   "reader_version": "2.1",
+ *   "compatible": true
  * }
+ *
+ * The header is decoded by memvid-core. "format_version" is read from the
+ * header; "min_reader_version" is not stored in the file but derived from it
+ * as "<major>.0", since any reader of the same major version can open the
+ * file. "compatible" is false when core rejects the header (bad magic or an
+ * unsupported version); "format_version" and "min_reader_version" are then
+ * "unknown".
  */
-char *memvid_timeline(MemvidHandle *handle, const char *query_json, MemvidError *error);
-
-/* ============================================================================
- * Verification Functions
- * ============================================================================ */
+char *memvid_format_info(const char *path, MemvidError *error);
 
 /**
- * Verify file integrity.
+ * Summarize a file's header and manifest for tooling.
+ *
+ * The header is read as in memvid_format_info(); the manifest is read by
+ * opening the file read-only, without the write lock or verification, and
+ * only when the header is compatible. A part that cannot be parsed is null
+ * with the reason in "errors", so a file too corrupt to open still reports
+ * its header. memvid-core has no encrypted format, so "encrypted" is always
+ * false.
  *
  * @param path   Path to the .mv2 file (null-terminated UTF-8 string)
- * @param deep   Perform deep verification (non-zero for true)
  * @param error  Out-parameter for error information (may be NULL)
  *
- * @return JSON string with verification report on success, NULL on failure.
+ * @return JSON string on success, NULL if the file cannot be read at all.
  *         Caller must free with memvid_string_free().
+ *
+ * Response JSON Schema:
+ * {
+ *   "file_path": "/path/to/file.mv2",
+ *   "file_size": 1048576,
+ *   "encrypted": false,
+ *   "header": { "magic_ok": true, "format_version": "2.1", "compatible": true },
+ *   "manifest": {
+ *     "frame_count": 120,
+ *     "active_frame_count": 118,
+ *     "capacity_bytes": 1073741824,
+ *     "has_lex_index": true,
+ *     "has_vec_index": false,
+ *     "has_clip_index": false,
+ *     "has_time_index": true
+ *   },
+ *   "errors": []
+ * }
  */
-char *memvid_verify(const char *path, int deep, MemvidError *error);
+char *memvid_inspect(const char *path, MemvidError *error);
 
 /* ============================================================================
  * RAG/Ask Functions
@@ -521,26 +2291,148 @@ char *memvid_verify(const char *path, int deep, MemvidError *error);
  *   "uri": null,
  *   "scope": null,
  *   "context_only": true,
- *   "mode": "hybrid"
+ *   "mode": "hybrid",
+ *   "order_fragments": "rank",
+ *   "dedup_citations": false,
+ *   "min_score": 0.2,
+ *   "compact": false,
+ *   "context_budget_chars": 8000
  * }
  *
  * Mode values: "lex", "sem", "hybrid" (default: "hybrid")
  *
+ * order_fragments values: "rank" (default, retrieval order), "document"
+ * (frame, then offset within the frame), "chronological" (frame timestamp).
+ * Fragments keep their rank and score; citations are rebuilt so citation
+ * i + 1 refers to the i-th fragment.
+ *
+ * dedup_citations collapses citations to one per document URI, keeping the
+ * best-scoring chunk's frame_id, chunk_range, and score, and renumbers them
+ * 1..n in order of first appearance. [n] markers in a synthesized answer are
+ * rewritten to match. context_fragments stay one per chunk.
+ *
+ * min_score drops retrieval hits and context fragments scoring below the
+ * threshold; unscored entries are kept. It filters after ranking without
+ * fetching more, so fewer than top_k fragments may return; citations are
+ * rebuilt from the kept fragments. With a reranker it applies to the
+ * reranker's scores; with synthesis, dropped fragments are left out of the
+ * prompt.
+ *
+ * compact omits retrieval.hits, keeping the other retrieval fields such as
+ * total_hits and elapsed_ms. The hits' text is repeated in
+ * context_fragments, so this roughly halves the response.
+ *
+ * context_budget_chars caps retrieval.context as in memvid_search(), leaving
+ * out the first hit that would overflow and every lower-ranked hit;
+ * retrieval.context_truncated reports whether anything was. Fragments and
+ * citations are unaffected.
+ *
+ * retriever_reason explains why "retriever" differs from the requested mode
+ * (lex_fallback: vec feature off, no embedder registered with
+ * memvid_register_embedder(), or no semantic hits;
+ * timeline_fallback: no hits for the question). It is null when the
+ * requested retriever ran. memvid-core reports only the retriever, so the
+ * reason is inferred from the build, the embedder, and the results.
+ *
+ * source_frame_ids lists the distinct frames behind context_fragments in
+ * ascending order (independent of order_fragments), e.g. as a cache key for
+ * generated answers.
+ *
  * Response JSON Schema:
  * {
  *   "question": "...",
  *   "mode": "hybrid",
- *   "retriever": "lex",
+ *   "retriever": "lex_fallback",
+ *   "retriever_reason": "hybrid mode requested but no embedder was provided ...",
  *   "context_only": true,
  *   "retrieval": { "query": "...", "hits": [...], ... },
  *   "answer": null,
  *   "citations": [...],
  *   "context_fragments": [...],
+ *   "source_frame_ids": [3, 7, 12],
  *   "stats": { "retrieval_ms": 5, "synthesis_ms": 0, "latency_ms": 5 }
  * }
  */
 char *memvid_ask(MemvidHandle *handle, const char *request_json, MemvidError *error);
 
+/**
+ * Reranker callback.
+ *
+ * Receives the question and a JSON array of candidates:
+ *   [{"index": 0, "frame_id": 42, "uri": "...", "title": null, "score": 0.5, "text": "..."}]
+ *
+ * Returns a JSON array ordered best-first (omitted candidates are dropped):
+ *   [{"index": 3, "score": 0.97}, {"index": 0, "score": 0.81}]
+ *
+ * The returned string must be allocated with malloc(); ownership transfers
+ * to the library, which frees it. Returning NULL aborts with RerankFailed.
+ * The callback must not call back into the handle: such calls fail with
+ * InvalidHandle.
+ */
+typedef char *(*MemvidRerankFn)(const char *query, const char *candidates_json, void *user_data);
+
+/**
+ * Ask a question and rerank the retrieved candidates with a host callback.
+ *
+ * Retrieval runs as in memvid_ask(), then the reranker's ordering and scores
+ * determine the final context_fragments and citations, truncated to top_k.
+ * retrieval.hits follow the same order and truncation, and
+ * retrieval.context is rebuilt from them. Set "rerank_candidates" in the request to retrieve a larger pool than top_k.
+ * "order_fragments" and "dedup_citations" are applied after reranking.
+ * rerank_fn must not call back into handle: such calls fail with
+ * InvalidHandle.
+ *
+ * @param handle        Valid Memvid handle
+ * @param request_json  JSON string with ask parameters
+ * @param rerank_fn     Reranker callback (must not be NULL)
+ * @param user_data     Opaque pointer passed through to rerank_fn
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with ask response on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_ask_with_reranker(MemvidHandle *handle,
+                               const char *request_json,
+                               MemvidRerankFn rerank_fn,
+                               void *user_data,
+                               MemvidError *error);
+
+/**
+ * Synthesis callback.
+ *
+ * Receives the assembled prompt (instructions, question, and numbered
+ * context; context [n] is citation n) and returns the answer.
+ *
+ * The returned string must be allocated with malloc(); ownership transfers
+ * to the library, which frees it. Returning NULL aborts with SynthesisFailed.
+ * The callback must not call back into the handle.
+ */
+typedef char *(*MemvidSynthesizeFn)(const char *prompt, void *user_data);
+
+/**
+ * Ask a question and synthesize the answer with a host callback.
+ *
+ * Retrieval runs as in memvid_ask() in context-only mode, then the question
+ * and context fragments are passed to synth_fn as a prompt, and its result
+ * becomes "answer". The response has "context_only": false and
+ * stats.synthesis_ms set. "order_fragments" also orders the prompt's
+ * context; with "dedup_citations", [n] markers in the answer are rewritten.
+ *
+ * @param handle        Valid Memvid handle
+ * @param request_json  JSON string with ask parameters ("context_only" is ignored)
+ * @param synth_fn      Synthesis callback (must not be NULL)
+ * @param user_data     Opaque pointer passed through to synth_fn
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with ask response on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_ask_with_synthesis(MemvidHandle *handle,
+                                const char *request_json,
+                                MemvidSynthesizeFn synth_fn,
+                                void *user_data,
+                                MemvidError *error);
+
 /* ============================================================================
  * Doctor (File Repair) Functions
  * ============================================================================ */
@@ -561,6 +2453,13 @@ char *memvid_ask(MemvidHandle *handle, const char *request_json, MemvidError *er
  *                 "vacuum": false, "dry_run": false, "quiet": false }
  *
  * Status values: "clean", "healed", "partial", "failed", "plan_only"
+ *
+ * Every entry of "findings" has a stable "finding_code" next to core's own
+ * fields, one of: "HEADER_DECODE_FAILURE", "HEADER_FOOTER_OFFSET_MISMATCH",
+ * "TOC_DECODE_FAILURE", "TOC_CHECKSUM_MISMATCH", "WAL_CHECKSUM_MISMATCH",
+ * "TIME_INDEX_MISSING", "TIME_INDEX_CHECKSUM_MISMATCH", "LEX_INDEX_MISSING",
+ * "LEX_INDEX_CORRUPT", "VEC_INDEX_CORRUPT", or "UNCLASSIFIED" for a code
+ * this library does not know. Branch on "finding_code", not the text.
  */
 char *memvid_doctor(const char *path, const char *options_json, MemvidError *error);
 
@@ -573,6 +2472,9 @@ char *memvid_doctor(const char *path, const char *options_json, MemvidError *err
  *
  * @return JSON string with doctor plan on success, NULL on failure.
  *         Caller must free with memvid_string_free().
+ *
+ * Findings carry a "finding_code" as in memvid_doctor(); the plan can be
+ * passed to memvid_doctor_apply() unchanged.
  */
 char *memvid_doctor_plan(const char *path, const char *options_json, MemvidError *error);
 
@@ -588,10 +2490,396 @@ char *memvid_doctor_plan(const char *path, const char *options_json, MemvidError
  */
 char *memvid_doctor_apply(const char *path, const char *plan_json, MemvidError *error);
 
+/**
+ * Rebuild a single index and verify it.
+ *
+ * Runs doctor with only the rebuild for index enabled, then a deep
+ * verification whose checks are filtered to that index.
+ * The file should NOT be open when rebuilding.
+ *
+ * @param path   Path to the .mv2 file (null-terminated UTF-8 string)
+ * @param index  One of "lex", "vec", "clip", "time"
+ * @param error  Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with rebuild report on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Errors: LexNotEnabled/VecNotEnabled/ClipNotEnabled if the feature is not
+ * compiled in; FeatureUnavailable for "clip", which memvid-core cannot
+ * rebuild; InvalidQuery for an unknown index name.
+ *
+ * Response JSON Schema:
+ * {
+ *   "index": "lex",
+ *   "doctor": { "status": "healed", ... },
+ *   "verification": {
+ *     "status": "passed",
+ *     "checks": [{"name": "LexIndexDecode", "status": "passed", "details": "..."}]
+ *   }
+ * }
+ */
+char *memvid_rebuild_index(const char *path, const char *index, MemvidError *error);
+
+/**
+ * Run doctor with a cancellation flag polled between steps.
+ *
+ * memvid-core's doctor is one uninterruptible call, so the work is split
+ * into steps, each a separate doctor run: one per requested rebuild (time,
+ * lex, then vec index), then vacuum. With nothing requested, or with
+ * dry_run, there is a single "diagnose" step.
+ *
+ * The flag is read before each step. Once it is nonzero, no further step
+ * starts and the call fails with Cancelled (the message lists completed
+ * steps). Completed steps keep their changes; a step in progress always
+ * runs to completion. Every boundary between steps is a safe point.
+ * The file should NOT be open when running doctor.
+ *
+ * @param path          Path to the .mv2 file (null-terminated UTF-8 string)
+ * @param options_json  Doctor options as in memvid_doctor() (NULL for defaults)
+ * @param cancel_flag   Caller-owned byte; set to 1 (e.g. from a signal
+ *                      handler) to cancel. May be NULL.
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return JSON string with the step reports on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Response JSON Schema:
+ * {
+ *   "steps": [
+ *     {"step": "rebuild_lex_index", "report": { "status": "healed", ... }},
+ *     {"step": "vacuum", "report": { ... }}
+ *   ]
+ * }
+ *
+ * Step values: "diagnose", "rebuild_time_index", "rebuild_lex_index",
+ * "rebuild_vec_index", "vacuum". Each report has the memvid_doctor() schema.
+ */
+char *memvid_doctor_cancellable(const char *path,
+                                const char *options_json,
+                                const volatile uint8_t *cancel_flag,
+                                MemvidError *error);
+
+/**
+ * Remove deleted frames and compact the file of an open handle.
+ *
+ * Commits staged changes, then runs doctor's vacuum without closing the
+ * handle: the memory is closed for the duration and reopened afterwards.
+ * Frame IDs are preserved. The vacuum runs on a copy ("<path>.vacuum", so
+ * it needs that much free disk space), which replaces the file only if
+ * every active frame kept its ID, timestamp, URI, title, and parent;
+ * otherwise the call fails and the file is left as it was. Like
+ * memvid_refresh(), it invalidates outstanding cursors.
+ *
+ * Errors: ReadOnly for a read-only handle; Doctor if vacuum would change
+ * frame IDs; Io if the copy cannot be written or moved into place. If the
+ * file cannot be reopened afterwards, the handle fails every later call
+ * with InvalidHandle and must be closed.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_vacuum(MemvidHandle *handle, MemvidError *error);
+
+/**
+ * Vacuum an open handle, reporting the space reclaimed.
+ *
+ * Same as memvid_vacuum(), returning file sizes instead of a flag.
+ *
+ * @param handle  Valid Memvid handle
+ * @param error   Out-parameter for error information (may be NULL)
+ *
+ * @return JSON report on success, NULL on failure.
+ *         Caller must free with memvid_string_free().
+ *
+ * Response JSON Schema:
+ * {
+ *   "bytes_before": 1048576,
+ *   "bytes_after": 524288,
+ *   "reclaimed_bytes": 524288
+ * }
+ */
+char *memvid_vacuum_report(MemvidHandle *handle, MemvidError *error);
+
+/* ============================================================================
+ * JSONL Export and Import Functions
+ * ============================================================================ */
+
+/**
+ * Export frames to a JSONL file, one JSON object per frame.
+ *
+ * Each line holds the frame's metadata and text content. Lines are streamed
+ * to disk, replacing any existing file. Chunk frames are skipped; their
+ * parent document carries the full content.
+ *
+ * Options JSON Schema:
+ *   {
+ *     "include_deleted": false,  // also export deleted frames
+ *     "include_payload": false   // add the raw payload as base64
+ *   }
+ *
+ * Line JSON Schema:
+ *   {
+ *     "id": 1, "status": "Active", "timestamp": 1234567890,
+ *     "uri": "mv2://doc", "title": "Title", "track": "notes",
+ *     "kind": "text/plain", "tags": ["tag1"], "labels": ["label1"],
+ *     "extra_metadata": {"key": "value"},
+ *     "text": "Frame text",          // null if text cannot be extracted
+ *     "payload_base64": "SGVsbG8="   // only with include_payload
+ *   }
+ *
+ * @param handle        Valid Memvid handle
+ * @param out_path      Output file path (null-terminated UTF-8 string)
+ * @param options_json  JSON options (may be NULL for defaults)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Number of frames written on success, 0 on failure
+ *         (Io if the file cannot be written; a partial file may remain).
+ */
+uint64_t memvid_export_jsonl(MemvidHandle *handle,
+                             const char *out_path,
+                             const char *options_json,
+                             MemvidError *error);
+
+/**
+ * Import frames from a JSONL file in the memvid_export_jsonl() format.
+ *
+ * Each line is put as a new frame, with its fields as put options (see
+ * memvid_put_bytes_with_options()). Content comes from "payload_base64"
+ * when present, otherwise from "text". Stored "tags" and "extra_metadata"
+ * are carried over, including idempotency keys, so re-importing a file
+ * does not duplicate keyed frames. Lines whose "status" is not "Active"
+ * are skipped. Frame IDs are assigned anew; frames are staged until
+ * memvid_commit().
+ *
+ * A line is malformed if it is not a JSON object or has neither "text" nor
+ * valid "payload_base64". Malformed lines are skipped unless strict is set.
+ *
+ * Errors: Io if the file cannot be read; JsonParse for a malformed line in
+ * strict mode, naming the line; ReadOnly for a read-only handle. Frames
+ * imported before the failing line remain staged.
+ *
+ * Options JSON Schema:
+ *   {
+ *     "strict": false  // abort on the first malformed line
+ *   }
+ *
+ * @param handle        Valid Memvid handle
+ * @param in_path       Input file path (null-terminated UTF-8 string)
+ * @param options_json  JSON options (may be NULL for defaults)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Number of frames imported on success, 0 on failure.
+ */
+uint64_t memvid_import_jsonl(MemvidHandle *handle,
+                             const char *in_path,
+                             const char *options_json,
+                             MemvidError *error);
+
+/* ============================================================================
+ * Merge Functions
+ * ============================================================================ */
+
+/**
+ * Merge the active frames of one memory file into another.
+ *
+ * Opens dest for writing and src read-only, puts each active document
+ * frame of src into dest with its payload and metadata, then commits dest.
+ * Frame IDs are assigned anew and chunks are rebuilt; deleted source frames
+ * are not carried over. Frames whose idempotency key already exists in
+ * dest are not merged again. Neither file may be open elsewhere, including
+ * through a handle in this process.
+ *
+ * A conflict is a source frame whose URI matches an active frame in dest.
+ * "skip" keeps the destination frame, "replace" deletes every destination
+ * frame with that URI before adding the source frame, and "keep_both" (the
+ * default) adds it alongside them.
+ *
+ * Errors: InvalidQuery if both paths name the same file; Locked if either
+ * file is open elsewhere. On failure nothing is committed; frames staged
+ * before the failure stay in dest's WAL.
+ *
+ * Options JSON Schema:
+ *   {
+ *     "on_uri_conflict": "keep_both"  // "skip", "replace", or "keep_both"
+ *   }
+ *
+ * @param dest_path     Memory to merge into (null-terminated UTF-8 string)
+ * @param src_path      Memory to merge from (null-terminated UTF-8 string)
+ * @param options_json  JSON options (may be NULL for defaults)
+ * @param error         Out-parameter for error information (may be NULL)
+ *
+ * @return Number of frames merged on success, 0 on failure.
+ */
+uint64_t memvid_merge(const char *dest_path,
+                      const char *src_path,
+                      const char *options_json,
+                      MemvidError *error);
+
+/* ============================================================================
+ * Watch Functions
+ * ============================================================================ */
+
+/**
+ * Watch callback.
+ *
+ * Receives a JSON event describing newly committed frames:
+ *   {"frame_ids": [12, 13], "frame_count": 14}
+ *
+ * The event string is only valid for the duration of the call.
+ */
+typedef void (*MemvidWatchFn)(const char *event_json, void *user_data);
+
+/**
+ * Watch the memory file for commits.
+ *
+ * Starts a background thread that checks the file's size and modification
+ * time every 250 ms, reopening it read-only only when they change, and
+ * invokes callback when a commit (from this or another process) adds
+ * frames. The event lists the IDs that were not in the frame table before,
+ * so it stays exact after deletes or compaction. The watcher reads the file
+ * independently, so the handle may be closed while it runs.
+ *
+ * callback runs on the watcher thread, not the calling thread, and must not
+ * call into handle.
+ *
+ * @param handle     Valid Memvid handle
+ * @param callback   Function invoked for each batch of new frames (must not be NULL)
+ * @param user_data  Opaque pointer passed through to callback
+ * @param error      Out-parameter for error information (may be NULL)
+ *
+ * @return Watcher on success, NULL on failure.
+ *         Caller must stop and free with memvid_watch_stop().
+ */
+MemvidWatcher *memvid_watch(MemvidHandle *handle,
+                            MemvidWatchFn callback,
+                            void *user_data,
+                            MemvidError *error);
+
+/**
+ * Stop and free a watcher.
+ *
+ * Blocks until the watcher thread exits; no callbacks run after this
+ * returns. Must not be called from inside the callback.
+ *
+ * @param watcher  Watcher to stop (safe to pass NULL)
+ */
+void memvid_watch_stop(MemvidWatcher *watcher);
+
+/* ============================================================================
+ * Embedder Functions
+ * ============================================================================ */
+
+/**
+ * Embedding callback.
+ *
+ * Receives a null-terminated UTF-8 text and writes its embedding, exactly
+ * dim floats, to out. Returns 1 on success, 0 on failure.
+ */
+typedef int (*MemvidEmbedFn)(const char *text, float *out, uint32_t dim, void *user_data);
+
+/**
+ * Register a host embedder on a handle.
+ *
+ * Once registered, the handle embeds with embed_callback: puts store an
+ * embedding of the frame's search_text, or of the content decoded as
+ * UTF-8, in the vector index; "sem" and "hybrid" searches without
+ * query_embedding embed the query; memvid_ask() and its variants embed the
+ * question. Puts and searches only use the vector index with the vec
+ * feature. Registering again replaces the embedder; NULL removes it.
+ *
+ * The callback runs on the calling thread, inside the call that needs an
+ * embedding; calls back into the same handle from it fail with
+ * InvalidHandle. user_data must stay valid until the embedder is replaced
+ * or the handle is closed.
+ *
+ * Errors: InvalidQuery if dim is 0 with a non-NULL callback. A callback
+ * that returns 0 or writes a non-finite value fails the call that needed
+ * the embedding with EmbeddingFailed.
+ *
+ * @param handle          Valid Memvid handle
+ * @param dim             Embedding dimension; must match the vector index
+ * @param embed_callback  Embedding callback, or NULL to remove the embedder
+ * @param user_data       Opaque pointer passed through to embed_callback
+ * @param error           Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_register_embedder(MemvidHandle *handle,
+                             uint32_t dim,
+                             MemvidEmbedFn embed_callback,
+                             void *user_data,
+                             MemvidError *error);
+
+/* ============================================================================
+ * Logging Functions
+ * ============================================================================ */
+
+/**
+ * Log callback.
+ *
+ * level is 1 (error), 2 (warn), 3 (info), 4 (debug), or 5 (trace); msg is
+ * the formatted record, "target: message key=value ...", valid only for
+ * the duration of the call.
+ */
+typedef void (*MemvidLogFn)(uint32_t level, const char *msg, void *user_data);
+
+/**
+ * Forward memvid-core's log records to a host callback.
+ *
+ * Installs a process-global tracing subscriber (also bridging the log
+ * crate) that passes each record to callback. Call it once at startup;
+ * later calls replace the callback and user data, and NULL stops
+ * forwarding. Records arrive on whichever thread emits them, so the
+ * callback must be thread-safe.
+ *
+ * Errors: FeatureUnavailable if another global tracing subscriber was
+ * installed first.
+ *
+ * @param callback   Log callback, or NULL to stop forwarding
+ * @param user_data  Opaque pointer passed through to callback
+ * @param error      Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure.
+ */
+int memvid_set_log_callback(MemvidLogFn callback, void *user_data, MemvidError *error);
+
 /* ============================================================================
  * Memory Management Functions
  * ============================================================================ */
 
+/** Host allocation callback, with malloc() semantics. */
+typedef void *(*MemvidMallocFn)(size_t size);
+
+/** Host deallocation callback, with free() semantics. */
+typedef void (*MemvidFreeFn)(void *ptr);
+
+/**
+ * Install host callbacks for memory returned to the caller.
+ *
+ * Returned strings, byte buffers, and arrays are then allocated with
+ * malloc_fn, and memvid_string_free(), memvid_bytes_free(), and
+ * memvid_u64_array_free() release them with free_fn. Memory the library
+ * keeps ownership of (handles, MemvidError.message) is unaffected.
+ *
+ * The allocator is process-global and can be set only once. Set it at
+ * startup, before creating any handle: the call fails once any memory has
+ * been returned with the default allocator. If never set, the library's
+ * own allocator is used.
+ *
+ * If malloc_fn returns NULL, the call that was returning the memory fails
+ * with AllocationFailed and returns NULL or 0.
+ *
+ * @param malloc_fn  Allocation callback (must not be NULL)
+ * @param free_fn    Deallocation callback (must not be NULL)
+ * @param error      Out-parameter for error information (may be NULL)
+ *
+ * @return 1 on success, 0 on failure (FeatureUnavailable if an allocator is
+ *         already set or memory has already been returned).
+ */
+int memvid_set_allocator(MemvidMallocFn malloc_fn, MemvidFreeFn free_fn, MemvidError *error);
+
 /**
  * Free an error's message field.
  *
@@ -601,6 +2889,51 @@ char *memvid_doctor_apply(const char *path, const char *plan_json, MemvidError *
  */
 void memvid_error_free(MemvidError *error);
 
+/**
+ * Get the cause chain of an error as JSON.
+ *
+ * Returns a JSON array of messages from the outermost error to the root
+ * cause. The full chain is available for the most recent core error on the
+ * calling thread until its message is freed; other errors yield a
+ * single-element array with their message, and an error without a message
+ * yields [].
+ *
+ * @param error  Error to inspect
+ *
+ * @return JSON string on success, NULL if error is NULL.
+ *         Caller must free with memvid_string_free().
+ */
+char *memvid_error_cause_json(const MemvidError *error);
+
+/**
+ * Get the name of an error code, e.g. "VecNotEnabled".
+ *
+ * Covers every MemvidErrorCode value; code must be one of them.
+ *
+ * @return Static string with the code name. Do not free this string.
+ */
+const char *memvid_error_code_str(MemvidErrorCode code);
+
+/**
+ * Get the code of the most recent failing call on the calling thread.
+ *
+ * An opt-in alternative to the MemvidError out-parameter: every failing call
+ * also records its error per thread, whether or not an out-parameter was
+ * passed (error may be NULL). Successful calls leave it unchanged.
+ *
+ * @return Last error code, or MemvidErrorCode_Ok if no call on this thread has failed.
+ */
+MemvidErrorCode memvid_last_error_code(void);
+
+/**
+ * Get the message of the most recent failing call on the calling thread.
+ *
+ * @return Last error message, or NULL if no call on this thread has failed.
+ *         Owned by the library and invalidated by the next failing call on
+ *         the same thread; copy it immediately and do not free it.
+ */
+const char *memvid_last_error_message(void);
+
 #ifdef __cplusplus
 }
 #endif
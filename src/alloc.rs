@@ -0,0 +1,179 @@
+//! Allocation of strings and buffers returned to the caller.
+//!
+//! Returned memory comes from Rust's global allocator unless the host
+//! installs its own with `memvid_set_allocator()`.
+
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::util::{set_error, set_ok};
+use libc::size_t;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::sync::{Mutex, PoisonError};
+
+/// Host allocation callback, with `malloc` semantics.
+pub type MemvidMallocFn = extern "C" fn(size: size_t) -> *mut c_void;
+
+/// Host deallocation callback, with `free` semantics.
+pub type MemvidFreeFn = extern "C" fn(ptr: *mut c_void);
+
+/// Allocation callbacks installed by the host.
+#[derive(Clone, Copy)]
+struct HostAllocator {
+    malloc: MemvidMallocFn,
+    free: MemvidFreeFn,
+}
+
+/// Which allocator returned memory comes from.
+///
+/// Checked and changed under one lock, so `memvid_set_allocator` cannot
+/// interleave with a Rust allocation and leave memory freed by the wrong
+/// allocator.
+#[derive(Clone, Copy)]
+enum Allocator {
+    /// Nothing returned yet; the host may still install its own
+    Unset,
+    /// Rust's global allocator, fixed once memory was returned with it
+    Rust,
+    /// Callbacks from `memvid_set_allocator`
+    Host(HostAllocator),
+}
+
+static ALLOCATOR: Mutex<Allocator> = Mutex::new(Allocator::Unset);
+
+/// The host allocator, or `None` for Rust's, which this call then fixes.
+fn allocator() -> Option<HostAllocator> {
+    let mut allocator = ALLOCATOR.lock().unwrap_or_else(PoisonError::into_inner);
+    match *allocator {
+        Allocator::Host(host) => Some(host),
+        Allocator::Unset | Allocator::Rust => {
+            *allocator = Allocator::Rust;
+            None
+        }
+    }
+}
+
+/// Copy `bytes` into a buffer from the host allocator.
+///
+/// Fails with `AllocationFailed` if the host's `malloc` returns NULL.
+fn host_copy(host: HostAllocator, bytes: &[u8]) -> Result<*mut u8, MemvidError> {
+    let ptr = (host.malloc)(bytes.len().max(1)) as *mut u8;
+    if ptr.is_null() {
+        return Err(MemvidError::new(
+            MemvidErrorCode::AllocationFailed,
+            format!(
+                "host allocator returned NULL for {} bytes",
+                bytes.len().max(1)
+            ),
+        ));
+    }
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len()) };
+    Ok(ptr)
+}
+
+/// Hand a C string to the caller, to be freed with `free_cstring`.
+pub fn cstring_into_raw(s: CString) -> Result<*mut c_char, MemvidError> {
+    match allocator() {
+        Some(host) => host_copy(host, s.as_bytes_with_nul()).map(|ptr| ptr as *mut c_char),
+        None => Ok(s.into_raw()),
+    }
+}
+
+/// Hand a slice to the caller, to be freed with `free_slice`.
+pub fn slice_into_raw<T: Copy>(values: Box<[T]>) -> Result<*mut T, MemvidError> {
+    match allocator() {
+        Some(host) => {
+            let bytes = unsafe {
+                std::slice::from_raw_parts(values.as_ptr() as *const u8, size_of_val(&*values))
+            };
+            host_copy(host, bytes).map(|ptr| ptr as *mut T)
+        }
+        None => Ok(Box::into_raw(values) as *mut T),
+    }
+}
+
+/// Free a C string from `cstring_into_raw`.
+///
+/// # Safety
+///
+/// `ptr` must come from `cstring_into_raw` and not be freed twice.
+pub unsafe fn free_cstring(ptr: *mut c_char) {
+    match allocator() {
+        Some(host) => (host.free)(ptr as *mut c_void),
+        None => drop(unsafe { CString::from_raw(ptr) }),
+    }
+}
+
+/// Free a slice from `slice_into_raw`.
+///
+/// # Safety
+///
+/// `ptr` must come from `slice_into_raw` with length `len` and not be freed twice.
+pub unsafe fn free_slice<T>(ptr: *mut T, len: usize) {
+    match allocator() {
+        Some(host) => (host.free)(ptr as *mut c_void),
+        None => drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) }),
+    }
+}
+
+/// Install host callbacks for memory returned to the caller.
+///
+/// Strings, byte buffers, and arrays returned by the library are then
+/// allocated with `malloc_fn`, and `memvid_string_free()`,
+/// `memvid_bytes_free()`, and `memvid_u64_array_free()` release them with
+/// `free_fn`. Memory the library keeps ownership of (handles,
+/// `MemvidError.message`) still uses Rust's allocator.
+///
+/// The allocator is process-global and can be set only once. Set it at
+/// startup, before creating any handle: the call fails once any memory has
+/// been returned with the default allocator. If it is never set, Rust's
+/// global allocator is used.
+///
+/// If `malloc_fn` returns NULL, the call that was returning the memory
+/// fails with `AllocationFailed` and returns NULL or 0.
+///
+/// # Parameters
+///
+/// - `malloc_fn`: Allocation callback
+/// - `free_fn`: Deallocation callback
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure (`FeatureUnavailable` if an allocator is
+/// already set or memory has already been returned).
+///
+/// # Safety
+///
+/// - `malloc_fn` and `free_fn` must be valid function pointers
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_set_allocator(
+    malloc_fn: Option<MemvidMallocFn>,
+    free_fn: Option<MemvidFreeFn>,
+    error: *mut MemvidError,
+) -> i32 {
+    let (malloc, free) = match (malloc_fn, free_fn) {
+        (Some(m), Some(f)) => (m, f),
+        (None, _) => return unsafe { set_error(error, MemvidError::null_pointer("malloc_fn")) },
+        (_, None) => return unsafe { set_error(error, MemvidError::null_pointer("free_fn")) },
+    };
+
+    let installed = {
+        let mut allocator = ALLOCATOR.lock().unwrap_or_else(PoisonError::into_inner);
+        let unset = matches!(*allocator, Allocator::Unset);
+        if unset {
+            *allocator = Allocator::Host(HostAllocator { malloc, free });
+        }
+        unset
+    };
+    if !installed {
+        let err = MemvidError::new(
+            MemvidErrorCode::FeatureUnavailable,
+            "allocator must be set once, before any memory is returned",
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    unsafe { set_ok(error) };
+    1
+}
@@ -1,14 +1,19 @@
 //! RAG/Ask query functions.
 
-use crate::error::MemvidError;
+use crate::error::{MemvidError, MemvidErrorCode};
 use crate::handle::MemvidHandle;
-use crate::util::{cstr_to_string, set_error_null, set_ok, string_to_cstr};
+use crate::search::budget_context;
+use crate::util::{cstr_to_json, json_to_cstr, set_error_null};
 use memvid_core::types::{AskContextFragment, AskContextFragmentKind};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::{c_void, CStr, CString};
 use std::os::raw::c_char;
+use std::time::Instant;
 
 /// Ask mode for JSON serialization.
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 enum AskModeJson {
     Lex,
@@ -61,7 +66,7 @@ impl From<&memvid_core::AskRetriever> for AskRetrieverJson {
 }
 
 /// Ask request from JSON.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 struct AskRequestJson {
     question: String,
     #[serde(default = "default_top_k")]
@@ -86,6 +91,42 @@ struct AskRequestJson {
     as_of_frame: Option<u64>,
     #[serde(default)]
     as_of_ts: Option<i64>,
+    /// Candidates to retrieve before reranking (default: top_k)
+    #[serde(default)]
+    rerank_candidates: Option<usize>,
+    /// Order of the returned context fragments
+    #[serde(default)]
+    order_fragments: FragmentOrderJson,
+    /// Collapse citations to one per document
+    #[serde(default)]
+    dedup_citations: bool,
+    /// Drop context scoring below this
+    #[serde(default)]
+    min_score: Option<f32>,
+    /// Omit `retrieval.hits` from the response
+    #[serde(default)]
+    compact: bool,
+    /// Cap on `retrieval.context`, in characters
+    #[serde(default)]
+    context_budget_chars: Option<usize>,
+}
+
+/// JSON Schema of the ask request, for `memvid_request_schema`.
+pub(crate) fn request_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(AskRequestJson)
+}
+
+/// Context fragment ordering for JSON deserialization.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum FragmentOrderJson {
+    /// Retrieval rank (best first)
+    #[default]
+    Rank,
+    /// Position within the memory: frame, then offset in the frame
+    Document,
+    /// Frame timestamp, then document position
+    Chronological,
 }
 
 fn default_top_k() -> usize {
@@ -248,9 +289,13 @@ struct SearchResponseJson {
     query: String,
     elapsed_ms: u128,
     total_hits: usize,
-    hits: Vec<SearchHitJson>,
+    /// `None` when the request asked for a compact response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hits: Option<Vec<SearchHitJson>>,
     context: String,
     next_cursor: Option<String>,
+    /// Whether `context_budget_chars` left hit text out of `context`
+    context_truncated: bool,
 }
 
 impl From<&memvid_core::SearchResponse> for SearchResponseJson {
@@ -259,9 +304,10 @@ impl From<&memvid_core::SearchResponse> for SearchResponseJson {
             query: r.query.clone(),
             elapsed_ms: r.elapsed_ms,
             total_hits: r.total_hits,
-            hits: r.hits.iter().map(SearchHitJson::from).collect(),
+            hits: Some(r.hits.iter().map(SearchHitJson::from).collect()),
             context: r.context.clone(),
             next_cursor: r.next_cursor.clone(),
+            context_truncated: false,
         }
     }
 }
@@ -272,20 +318,25 @@ struct AskResponseJson {
     question: String,
     mode: AskModeJson,
     retriever: AskRetrieverJson,
+    /// Why `retriever` differs from the requested mode (null if it does not)
+    retriever_reason: Option<String>,
     context_only: bool,
     retrieval: SearchResponseJson,
     answer: Option<String>,
     citations: Vec<AskCitationJson>,
     context_fragments: Vec<AskContextFragmentJson>,
+    /// Distinct frames behind `context_fragments`, ascending
+    source_frame_ids: Vec<u64>,
     stats: AskStatsJson,
 }
 
 impl From<&memvid_core::AskResponse> for AskResponseJson {
     fn from(r: &memvid_core::AskResponse) -> Self {
-        Self {
+        let mut response = Self {
             question: r.question.clone(),
             mode: AskModeJson::from(&r.mode),
             retriever: AskRetrieverJson::from(&r.retriever),
+            retriever_reason: None,
             context_only: r.context_only,
             retrieval: SearchResponseJson::from(&r.retrieval),
             answer: r.answer.clone(),
@@ -295,9 +346,409 @@ impl From<&memvid_core::AskResponse> for AskResponseJson {
                 .iter()
                 .map(AskContextFragmentJson::from)
                 .collect(),
+            source_frame_ids: Vec::new(),
             stats: AskStatsJson::from(&r.stats),
+        };
+        response.collect_source_frame_ids();
+        response
+    }
+}
+
+impl AskResponseJson {
+    /// Explain a retriever that differs from the requested mode.
+    ///
+    /// memvid-core reports only which retriever ran, so the reason is
+    /// inferred from the build features, whether an embedder was passed,
+    /// and the retrieval results.
+    fn explain_retriever(&mut self, has_embedder: bool) {
+        let mode = match self.mode {
+            AskModeJson::Lex => "lex",
+            AskModeJson::Sem => "sem",
+            AskModeJson::Hybrid => "hybrid",
+        };
+        let semantic_cause = if !cfg!(feature = "vec") {
+            "the vec feature is not compiled in"
+        } else if !has_embedder {
+            "no embedder was provided to embed the question"
+        } else {
+            "semantic retrieval returned no hits"
+        };
+
+        self.retriever_reason = match (&self.retriever, &self.mode) {
+            (AskRetrieverJson::LexFallback, _)
+            | (AskRetrieverJson::Lex, AskModeJson::Sem | AskModeJson::Hybrid) => Some(format!(
+                "{mode} mode requested but {semantic_cause}; used lexical search"
+            )),
+            (AskRetrieverJson::TimelineFallback, _) => Some(format!(
+                "{mode} retrieval found no hits for the question; used the most recent frames from the timeline"
+            )),
+            _ => None,
+        };
+    }
+
+    /// Reorder fragments by a reranker's output and rebuild citations.
+    ///
+    /// `order` lists candidate indices best-first with optional new scores.
+    /// At most `top_k` fragments are kept.
+    fn apply_rerank(
+        &mut self,
+        order: Vec<RerankEntryJson>,
+        top_k: usize,
+    ) -> Result<(), MemvidError> {
+        let mut fragments: Vec<Option<AskContextFragmentJson>> =
+            self.context_fragments.drain(..).map(Some).collect();
+
+        let mut reranked = Vec::with_capacity(order.len().min(top_k));
+        for entry in order {
+            if reranked.len() == top_k {
+                break;
+            }
+            let fragment = fragments
+                .get_mut(entry.index)
+                .and_then(Option::take)
+                .ok_or_else(|| {
+                    MemvidError::new(
+                        MemvidErrorCode::RerankFailed,
+                        format!(
+                            "reranker returned invalid or duplicate index: {}",
+                            entry.index
+                        ),
+                    )
+                })?;
+            reranked.push(AskContextFragmentJson {
+                rank: reranked.len() + 1,
+                score: entry.score.or(fragment.score),
+                ..fragment
+            });
+        }
+
+        self.context_fragments = reranked;
+        self.rebuild_citations();
+        self.collect_source_frame_ids();
+        self.rerank_hits();
+        Ok(())
+    }
+
+    /// Reorder `retrieval.hits` to follow the reranked fragments.
+    ///
+    /// Each fragment takes the best remaining hit on its frame; hits left
+    /// over were not chosen by the reranker and are dropped, so at most
+    /// `top_k` remain. `retrieval.context` is rebuilt from them.
+    fn rerank_hits(&mut self) {
+        let Some(hits) = self.retrieval.hits.take() else {
+            return;
+        };
+        let mut hits: Vec<Option<SearchHitJson>> = hits.into_iter().map(Some).collect();
+        let mut reranked = Vec::with_capacity(self.context_fragments.len());
+        for fragment in &self.context_fragments {
+            let hit = hits
+                .iter_mut()
+                .find(|h| h.as_ref().is_some_and(|h| h.frame_id == fragment.frame_id))
+                .and_then(Option::take);
+            if let Some(hit) = hit {
+                reranked.push(SearchHitJson {
+                    rank: reranked.len() + 1,
+                    ..hit
+                });
+            }
+        }
+        self.retrieval.hits = Some(reranked);
+        self.fit_context(usize::MAX);
+    }
+
+    /// Rebuild `retrieval.context` from the retrieval hits within `budget`
+    /// characters.
+    fn fit_context(&mut self, budget: usize) {
+        let hits = self.retrieval.hits.as_deref().unwrap_or_default();
+        let texts = hits.iter().map(|hit| hit.text.as_str());
+        (self.retrieval.context, self.retrieval.context_truncated) = budget_context(texts, budget);
+    }
+
+    /// Omit the retrieval hits, whose text `context_fragments` repeats.
+    fn compact(&mut self) {
+        self.retrieval.hits = None;
+    }
+
+    /// Drop hits and fragments scoring below `min_score`.
+    ///
+    /// Unscored entries are kept. Citations are rebuilt if any fragment
+    /// is dropped.
+    fn filter_min_score(&mut self, min_score: f32) {
+        let keep = |score: Option<f32>| score.is_none_or(|score| score >= min_score);
+        if let Some(hits) = &mut self.retrieval.hits {
+            hits.retain(|hit| keep(hit.score));
+        }
+        let before = self.context_fragments.len();
+        self.context_fragments.retain(|f| keep(f.score));
+        if self.context_fragments.len() != before {
+            self.rebuild_citations();
+            self.collect_source_frame_ids();
+        }
+    }
+
+    /// Recompute `source_frame_ids` from the context fragments.
+    fn collect_source_frame_ids(&mut self) {
+        let mut ids: Vec<u64> = self.context_fragments.iter().map(|f| f.frame_id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        self.source_frame_ids = ids;
+    }
+
+    /// Reorder fragments for presentation, keeping `rank` and `score`.
+    ///
+    /// Chronological order looks up frame timestamps through `handle`.
+    fn order_fragments(&mut self, order: FragmentOrderJson, handle: &mut MemvidHandle) {
+        let position = |f: &AskContextFragmentJson| {
+            let start = f.range.or(f.chunk_range).map_or(0, |(start, _)| start);
+            (f.frame_id, start)
+        };
+        match order {
+            FragmentOrderJson::Rank => return,
+            FragmentOrderJson::Document => self.context_fragments.sort_by_key(position),
+            FragmentOrderJson::Chronological => {
+                let mut timestamps = HashMap::new();
+                for f in &self.context_fragments {
+                    timestamps.entry(f.frame_id).or_insert_with(|| {
+                        handle
+                            .as_mut()
+                            .frame_by_id(f.frame_id)
+                            .map_or(i64::MAX, |frame| frame.timestamp)
+                    });
+                }
+                self.context_fragments
+                    .sort_by_key(|f| (timestamps[&f.frame_id], position(f)));
+            }
+        }
+        self.rebuild_citations();
+    }
+
+    /// Collapse citations to one per document URI.
+    ///
+    /// Each document keeps its best-scoring chunk's frame and range, and
+    /// takes the position of its first citation. `[n]` markers in the
+    /// answer are rewritten to the collapsed indices.
+    fn dedup_citations(&mut self) {
+        let mut collapsed: Vec<AskCitationJson> = Vec::new();
+        let mut remap = HashMap::new();
+        for citation in self.citations.drain(..) {
+            match collapsed.iter().position(|c| c.uri == citation.uri) {
+                Some(pos) => {
+                    remap.insert(citation.index, pos + 1);
+                    let best = &mut collapsed[pos];
+                    if citation.score > best.score {
+                        best.frame_id = citation.frame_id;
+                        best.chunk_range = citation.chunk_range;
+                        best.score = citation.score;
+                    }
+                }
+                None => {
+                    let index = collapsed.len() + 1;
+                    remap.insert(citation.index, index);
+                    collapsed.push(AskCitationJson { index, ..citation });
+                }
+            }
+        }
+        self.citations = collapsed;
+        if let Some(answer) = &mut self.answer {
+            *answer = remap_citation_markers(answer, &remap);
+        }
+    }
+
+    /// Rebuild citations so citation `i + 1` is context fragment `i`.
+    fn rebuild_citations(&mut self) {
+        self.citations = self
+            .context_fragments
+            .iter()
+            .enumerate()
+            .map(|(i, f)| AskCitationJson {
+                index: i + 1,
+                frame_id: f.frame_id,
+                uri: f.uri.clone(),
+                chunk_range: f.chunk_range,
+                score: f.score,
+            })
+            .collect();
+    }
+}
+
+/// Rewrite `[n]` citation markers through `remap`, leaving others intact.
+fn remap_citation_markers(text: &str, remap: &HashMap<usize, usize>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let marker = after
+            .find(']')
+            .and_then(|close| Some((close, after[..close].parse::<usize>().ok()?)))
+            .and_then(|(close, n)| Some((close, *remap.get(&n)?)));
+        match marker {
+            Some((close, n)) => {
+                out.push_str(&format!("[{n}]"));
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('[');
+                rest = after;
+            }
         }
     }
+    out.push_str(rest);
+    out
+}
+
+/// Rerank candidate passed to the host reranker.
+#[derive(Debug, Serialize)]
+struct RerankCandidateJson<'a> {
+    index: usize,
+    frame_id: u64,
+    uri: &'a str,
+    title: Option<&'a str>,
+    score: Option<f32>,
+    text: &'a str,
+}
+
+/// Rerank entry returned by the host reranker.
+#[derive(Debug, Deserialize)]
+struct RerankEntryJson {
+    index: usize,
+    #[serde(default)]
+    score: Option<f32>,
+}
+
+/// Reranker callback type.
+///
+/// Receives the question and a JSON array of candidates, and returns a JSON
+/// array of `{"index": N, "score": S}` entries ordered best-first. The
+/// returned string must be allocated with `malloc`; ownership transfers to
+/// the FFI layer, which frees it with `free`. Returning NULL aborts the ask
+/// with `RerankFailed`. The callback must not call back into the handle;
+/// such calls fail with `InvalidHandle`.
+pub type MemvidRerankFn = extern "C" fn(
+    query: *const c_char,
+    candidates_json: *const c_char,
+    user_data: *mut c_void,
+) -> *mut c_char;
+
+/// Run the host reranker over the response's context fragments.
+fn rerank(
+    handle: &mut MemvidHandle,
+    response: &mut AskResponseJson,
+    rerank_fn: MemvidRerankFn,
+    user_data: *mut c_void,
+    top_k: usize,
+) -> Result<(), MemvidError> {
+    let candidates: Vec<RerankCandidateJson> = response
+        .context_fragments
+        .iter()
+        .enumerate()
+        .map(|(index, f)| RerankCandidateJson {
+            index,
+            frame_id: f.frame_id,
+            uri: &f.uri,
+            title: f.title.as_deref(),
+            score: f.score,
+            text: &f.text,
+        })
+        .collect();
+    let candidates_json =
+        serde_json::to_string(&candidates).map_err(MemvidError::json_serialize)?;
+
+    let to_cstring = |s: String| {
+        CString::new(s).map_err(|_| {
+            MemvidError::new(
+                MemvidErrorCode::RerankFailed,
+                "rerank input contains an interior null byte",
+            )
+        })
+    };
+    let query = to_cstring(response.question.clone())?;
+    let candidates_cstr = to_cstring(candidates_json)?;
+
+    let result =
+        handle.run_callback(|| rerank_fn(query.as_ptr(), candidates_cstr.as_ptr(), user_data));
+    if result.is_null() {
+        return Err(MemvidError::new(
+            MemvidErrorCode::RerankFailed,
+            "reranker returned NULL",
+        ));
+    }
+
+    // Copy the host string out and release it before any parse error returns
+    let order_json = unsafe { CStr::from_ptr(result) }
+        .to_str()
+        .map(str::to_owned);
+    unsafe { libc::free(result as *mut c_void) };
+
+    let order_json = order_json.map_err(|_| MemvidError::invalid_utf8("reranker output"))?;
+    let order: Vec<RerankEntryJson> =
+        serde_json::from_str(&order_json).map_err(MemvidError::json_parse)?;
+
+    response.apply_rerank(order, top_k)
+}
+
+/// Synthesis callback type.
+///
+/// Receives the assembled prompt (instructions, question, and numbered
+/// context) and returns the answer. The returned string must be allocated
+/// with `malloc`; ownership transfers to the FFI layer, which frees it with
+/// `free`. Returning NULL aborts the ask with `SynthesisFailed`.
+pub type MemvidSynthesizeFn =
+    extern "C" fn(prompt: *const c_char, user_data: *mut c_void) -> *mut c_char;
+
+/// Build the synthesis prompt; context `[n]` is citation `n`.
+fn synthesis_prompt(response: &AskResponseJson) -> String {
+    let mut prompt = String::from(
+        "Answer the question using only the context below. \
+         Cite the context you use with its [n] marker.\n\n",
+    );
+    prompt.push_str(&format!("Question: {}\n\nContext:\n", response.question));
+    for (i, f) in response.context_fragments.iter().enumerate() {
+        match &f.title {
+            Some(title) => prompt.push_str(&format!("\n[{}] {title} ({})\n", i + 1, f.uri)),
+            None => prompt.push_str(&format!("\n[{}] {}\n", i + 1, f.uri)),
+        }
+        prompt.push_str(&f.text);
+        prompt.push('\n');
+    }
+    prompt
+}
+
+/// Have the host synthesize an answer from the response's context.
+fn synthesize(
+    handle: &mut MemvidHandle,
+    response: &mut AskResponseJson,
+    synth_fn: MemvidSynthesizeFn,
+    user_data: *mut c_void,
+) -> Result<(), MemvidError> {
+    response.rebuild_citations();
+    let prompt = CString::new(synthesis_prompt(response)).map_err(|_| {
+        MemvidError::new(
+            MemvidErrorCode::SynthesisFailed,
+            "synthesis prompt contains an interior null byte",
+        )
+    })?;
+
+    let started = Instant::now();
+    let result = handle.run_callback(|| synth_fn(prompt.as_ptr(), user_data));
+    let synthesis_ms = started.elapsed().as_millis();
+    if result.is_null() {
+        return Err(MemvidError::new(
+            MemvidErrorCode::SynthesisFailed,
+            "synthesizer returned NULL",
+        ));
+    }
+
+    let answer = unsafe { CStr::from_ptr(result) }
+        .to_str()
+        .map(str::to_owned);
+    unsafe { libc::free(result as *mut c_void) };
+
+    response.answer = Some(answer.map_err(|_| MemvidError::invalid_utf8("synthesizer output"))?);
+    response.context_only = false;
+    response.stats.synthesis_ms = synthesis_ms;
+    response.stats.latency_ms += synthesis_ms;
+    Ok(())
 }
 
 /// Ask a question using RAG (Retrieval-Augmented Generation).
@@ -332,19 +783,67 @@ impl From<&memvid_core::AskResponse> for AskResponseJson {
 ///   "context_only": true,
 ///   "mode": "hybrid",
 ///   "as_of_frame": null,
-///   "as_of_ts": null
+///   "as_of_ts": null,
+///   "order_fragments": "rank",
+///   "dedup_citations": false,
+///   "min_score": 0.2,
+///   "compact": false,
+///   "context_budget_chars": 8000
 /// }
 /// ```
 ///
 /// Mode values: "lex", "sem", "hybrid" (default: "hybrid")
 ///
+/// `order_fragments` controls the order of `context_fragments`: `"rank"`
+/// (default) keeps retrieval order, `"document"` sorts by frame and offset
+/// within the frame, and `"chronological"` sorts by frame timestamp. Each
+/// fragment keeps its `rank` and `score`, and `citations` are rebuilt so
+/// citation `i + 1` refers to the `i`th fragment.
+///
+/// `dedup_citations` collapses `citations` to one per document URI, keeping
+/// the best-scoring chunk's `frame_id`, `chunk_range`, and `score`, and
+/// renumbers them `1..=n` in order of first appearance. `[n]` markers in a
+/// synthesized `answer` are rewritten to match. `context_fragments` stay
+/// one per chunk, so fragment `i` no longer maps to citation `i + 1`.
+///
+/// `min_score` drops `retrieval.hits` and `context_fragments` scoring below
+/// the threshold; entries without a score are kept. It filters after
+/// ranking without retrieving more, so fewer than `top_k` fragments may be
+/// returned, and `citations` are rebuilt from the kept fragments. With a
+/// reranker it applies to the reranker's scores; with synthesis, dropped
+/// fragments are left out of the prompt.
+///
+/// `compact` omits `retrieval.hits` from the response, keeping the other
+/// `retrieval` fields such as `total_hits` and `elapsed_ms`. The hits'
+/// text is repeated in `context_fragments`, so this roughly halves the
+/// response for hosts that only need fragments and citations.
+///
+/// `context_budget_chars` caps `retrieval.context` as in `memvid_search`:
+/// retrieval hits are joined in rank order, and the first hit that would
+/// overflow the budget and every lower-ranked hit are left out.
+/// `retrieval.context_truncated` reports whether anything was. Fragments
+/// and citations are unaffected.
+///
+/// `retriever_reason` explains why `retriever` differs from the requested
+/// `mode` (e.g. `lex_fallback` because the vec feature is off, no embedder
+/// was registered with `memvid_register_embedder`, or semantic retrieval
+/// found nothing; `timeline_fallback`
+/// because retrieval found no hits). It is null when the requested
+/// retriever ran. memvid-core reports only the retriever, so the reason is
+/// inferred from the build, the embedder, and the results.
+///
+/// `source_frame_ids` lists the distinct frames behind `context_fragments`
+/// in ascending order, independent of `order_fragments`, for use as a
+/// cache key for generated answers.
+///
 /// # Response JSON Schema
 ///
 /// ```json
 /// {
 ///   "question": "What is the capital of France?",
 ///   "mode": "hybrid",
-///   "retriever": "lex",
+///   "retriever": "lex_fallback",
+///   "retriever_reason": "hybrid mode requested but no embedder was provided to embed the question; used lexical search",
 ///   "context_only": true,
 ///   "retrieval": {
 ///     "query": "capital France",
@@ -352,11 +851,13 @@ impl From<&memvid_core::AskResponse> for AskResponseJson {
 ///     "total_hits": 3,
 ///     "hits": [...],
 ///     "context": "...",
-///     "next_cursor": null
+///     "next_cursor": null,
+///     "context_truncated": false
 ///   },
 ///   "answer": null,
 ///   "citations": [...],
 ///   "context_fragments": [...],
+///   "source_frame_ids": [3, 7, 12],
 ///   "stats": {
 ///     "retrieval_ms": 5,
 ///     "synthesis_ms": 0,
@@ -381,30 +882,266 @@ pub unsafe extern "C" fn memvid_ask(
         None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
     };
 
-    let json_str = match unsafe { cstr_to_string(request_json, "request_json") } {
-        Ok(s) => s,
-        Err(e) => return unsafe { set_error_null(error, e) },
-    };
-
-    let request_json: AskRequestJson = match serde_json::from_str(&json_str) {
+    let request_json: AskRequestJson = match unsafe { cstr_to_json(request_json, "request_json") } {
         Ok(r) => r,
-        Err(e) => return unsafe { set_error_null(error, MemvidError::json_parse(e)) },
+        Err(e) => return unsafe { set_error_null(error, e) },
     };
 
+    let order = request_json.order_fragments;
+    let dedup_citations = request_json.dedup_citations;
+    let min_score = request_json.min_score;
+    let compact = request_json.compact;
+    let context_budget = request_json.context_budget_chars;
     let request = request_json.into_request();
 
-    // Call ask without an embedder (context_only mode or lex-only)
-    match handle.as_mut().ask(request, None::<&dyn memvid_core::VecEmbedder>) {
+    // Semantic retrieval needs a registered embedder; without one core
+    // falls back to lexical search
+    let embedder = handle.embedder();
+    match handle.as_mut().ask(request, embedder.as_ref()) {
         Ok(response) => {
-            let json_response = AskResponseJson::from(&response);
-            match serde_json::to_string(&json_response) {
-                Ok(json) => {
-                    unsafe { set_ok(error) };
-                    string_to_cstr(json)
-                }
-                Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
+            let mut response = AskResponseJson::from(&response);
+            response.explain_retriever(embedder.is_some());
+            if let Some(min_score) = min_score {
+                response.filter_min_score(min_score);
+            }
+            response.order_fragments(order, handle);
+            if dedup_citations {
+                response.dedup_citations();
+            }
+            if let Some(budget) = context_budget {
+                response.fit_context(budget);
             }
+            if compact {
+                response.compact();
+            }
+            unsafe { json_to_cstr(&response, error) }
         }
         Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
     }
 }
+
+/// Ask a question and rerank the retrieved candidates with a host callback.
+///
+/// Retrieval runs as in `memvid_ask`, then the context fragments are passed
+/// to `rerank_fn`. Its ordering and scores determine the final
+/// `context_fragments` and `citations`, truncated to `top_k`.
+/// `retrieval.hits` follow the same order and truncation, and
+/// `retrieval.context` is rebuilt from them.
+///
+/// Set `"rerank_candidates"` in the request to retrieve a larger pool than
+/// `top_k` for the reranker to choose from. `"order_fragments"` is applied
+/// after reranking.
+///
+/// The callback must not call back into this handle; such calls fail with
+/// `InvalidHandle`.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `request_json`: JSON string with ask parameters (same as `memvid_ask`)
+/// - `rerank_fn`: Reranker callback (see `MemvidRerankFn`)
+/// - `user_data`: Opaque pointer passed through to `rerank_fn`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with ask response on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Reranker JSON
+///
+/// Candidates passed to the reranker:
+///
+/// ```json
+/// [
+///   {"index": 0, "frame_id": 42, "uri": "mv2://doc.txt", "title": null,
+///    "score": 0.5, "text": "..."}
+/// ]
+/// ```
+///
+/// Expected output (best first; omitted candidates are dropped):
+///
+/// ```json
+/// [{"index": 3, "score": 0.97}, {"index": 0, "score": 0.81}]
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `request_json` must be a valid null-terminated UTF-8 string
+/// - `rerank_fn` must be a valid function pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_ask_with_reranker(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    rerank_fn: Option<MemvidRerankFn>,
+    user_data: *mut c_void,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let rerank_fn = match rerank_fn {
+        Some(f) => f,
+        None => return unsafe { set_error_null(error, MemvidError::null_pointer("rerank_fn")) },
+    };
+
+    let mut request: AskRequestJson = match unsafe { cstr_to_json(request_json, "request_json") } {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let top_k = request.top_k;
+    let order = request.order_fragments;
+    let dedup_citations = request.dedup_citations;
+    let min_score = request.min_score;
+    let compact = request.compact;
+    let context_budget = request.context_budget_chars;
+    if let Some(candidates) = request.rerank_candidates {
+        request.top_k = candidates.max(top_k);
+    }
+
+    let request = request.into_request();
+    let embedder = handle.embedder();
+    let response = match handle.as_mut().ask(request, embedder.as_ref()) {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let mut response = AskResponseJson::from(&response);
+    response.explain_retriever(embedder.is_some());
+    if let Err(e) = rerank(handle, &mut response, rerank_fn, user_data, top_k) {
+        return unsafe { set_error_null(error, e) };
+    }
+    if let Some(min_score) = min_score {
+        response.filter_min_score(min_score);
+    }
+    response.order_fragments(order, handle);
+    if dedup_citations {
+        response.dedup_citations();
+    }
+    if let Some(budget) = context_budget {
+        response.fit_context(budget);
+    }
+    if compact {
+        response.compact();
+    }
+
+    unsafe { json_to_cstr(&response, error) }
+}
+
+/// Ask a question and synthesize the answer with a host callback.
+///
+/// Retrieval runs as in `memvid_ask` in context-only mode, then the
+/// question and context fragments are assembled into a prompt for
+/// `synth_fn`, whose result becomes `answer`. Context item `[n]` in
+/// the prompt is citation `n` in the response, so `[n]` markers in the
+/// answer resolve against `citations`. The response has `context_only`
+/// false and `stats.synthesis_ms` set to the callback's run time.
+///
+/// `"order_fragments"` is applied before the prompt is built, so it also
+/// orders the prompt's context. With `"dedup_citations"`, markers in the
+/// answer are rewritten to the collapsed citations.
+///
+/// The callback must not call back into this handle; such calls fail with
+/// `InvalidHandle`.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `request_json`: JSON string with ask parameters (same as `memvid_ask`;
+///   `context_only` is ignored)
+/// - `synth_fn`: Synthesis callback (see `MemvidSynthesizeFn`)
+/// - `user_data`: Opaque pointer passed through to `synth_fn`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with ask response on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `SynthesisFailed` if `synth_fn` returns NULL
+/// - `InvalidUtf8` if its answer is not valid UTF-8
+///
+/// # Prompt Format
+///
+/// ```text
+/// Answer the question using only the context below. Cite the context you use with its [n] marker.
+///
+/// Question: What is the capital of France?
+///
+/// Context:
+///
+/// [1] Geography (mv2://geo.txt)
+/// Paris is the capital of France.
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `request_json` must be a valid null-terminated UTF-8 string
+/// - `synth_fn` must be a valid function pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_ask_with_synthesis(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    synth_fn: Option<MemvidSynthesizeFn>,
+    user_data: *mut c_void,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let synth_fn = match synth_fn {
+        Some(f) => f,
+        None => return unsafe { set_error_null(error, MemvidError::null_pointer("synth_fn")) },
+    };
+
+    let mut request: AskRequestJson = match unsafe { cstr_to_json(request_json, "request_json") } {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let order = request.order_fragments;
+    let dedup_citations = request.dedup_citations;
+    let min_score = request.min_score;
+    let compact = request.compact;
+    let context_budget = request.context_budget_chars;
+    request.context_only = true;
+
+    let request = request.into_request();
+    let embedder = handle.embedder();
+    let response = match handle.as_mut().ask(request, embedder.as_ref()) {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let mut response = AskResponseJson::from(&response);
+    response.explain_retriever(embedder.is_some());
+    if let Some(min_score) = min_score {
+        response.filter_min_score(min_score);
+    }
+    response.order_fragments(order, handle);
+    if let Err(e) = synthesize(handle, &mut response, synth_fn, user_data) {
+        return unsafe { set_error_null(error, e) };
+    }
+    if dedup_citations {
+        response.dedup_citations();
+    }
+    if let Some(budget) = context_budget {
+        response.fit_context(budget);
+    }
+    if compact {
+        response.compact();
+    }
+
+    unsafe { json_to_cstr(&response, error) }
+}
@@ -1,8 +1,127 @@
 //! Doctor (file repair/maintenance) functions.
 
-use crate::error::MemvidError;
-use crate::util::{cstr_to_path, cstr_to_string, set_error_null, set_ok, string_to_cstr};
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::handle::MemvidHandle;
+use crate::util::{
+    cstr_to_option_json, cstr_to_path, cstr_to_string, json_to_cstr, set_error, set_error_null,
+    set_ok,
+};
+use crate::verify::{VerificationCheckJson, VerificationStatusJson};
+use memvid_core::types::FrameStatus;
+use memvid_core::DoctorFindingCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
 use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Stable code of findings whose core code this library does not know.
+const UNCLASSIFIED_FINDING: &str = "UNCLASSIFIED";
+
+/// Stable code of a memvid-core finding code.
+///
+/// The constants are part of this library's ABI: a renamed core variant
+/// keeps its constant, and a new one is `UNCLASSIFIED` until listed here.
+fn finding_code(code: DoctorFindingCode) -> &'static str {
+    #[allow(unreachable_patterns)]
+    match code {
+        DoctorFindingCode::HeaderDecodeFailure => "HEADER_DECODE_FAILURE",
+        DoctorFindingCode::HeaderFooterOffsetMismatch => "HEADER_FOOTER_OFFSET_MISMATCH",
+        DoctorFindingCode::TocDecodeFailure => "TOC_DECODE_FAILURE",
+        DoctorFindingCode::TocChecksumMismatch => "TOC_CHECKSUM_MISMATCH",
+        DoctorFindingCode::WalChecksumMismatch => "WAL_CHECKSUM_MISMATCH",
+        DoctorFindingCode::TimeIndexMissing => "TIME_INDEX_MISSING",
+        DoctorFindingCode::TimeIndexChecksumMismatch => "TIME_INDEX_CHECKSUM_MISMATCH",
+        DoctorFindingCode::LexIndexMissing => "LEX_INDEX_MISSING",
+        DoctorFindingCode::LexIndexCorrupt => "LEX_INDEX_CORRUPT",
+        DoctorFindingCode::VecIndexCorrupt => "VEC_INDEX_CORRUPT",
+        _ => UNCLASSIFIED_FINDING,
+    }
+}
+
+/// Call `f` on every finding object under a `findings` array in `value`.
+fn visit_findings(
+    value: &mut serde_json::Value,
+    f: &mut dyn FnMut(&mut serde_json::Map<String, serde_json::Value>),
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match child {
+                    serde_json::Value::Array(items) if key == "findings" => {
+                        for item in items {
+                            if let serde_json::Value::Object(finding) = item {
+                                f(finding);
+                            }
+                        }
+                    }
+                    _ => visit_findings(child, f),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                visit_findings(item, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Serialize a doctor result, adding a stable `finding_code` to every
+/// finding.
+///
+/// Core's own fields are left as they are, so a plan serialized here is
+/// still a valid `DoctorPlan` for `memvid_doctor_apply`.
+unsafe fn doctor_json_to_cstr<T: Serialize>(value: &T, error: *mut MemvidError) -> *mut c_char {
+    let mut value = match serde_json::to_value(value) {
+        Ok(v) => v,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
+    };
+    visit_findings(&mut value, &mut |finding| {
+        let code = finding
+            .get("code")
+            .cloned()
+            .and_then(|v| serde_json::from_value::<DoctorFindingCode>(v).ok())
+            .map_or(UNCLASSIFIED_FINDING, finding_code);
+        finding.insert(
+            "finding_code".to_string(),
+            serde_json::Value::String(code.to_string()),
+        );
+    });
+    unsafe { json_to_cstr(&value, error) }
+}
+
+/// Doctor options as read from JSON by memvid-core's `DoctorOptions`,
+/// which has no schema of its own. Only used to describe the schema.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct DoctorOptionsJson {
+    /// Rebuild the time index
+    #[serde(default)]
+    rebuild_time_index: bool,
+    /// Rebuild the lexical index
+    #[serde(default)]
+    rebuild_lex_index: bool,
+    /// Rebuild the vector index
+    #[serde(default)]
+    rebuild_vec_index: bool,
+    /// Compact the file, dropping deleted frames
+    #[serde(default)]
+    vacuum: bool,
+    /// Plan repairs without writing
+    #[serde(default)]
+    dry_run: bool,
+    /// Suppress progress output
+    #[serde(default)]
+    quiet: bool,
+}
+
+/// JSON Schema of the doctor options, for `memvid_request_schema`.
+pub(crate) fn request_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(DoctorOptionsJson)
+}
 
 /// Run doctor diagnostics and optionally repair a memory file.
 ///
@@ -40,12 +159,19 @@ use std::os::raw::c_char;
 ///   "plan": { ... },
 ///   "status": "clean|healed|partial|failed|plan_only",
 ///   "phases": [...],
-///   "findings": [...],
+///   "findings": [{"code": "lex_index_missing", "finding_code": "LEX_INDEX_MISSING", ...}],
 ///   "metrics": { "total_duration_ms": 123, ... },
 ///   "verification": { ... }
 /// }
 /// ```
 ///
+/// Every finding carries a stable `finding_code` next to core's own
+/// fields, one of: `HEADER_DECODE_FAILURE`, `HEADER_FOOTER_OFFSET_MISMATCH`,
+/// `TOC_DECODE_FAILURE`, `TOC_CHECKSUM_MISMATCH`, `WAL_CHECKSUM_MISMATCH`,
+/// `TIME_INDEX_MISSING`, `TIME_INDEX_CHECKSUM_MISMATCH`, `LEX_INDEX_MISSING`,
+/// `LEX_INDEX_CORRUPT`, `VEC_INDEX_CORRUPT`, or `UNCLASSIFIED` for a code
+/// this library does not know. Branch on `finding_code`, not the text.
+///
 /// # Safety
 ///
 /// - `path` must be a valid null-terminated UTF-8 string
@@ -75,13 +201,7 @@ pub unsafe extern "C" fn memvid_doctor(
     };
 
     match memvid_core::Memvid::doctor(&path, options) {
-        Ok(report) => match serde_json::to_string(&report) {
-            Ok(json) => {
-                unsafe { set_ok(error) };
-                string_to_cstr(json)
-            }
-            Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
-        },
+        Ok(report) => unsafe { doctor_json_to_cstr(&report, error) },
         Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
     }
 }
@@ -102,6 +222,9 @@ pub unsafe extern "C" fn memvid_doctor(
 /// JSON string with doctor plan on success, NULL on failure.
 /// Caller must free with `memvid_string_free()`.
 ///
+/// Findings carry a `finding_code` as in `memvid_doctor`; the plan can be
+/// passed to `memvid_doctor_apply` unchanged.
+///
 /// # Safety
 ///
 /// - `path` must be a valid null-terminated UTF-8 string
@@ -131,13 +254,7 @@ pub unsafe extern "C" fn memvid_doctor_plan(
     };
 
     match memvid_core::Memvid::doctor_plan(&path, options) {
-        Ok(plan) => match serde_json::to_string(&plan) {
-            Ok(json) => {
-                unsafe { set_ok(error) };
-                string_to_cstr(json)
-            }
-            Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
-        },
+        Ok(plan) => unsafe { doctor_json_to_cstr(&plan, error) },
         Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
     }
 }
@@ -180,13 +297,548 @@ pub unsafe extern "C" fn memvid_doctor_apply(
     };
 
     match memvid_core::Memvid::doctor_apply(&path, plan) {
-        Ok(report) => match serde_json::to_string(&report) {
-            Ok(json) => {
-                unsafe { set_ok(error) };
-                string_to_cstr(json)
+        Ok(report) => unsafe { doctor_json_to_cstr(&report, error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// One doctor run of `memvid_doctor_cancellable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DoctorStep {
+    /// Diagnose and apply doctor's own repairs, with no explicit rebuild
+    Diagnose,
+    RebuildTimeIndex,
+    RebuildLexIndex,
+    RebuildVecIndex,
+    Vacuum,
+}
+
+impl DoctorStep {
+    /// Split `options` into steps, each a separate doctor run.
+    fn plan(options: &memvid_core::DoctorOptions) -> Vec<Self> {
+        if options.dry_run {
+            return vec![Self::Diagnose];
+        }
+        let steps: Vec<Self> = [
+            (options.rebuild_time_index, Self::RebuildTimeIndex),
+            (options.rebuild_lex_index, Self::RebuildLexIndex),
+            (options.rebuild_vec_index, Self::RebuildVecIndex),
+            (options.vacuum, Self::Vacuum),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, step)| enabled.then_some(step))
+        .collect();
+        if steps.is_empty() {
+            vec![Self::Diagnose]
+        } else {
+            steps
+        }
+    }
+
+    /// Doctor options running only this step.
+    fn options(self, base: &memvid_core::DoctorOptions) -> memvid_core::DoctorOptions {
+        let mut options = memvid_core::DoctorOptions {
+            dry_run: base.dry_run,
+            quiet: base.quiet,
+            ..Default::default()
+        };
+        match self {
+            Self::Diagnose => {}
+            Self::RebuildTimeIndex => options.rebuild_time_index = true,
+            Self::RebuildLexIndex => options.rebuild_lex_index = true,
+            Self::RebuildVecIndex => options.rebuild_vec_index = true,
+            Self::Vacuum => options.vacuum = true,
+        }
+        options
+    }
+}
+
+/// A completed step and its doctor report.
+#[derive(Debug, Serialize)]
+struct DoctorStepReportJson {
+    step: DoctorStep,
+    report: memvid_core::DoctorReport,
+}
+
+/// Report of `memvid_doctor_cancellable`.
+#[derive(Debug, Serialize)]
+struct CancellableDoctorReportJson {
+    steps: Vec<DoctorStepReportJson>,
+}
+
+/// Run doctor step by step, checking `cancel` before each step.
+fn run_doctor_steps(
+    path: &Path,
+    options: &memvid_core::DoctorOptions,
+    cancel: Option<&AtomicU8>,
+) -> Result<CancellableDoctorReportJson, MemvidError> {
+    let mut steps: Vec<DoctorStepReportJson> = Vec::new();
+    for step in DoctorStep::plan(options) {
+        if cancel.is_some_and(|flag| flag.load(Ordering::Relaxed) != 0) {
+            let done: Vec<DoctorStep> = steps.iter().map(|s| s.step).collect();
+            return Err(MemvidError::new(
+                MemvidErrorCode::Cancelled,
+                format!("doctor cancelled before {step:?}; completed steps: {done:?}"),
+            ));
+        }
+        let report = memvid_core::Memvid::doctor(path, step.options(options))
+            .map_err(MemvidError::from_core_error)?;
+        steps.push(DoctorStepReportJson { step, report });
+    }
+    Ok(CancellableDoctorReportJson { steps })
+}
+
+/// Run doctor with a cancellation flag polled between steps.
+///
+/// memvid-core's doctor runs as one uninterruptible call, so the requested
+/// work is split into steps, each a separate doctor run: one per requested
+/// rebuild (time, lex, then vec index), then vacuum. With no rebuild or
+/// vacuum requested, or with `dry_run`, there is a single `diagnose` step.
+///
+/// The flag is read before each step. Once it is nonzero, no further step
+/// starts and the call fails with `Cancelled`; steps that already ran keep
+/// their changes, and the file is left as the last completed step wrote it.
+/// A step in progress always runs to completion, so cancellation can wait
+/// for the longest single rebuild. Every boundary between steps is a safe
+/// point: each rebuild is independent of the others.
+///
+/// This is a static function that does not require an open handle.
+/// The file should NOT be open when running doctor.
+///
+/// # Parameters
+///
+/// - `path`: Path to the .mv2 file (null-terminated UTF-8 string)
+/// - `options_json`: JSON string with doctor options (as `memvid_doctor`;
+///   NULL for defaults)
+/// - `cancel_flag`: Caller-owned byte, set to 1 to cancel (may be NULL)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with the step reports on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `Cancelled` if the flag was set before a step; the message lists the
+///   completed steps
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "steps": [
+///     {"step": "rebuild_lex_index", "report": { "status": "healed", ... }},
+///     {"step": "vacuum", "report": { ... }}
+///   ]
+/// }
+/// ```
+///
+/// Step values: "diagnose", "rebuild_time_index", "rebuild_lex_index",
+/// "rebuild_vec_index", "vacuum". Each report has the `memvid_doctor`
+/// response schema.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `cancel_flag` must be NULL or point to a byte that stays valid for the
+///   duration of the call; it may be written concurrently (e.g. from a
+///   signal handler)
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_doctor_cancellable(
+    path: *const c_char,
+    options_json: *const c_char,
+    cancel_flag: *const u8,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let options: memvid_core::DoctorOptions =
+        match unsafe { cstr_to_option_json(options_json, "options_json") } {
+            Ok(o) => o,
+            Err(e) => return unsafe { set_error_null(error, e) },
+        };
+
+    // AtomicU8 has the same layout as u8.
+    let cancel = unsafe { (cancel_flag as *const AtomicU8).as_ref() };
+
+    match run_doctor_steps(&path, &options, cancel) {
+        Ok(report) => unsafe { doctor_json_to_cstr(&report, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Index that can be rebuilt on its own.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum IndexKind {
+    Lex,
+    Vec,
+    Clip,
+    Time,
+}
+
+impl IndexKind {
+    /// Error if the index's feature is not compiled in.
+    fn check_enabled(self) -> Result<(), MemvidError> {
+        let (enabled, code) = match self {
+            Self::Lex => (cfg!(feature = "lex"), MemvidErrorCode::LexNotEnabled),
+            Self::Vec => (cfg!(feature = "vec"), MemvidErrorCode::VecNotEnabled),
+            Self::Clip => (cfg!(feature = "clip"), MemvidErrorCode::ClipNotEnabled),
+            Self::Time => (true, MemvidErrorCode::Ok),
+        };
+        if enabled {
+            Ok(())
+        } else {
+            Err(MemvidError::new(
+                code,
+                format!("{:?} index is not enabled", self),
+            ))
+        }
+    }
+
+    /// Doctor options that rebuild only this index.
+    fn doctor_options(self) -> Result<memvid_core::DoctorOptions, MemvidError> {
+        let mut options = memvid_core::DoctorOptions::default();
+        match self {
+            Self::Lex => options.rebuild_lex_index = true,
+            Self::Vec => options.rebuild_vec_index = true,
+            Self::Time => options.rebuild_time_index = true,
+            Self::Clip => {
+                return Err(MemvidError::new(
+                    MemvidErrorCode::FeatureUnavailable,
+                    "memvid-core cannot rebuild the CLIP index",
+                ));
+            }
+        }
+        Ok(options)
+    }
+
+    /// Prefix of the verification check names covering this index.
+    fn check_prefix(self) -> &'static str {
+        match self {
+            Self::Lex => "Lex",
+            Self::Vec => "Vec",
+            Self::Clip => "Clip",
+            Self::Time => "TimeIndex",
+        }
+    }
+}
+
+/// Post-rebuild verification of a single index.
+#[derive(Debug, Serialize)]
+struct IndexVerificationJson {
+    status: VerificationStatusJson,
+    checks: Vec<VerificationCheckJson>,
+}
+
+impl IndexVerificationJson {
+    /// Keep the checks for `index`; any failure fails the index.
+    fn from_report(report: &memvid_core::VerificationReport, index: IndexKind) -> Self {
+        let checks: Vec<VerificationCheckJson> = report
+            .checks
+            .iter()
+            .filter(|c| c.name.starts_with(index.check_prefix()))
+            .map(VerificationCheckJson::from)
+            .collect();
+
+        let status = if checks
+            .iter()
+            .any(|c| matches!(c.status, VerificationStatusJson::Failed))
+        {
+            VerificationStatusJson::Failed
+        } else if checks
+            .iter()
+            .any(|c| matches!(c.status, VerificationStatusJson::Passed))
+        {
+            VerificationStatusJson::Passed
+        } else {
+            VerificationStatusJson::Skipped
+        };
+
+        Self { status, checks }
+    }
+}
+
+/// Rebuild report for JSON serialization.
+#[derive(Debug, Serialize)]
+struct RebuildIndexReportJson {
+    index: IndexKind,
+    doctor: memvid_core::DoctorReport,
+    verification: IndexVerificationJson,
+}
+
+/// Rebuild a single index and verify it.
+///
+/// A targeted alternative to `memvid_doctor`: runs doctor with only the
+/// rebuild for `index` enabled, then a deep verification whose checks are
+/// filtered to that index.
+///
+/// This is a static function that does not require an open handle.
+/// The file should NOT be open when rebuilding.
+///
+/// # Parameters
+///
+/// - `path`: Path to the .mv2 file (null-terminated UTF-8 string)
+/// - `index`: One of `"lex"`, `"vec"`, `"clip"`, `"time"`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with rebuild report on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `LexNotEnabled` / `VecNotEnabled` / `ClipNotEnabled` if the index's
+///   feature is not compiled in
+/// - `FeatureUnavailable` for `"clip"`, which memvid-core cannot rebuild
+/// - `InvalidQuery` for an unknown index name
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "index": "lex",
+///   "doctor": { "status": "healed", "phases": [...], ... },
+///   "verification": {
+///     "status": "passed",
+///     "checks": [
+///       {"name": "LexIndexDecode", "status": "passed", "details": "..."}
+///     ]
+///   }
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string
+/// - `index` must be a valid null-terminated UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_rebuild_index(
+    path: *const c_char,
+    index: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let index: IndexKind = match unsafe { cstr_to_string(index, "index") } {
+        Ok(name) => match serde_json::from_value(serde_json::Value::String(name.clone())) {
+            Ok(i) => i,
+            Err(_) => {
+                let err = MemvidError::new(
+                    MemvidErrorCode::InvalidQuery,
+                    format!("unknown index {name:?}; expected lex, vec, clip, or time"),
+                );
+                return unsafe { set_error_null(error, err) };
             }
-            Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
         },
-        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let options = match index.check_enabled().and_then(|()| index.doctor_options()) {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let doctor = match memvid_core::Memvid::doctor(&path, options) {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let verification = match memvid_core::Memvid::verify(&path, true) {
+        Ok(r) => IndexVerificationJson::from_report(&r, index),
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let report = RebuildIndexReportJson {
+        index,
+        doctor,
+        verification,
+    };
+    unsafe { doctor_json_to_cstr(&report, error) }
+}
+
+/// Identity of an active frame: ID, timestamp, URI, title, and parent.
+type FrameIdentity = (u64, i64, Option<String>, Option<String>, Option<u64>);
+
+/// Identities of `handle`'s active frames, in ID order.
+fn active_frame_identities(handle: &mut MemvidHandle) -> Vec<FrameIdentity> {
+    handle
+        .frames()
+        .filter(|f| matches!(f.status, FrameStatus::Active))
+        .map(|f| (f.id, f.timestamp, f.uri, f.title, f.parent_id))
+        .collect()
+}
+
+/// Vacuum `copy` and check that its active frames kept their IDs.
+fn vacuum_copy(copy: &Path, expected: &[FrameIdentity]) -> Result<(), MemvidError> {
+    let options = memvid_core::DoctorOptions {
+        vacuum: true,
+        ..Default::default()
+    };
+    memvid_core::Memvid::doctor(copy, options).map_err(MemvidError::from_core_error)?;
+
+    let memvid = memvid_core::Memvid::open_read_only(copy).map_err(MemvidError::from_core_error)?;
+    let mut vacuumed = MemvidHandle::new_read_only(memvid, copy.to_path_buf());
+    if active_frame_identities(&mut vacuumed) != expected {
+        return Err(MemvidError::new(
+            MemvidErrorCode::Doctor,
+            "vacuum would change the IDs of active frames; the file was left unchanged",
+        ));
+    }
+    Ok(())
+}
+
+/// Result of `memvid_vacuum_report`.
+#[derive(Debug, Serialize)]
+struct VacuumReportJson {
+    bytes_before: u64,
+    bytes_after: u64,
+    reclaimed_bytes: u64,
+}
+
+/// Commit, then vacuum the handle's file in place.
+///
+/// The vacuum runs on a copy next to the file, which replaces it only if
+/// every active frame kept its ID.
+fn vacuum_handle(handle: &mut MemvidHandle) -> Result<VacuumReportJson, MemvidError> {
+    handle.check_writable()?;
+    if handle.has_pending_changes() {
+        handle.commit().map_err(MemvidError::from_core_error)?;
+    }
+
+    let expected = active_frame_identities(handle);
+    let file_size = |path: &Path| fs::metadata(path).map(|m| m.len()).map_err(MemvidError::io);
+    let bytes_before = file_size(handle.path())?;
+
+    handle.reopen_after(|path| {
+        let mut copy = path.as_os_str().to_owned();
+        copy.push(".vacuum");
+        let copy = PathBuf::from(copy);
+
+        let result = fs::copy(path, &copy)
+            .map_err(MemvidError::io)
+            .and_then(|_| vacuum_copy(&copy, &expected))
+            .and_then(|()| fs::rename(&copy, path).map_err(MemvidError::io));
+        if result.is_err() {
+            let _ = fs::remove_file(&copy);
+        }
+        result
+    })?;
+
+    let bytes_after = file_size(handle.path())?;
+    Ok(VacuumReportJson {
+        bytes_before,
+        bytes_after,
+        reclaimed_bytes: bytes_before.saturating_sub(bytes_after),
+    })
+}
+
+/// Remove deleted frames and compact the file of an open handle.
+///
+/// Commits staged changes, then runs doctor's vacuum on the file without
+/// closing the handle: the memory is closed for the duration and reopened
+/// afterwards. Frame IDs are preserved. The vacuum runs on a copy
+/// (`<path>.vacuum`, so it needs that much free disk space), and the copy
+/// replaces the file only if every active frame kept its ID, timestamp,
+/// URI, title, and parent; otherwise the call fails and the file is left
+/// as it was. Like `memvid_refresh`, it invalidates outstanding cursors.
+///
+/// Use `memvid_vacuum_report` for the number of bytes reclaimed.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Errors
+///
+/// - `ReadOnly` if the handle was opened read-only
+/// - `Doctor` if vacuum would change frame IDs
+/// - `Io` if the copy cannot be written or moved into place
+///
+/// If the file cannot be reopened afterwards, the handle fails every later
+/// call with `InvalidHandle` and must be closed.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_vacuum(handle: *mut MemvidHandle, error: *mut MemvidError) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    match vacuum_handle(handle) {
+        Ok(_) => {
+            unsafe { set_ok(error) };
+            1
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
+
+/// Vacuum an open handle, reporting the space reclaimed.
+///
+/// Same as `memvid_vacuum`, returning file sizes instead of a flag.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with the report on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "bytes_before": 1048576,
+///   "bytes_after": 524288,
+///   "reclaimed_bytes": 524288
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Same as `memvid_vacuum`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_vacuum_report(
+    handle: *mut MemvidHandle,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    match vacuum_handle(handle) {
+        Ok(report) => unsafe { json_to_cstr(&report, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
     }
 }
@@ -0,0 +1,161 @@
+//! Host-provided embedders for vector indexing and semantic retrieval.
+
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::handle::{run_callback_for, MemvidHandle};
+use crate::util::{set_error, set_ok};
+use std::ffi::{c_void, CString};
+use std::os::raw::c_char;
+
+/// Embedding callback type.
+///
+/// Receives a null-terminated UTF-8 text and writes its embedding, exactly
+/// `dim` floats, to `out`. Returns 1 on success, 0 on failure.
+pub type MemvidEmbedFn =
+    extern "C" fn(text: *const c_char, out: *mut f32, dim: u32, user_data: *mut c_void) -> i32;
+
+/// `VecEmbedder` calling a host callback.
+#[derive(Clone, Copy)]
+pub struct HostEmbedder {
+    callback: MemvidEmbedFn,
+    user_data: *mut c_void,
+    dim: u32,
+    /// Address of the handle the embedder is registered on
+    handle: usize,
+}
+
+// SAFETY: `user_data` is only handed back to the callback, which the
+// caller guarantees may run on whichever thread calls into the handle
+// (documented on `memvid_register_embedder`).
+unsafe impl Send for HostEmbedder {}
+
+impl HostEmbedder {
+    /// Embed `text` through the callback, failing with a reason.
+    fn try_embed(&self, text: &str) -> Result<Vec<f32>, &'static str> {
+        // Null bytes cannot cross the C string boundary.
+        let text = CString::new(text.replace('\0', "")).unwrap_or_default();
+        let mut embedding = vec![0.0f32; self.dim as usize];
+        let ok = run_callback_for(self.handle, || {
+            (self.callback)(
+                text.as_ptr(),
+                embedding.as_mut_ptr(),
+                self.dim,
+                self.user_data,
+            )
+        });
+        if ok == 0 {
+            return Err("callback reported failure");
+        }
+        if embedding.iter().any(|v| !v.is_finite()) {
+            return Err("callback returned a non-finite value");
+        }
+        Ok(embedding)
+    }
+
+    /// Embed `text` through the callback.
+    pub fn embed(&self, text: &str) -> Result<Vec<f32>, MemvidError> {
+        self.try_embed(text).map_err(|reason| {
+            MemvidError::new(
+                MemvidErrorCode::EmbeddingFailed,
+                format!("host embedder: {reason}"),
+            )
+        })
+    }
+}
+
+impl std::fmt::Debug for HostEmbedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostEmbedder")
+            .field("dim", &self.dim)
+            .finish_non_exhaustive()
+    }
+}
+
+impl memvid_core::VecEmbedder for HostEmbedder {
+    fn embed_query(&self, text: &str) -> memvid_core::Result<Vec<f32>> {
+        self.try_embed(text)
+            .map_err(|reason| memvid_core::MemvidError::EmbeddingFailed {
+                reason: format!("host embedder: {reason}").into(),
+            })
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.dim as usize
+    }
+}
+
+/// Register a host embedder on a handle.
+///
+/// Once registered, the handle embeds with `embed_callback`:
+///
+/// - puts (`memvid_put_bytes_with_options` and the functions built on it)
+///   store an embedding of the frame's `search_text`, or of the content
+///   decoded as UTF-8, in the vector index
+/// - `"sem"` and `"hybrid"` searches without `query_embedding` embed the
+///   query
+/// - `memvid_ask` and its variants embed the question for semantic and
+///   hybrid retrieval
+///
+/// Puts and searches only use the vector index when the library is built
+/// with the `vec` feature. Registering again replaces the embedder;
+/// passing NULL removes it.
+///
+/// The callback runs on the calling thread, inside the FFI call that
+/// needs an embedding; calls back into the same handle from it fail with
+/// `InvalidHandle`. A handle moved to another thread takes its embedder
+/// along, so the callback and `user_data` must be usable from any thread
+/// that calls into the handle.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `dim`: Embedding dimension; must match the memory's vector index
+/// - `embed_callback`: Embedding callback (see `MemvidEmbedFn`), or NULL
+/// - `user_data`: Opaque pointer passed through to `embed_callback`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Errors
+///
+/// - `InvalidQuery` if `dim` is 0 with a non-NULL callback
+///
+/// A callback that returns 0 or writes a non-finite value fails the call
+/// that needed the embedding with `EmbeddingFailed`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `embed_callback` must be a valid function pointer or NULL
+/// - `user_data` must stay valid until the embedder is replaced or the
+///   handle is closed
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_register_embedder(
+    handle: *mut MemvidHandle,
+    dim: u32,
+    embed_callback: Option<MemvidEmbedFn>,
+    user_data: *mut c_void,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle_ptr = handle as usize;
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if embed_callback.is_some() && dim == 0 {
+        let err = MemvidError::new(MemvidErrorCode::InvalidQuery, "dim must be positive");
+        return unsafe { set_error(error, err) };
+    }
+
+    handle.set_embedder(embed_callback.map(|callback| HostEmbedder {
+        callback,
+        user_data,
+        dim,
+        handle: handle_ptr,
+    }));
+    unsafe { set_ok(error) };
+    1
+}
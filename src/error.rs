@@ -2,9 +2,20 @@
 //!
 //! This module provides C-compatible error types and conversion from memvid-core errors.
 
-use std::ffi::CString;
+use std::cell::RefCell;
+use std::error::Error as _;
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 
+thread_local! {
+    /// Cause chain of the most recent core error, keyed by its message pointer.
+    static CAUSE_CHAIN: RefCell<Option<(usize, Vec<String>)>> = const { RefCell::new(None) };
+
+    /// Code and message of the most recent failing call on this thread.
+    static LAST_ERROR: RefCell<(MemvidErrorCode, Option<CString>)> =
+        const { RefCell::new((MemvidErrorCode::Ok, None)) };
+}
+
 /// Error codes for FFI functions.
 ///
 /// These codes are stable and can be matched in C/Crystal code.
@@ -137,6 +148,22 @@ pub enum MemvidErrorCode {
     JsonParse = 102,
     /// Invalid handle
     InvalidHandle = 103,
+    /// JSON serialization error (library output, not caller input)
+    JsonSerialize = 104,
+    /// Operation cancelled by a host callback
+    Cancelled = 105,
+    /// Caller-provided buffer too small for the result
+    BufferTooSmall = 106,
+    /// Frame to restore is not deleted
+    FrameNotDeleted = 107,
+    /// Mutation attempted on a read-only handle
+    ReadOnly = 108,
+    /// Host synthesis callback failed
+    SynthesisFailed = 109,
+    /// Text result contains an interior null byte
+    InteriorNul = 110,
+    /// Host allocator returned NULL
+    AllocationFailed = 111,
     /// Unknown error
     Unknown = 255,
 }
@@ -165,14 +192,35 @@ impl MemvidError {
     }
 
     /// Create an error from a memvid-core error.
+    ///
+    /// The error's `source()` chain is recorded for `memvid_error_cause_json`.
     pub fn from_core_error(e: memvid_core::MemvidError) -> Self {
         let code = error_code_from_core(&e);
         let message = CString::new(e.to_string())
             .map(CString::into_raw)
             .unwrap_or(std::ptr::null_mut());
+
+        let mut chain = vec![e.to_string()];
+        let mut source = e.source();
+        while let Some(cause) = source {
+            chain.push(cause.to_string());
+            source = cause.source();
+        }
+        CAUSE_CHAIN.with(|c| *c.borrow_mut() = Some((message as usize, chain)));
+
         Self { code, message }
     }
 
+    /// Create an error with an explicit code and message.
+    pub fn new(code: MemvidErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: CString::new(message.into())
+                .map(CString::into_raw)
+                .unwrap_or(std::ptr::null_mut()),
+        }
+    }
+
     /// Create a null pointer error.
     pub fn null_pointer(param: &str) -> Self {
         let msg = format!("null pointer passed for parameter: {param}");
@@ -210,7 +258,18 @@ impl MemvidError {
     pub fn json_serialize(e: serde_json::Error) -> Self {
         let msg = format!("JSON serialization error: {e}");
         Self {
-            code: MemvidErrorCode::JsonParse,
+            code: MemvidErrorCode::JsonSerialize,
+            message: CString::new(msg)
+                .map(CString::into_raw)
+                .unwrap_or(std::ptr::null_mut()),
+        }
+    }
+
+    /// Create an I/O error from a standard library error.
+    pub fn io(e: std::io::Error) -> Self {
+        let msg = format!("I/O error: {e}");
+        Self {
+            code: MemvidErrorCode::Io,
             message: CString::new(msg)
                 .map(CString::into_raw)
                 .unwrap_or(std::ptr::null_mut()),
@@ -226,6 +285,20 @@ impl MemvidError {
                 .unwrap_or(std::ptr::null_mut()),
         }
     }
+
+    /// Take the message out of an error that is not returned to the caller,
+    /// freeing it.
+    pub(crate) fn into_message(mut self) -> String {
+        let message = if self.message.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(self.message) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        unsafe { memvid_error_free(&mut self) };
+        message
+    }
 }
 
 /// Convert a memvid-core error to an FFI error code.
@@ -286,6 +359,84 @@ fn error_code_from_core(e: &memvid_core::MemvidError) -> MemvidErrorCode {
     }
 }
 
+/// Name of an error code, e.g. "VecNotEnabled".
+///
+/// The match is exhaustive, so adding a code without a name fails to compile.
+/// `code` must be one of the `MemvidErrorCode` values; passing any other
+/// integer from C is undefined behavior.
+///
+/// # Returns
+///
+/// Static string with the variant name. Do not free this string.
+#[unsafe(no_mangle)]
+pub extern "C" fn memvid_error_code_str(code: MemvidErrorCode) -> *const c_char {
+    let name: &'static CStr = match code {
+        MemvidErrorCode::Ok => c"Ok",
+        MemvidErrorCode::Io => c"Io",
+        MemvidErrorCode::Encode => c"Encode",
+        MemvidErrorCode::Decode => c"Decode",
+        MemvidErrorCode::Lock => c"Lock",
+        MemvidErrorCode::Locked => c"Locked",
+        MemvidErrorCode::ChecksumMismatch => c"ChecksumMismatch",
+        MemvidErrorCode::InvalidHeader => c"InvalidHeader",
+        MemvidErrorCode::EncryptedFile => c"EncryptedFile",
+        MemvidErrorCode::InvalidToc => c"InvalidToc",
+        MemvidErrorCode::InvalidTimeIndex => c"InvalidTimeIndex",
+        MemvidErrorCode::LexNotEnabled => c"LexNotEnabled",
+        MemvidErrorCode::VecNotEnabled => c"VecNotEnabled",
+        MemvidErrorCode::ClipNotEnabled => c"ClipNotEnabled",
+        MemvidErrorCode::VecDimensionMismatch => c"VecDimensionMismatch",
+        MemvidErrorCode::InvalidSketchTrack => c"InvalidSketchTrack",
+        MemvidErrorCode::InvalidLogicMesh => c"InvalidLogicMesh",
+        MemvidErrorCode::LogicMeshNotEnabled => c"LogicMeshNotEnabled",
+        MemvidErrorCode::NerModelNotAvailable => c"NerModelNotAvailable",
+        MemvidErrorCode::InvalidTier => c"InvalidTier",
+        MemvidErrorCode::TicketSequence => c"TicketSequence",
+        MemvidErrorCode::TicketRequired => c"TicketRequired",
+        MemvidErrorCode::CapacityExceeded => c"CapacityExceeded",
+        MemvidErrorCode::ApiKeyRequired => c"ApiKeyRequired",
+        MemvidErrorCode::MemoryAlreadyBound => c"MemoryAlreadyBound",
+        MemvidErrorCode::RequiresSealed => c"RequiresSealed",
+        MemvidErrorCode::RequiresOpen => c"RequiresOpen",
+        MemvidErrorCode::DoctorNoOp => c"DoctorNoOp",
+        MemvidErrorCode::Doctor => c"Doctor",
+        MemvidErrorCode::FeatureUnavailable => c"FeatureUnavailable",
+        MemvidErrorCode::InvalidCursor => c"InvalidCursor",
+        MemvidErrorCode::InvalidFrame => c"InvalidFrame",
+        MemvidErrorCode::FrameNotFound => c"FrameNotFound",
+        MemvidErrorCode::FrameNotFoundByUri => c"FrameNotFoundByUri",
+        MemvidErrorCode::InvalidQuery => c"InvalidQuery",
+        MemvidErrorCode::TicketSignatureInvalid => c"TicketSignatureInvalid",
+        MemvidErrorCode::ModelSignatureInvalid => c"ModelSignatureInvalid",
+        MemvidErrorCode::ModelManifestInvalid => c"ModelManifestInvalid",
+        MemvidErrorCode::ModelIntegrity => c"ModelIntegrity",
+        MemvidErrorCode::ExtractionFailed => c"ExtractionFailed",
+        MemvidErrorCode::EmbeddingFailed => c"EmbeddingFailed",
+        MemvidErrorCode::RerankFailed => c"RerankFailed",
+        MemvidErrorCode::Tantivy => c"Tantivy",
+        MemvidErrorCode::TableExtraction => c"TableExtraction",
+        MemvidErrorCode::SchemaValidation => c"SchemaValidation",
+        MemvidErrorCode::WalCorruption => c"WalCorruption",
+        MemvidErrorCode::ManifestWalCorrupted => c"ManifestWalCorrupted",
+        MemvidErrorCode::CheckpointFailed => c"CheckpointFailed",
+        MemvidErrorCode::AuxiliaryFileDetected => c"AuxiliaryFileDetected",
+        MemvidErrorCode::NullPointer => c"NullPointer",
+        MemvidErrorCode::InvalidUtf8 => c"InvalidUtf8",
+        MemvidErrorCode::JsonParse => c"JsonParse",
+        MemvidErrorCode::InvalidHandle => c"InvalidHandle",
+        MemvidErrorCode::JsonSerialize => c"JsonSerialize",
+        MemvidErrorCode::Cancelled => c"Cancelled",
+        MemvidErrorCode::BufferTooSmall => c"BufferTooSmall",
+        MemvidErrorCode::FrameNotDeleted => c"FrameNotDeleted",
+        MemvidErrorCode::ReadOnly => c"ReadOnly",
+        MemvidErrorCode::SynthesisFailed => c"SynthesisFailed",
+        MemvidErrorCode::InteriorNul => c"InteriorNul",
+        MemvidErrorCode::AllocationFailed => c"AllocationFailed",
+        MemvidErrorCode::Unknown => c"Unknown",
+    };
+    name.as_ptr()
+}
+
 /// Free error message memory.
 ///
 /// Safe to call with NULL error or NULL message.
@@ -301,8 +452,107 @@ pub unsafe extern "C" fn memvid_error_free(error: *mut MemvidError) {
     unsafe {
         let err = &mut *error;
         if !err.message.is_null() {
+            forget_cause_chain(err.message);
             drop(CString::from_raw(err.message));
             err.message = std::ptr::null_mut();
         }
     }
 }
+
+/// Record `err` as the calling thread's last error.
+pub(crate) fn record_last_error(err: &MemvidError) {
+    let message = if err.message.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(err.message) }.to_owned())
+    };
+    LAST_ERROR.with(|e| *e.borrow_mut() = (err.code, message));
+}
+
+/// Get the code of the most recent failing call on the calling thread.
+///
+/// An opt-in alternative to the `MemvidError` out-parameter: every call
+/// that fails also records its error here, whether or not an out-parameter
+/// was passed. Successful calls leave it unchanged.
+///
+/// # Returns
+///
+/// The last error code, or `Ok` if no call on this thread has failed.
+#[unsafe(no_mangle)]
+pub extern "C" fn memvid_last_error_code() -> MemvidErrorCode {
+    LAST_ERROR.with(|e| e.borrow().0)
+}
+
+/// Get the message of the most recent failing call on the calling thread.
+///
+/// # Returns
+///
+/// The last error message, or NULL if no call on this thread has failed.
+///
+/// # Ownership
+///
+/// The string is owned by the library and is invalidated by the next
+/// failing call on the same thread. Copy it immediately; do not free it.
+#[unsafe(no_mangle)]
+pub extern "C" fn memvid_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|e| {
+        e.borrow()
+            .1
+            .as_ref()
+            .map_or(std::ptr::null(), |m| m.as_ptr())
+    })
+}
+
+/// Drop the recorded cause chain if it belongs to `message`.
+fn forget_cause_chain(message: *mut c_char) {
+    CAUSE_CHAIN.with(|c| {
+        let mut chain = c.borrow_mut();
+        if matches!(*chain, Some((ptr, _)) if ptr == message as usize) {
+            *chain = None;
+        }
+    });
+}
+
+/// Get the cause chain of an error as JSON.
+///
+/// Returns a JSON array of messages from the outermost error to the root
+/// cause, e.g. `["I/O error: ...", "Permission denied (os error 13)"]`.
+/// The full chain is available for the most recent core error on the
+/// calling thread until its message is freed; other errors yield a
+/// single-element array with their message. An error without a message
+/// yields `[]`.
+///
+/// # Returns
+///
+/// JSON string on success, NULL if `error` is NULL.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Safety
+///
+/// The error pointer must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_error_cause_json(error: *const MemvidError) -> *mut c_char {
+    let err = match unsafe { error.as_ref() } {
+        Some(e) => e,
+        None => return std::ptr::null_mut(),
+    };
+
+    let chain = if err.message.is_null() {
+        Vec::new()
+    } else {
+        CAUSE_CHAIN
+            .with(|c| match &*c.borrow() {
+                Some((ptr, chain)) if *ptr == err.message as usize => Some(chain.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| {
+                let message = unsafe { CStr::from_ptr(err.message) };
+                vec![message.to_string_lossy().into_owned()]
+            })
+    };
+
+    match serde_json::to_string(&chain) {
+        Ok(json) => unsafe { crate::util::string_to_cstr(json, None, std::ptr::null_mut()) },
+        Err(_) => std::ptr::null_mut(),
+    }
+}
@@ -1,11 +1,40 @@
 //! Frame retrieval and content functions.
 
-use crate::error::MemvidError;
-use crate::handle::MemvidHandle;
-use crate::util::{set_error, set_error_null, set_ok, string_to_cstr};
-use serde::Serialize;
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::handle::{MemvidHandle, IDEMPOTENCY_KEY, SIDECAR_KEY};
+use crate::mutation::stage_put;
+use crate::util::{
+    bytes_to_raw, cstr_to_json, cstr_to_string, json_to_cstr, set_error, set_error_null, set_ok,
+    string_to_cstr,
+};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use memvid_core::PutOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU64;
 use std::os::raw::c_char;
 
+/// Track holding deletion audit frames.
+const DELETIONS_TRACK: &str = "_deletions";
+
+/// Kind of deletion audit frames.
+const DELETION_KIND: &str = "application/x-memvid-deletion";
+
+/// Audit frame metadata key holding the deletion reason.
+const DELETE_REASON_KEY: &str = "delete_reason";
+
+/// Frame metadata key holding the ID of the deleted frame a frame restores.
+const RESTORED_FROM_KEY: &str = "restored_from";
+
+/// Appended to the `InteriorNul` error of frame text containing a null byte.
+const FRAME_TEXT_NUL_HINT: &str = "use memvid_frame_payload for binary-safe access";
+
+/// URI of the audit frame recording the deletion of `frame_id`.
+fn deletion_uri(frame_id: u64) -> String {
+    format!("mv2://_deleted/{frame_id}")
+}
+
 /// Frame data serialized for FFI.
 ///
 /// This mirrors the core Frame struct but with FFI-friendly types.
@@ -95,10 +124,7 @@ pub unsafe extern "C" fn memvid_frame_by_id(
         Ok(frame) => {
             let json_frame = FrameJson::from(&frame);
             match serde_json::to_string(&json_frame) {
-                Ok(json) => {
-                    unsafe { set_ok(error) };
-                    string_to_cstr(json)
-                }
+                Ok(json) => unsafe { string_to_cstr(json, None, error) },
                 Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
             }
         }
@@ -106,6 +132,84 @@ pub unsafe extern "C" fn memvid_frame_by_id(
     }
 }
 
+/// Extended frame data serialized for FFI.
+#[derive(Debug, Serialize)]
+struct FrameExJson {
+    #[serde(flatten)]
+    frame: FrameJson,
+    extra_metadata: BTreeMap<String, String>,
+    delete_reason: Option<String>,
+    deleted_at: Option<i64>,
+}
+
+/// Get extended frame metadata by ID, including deletion audit data.
+///
+/// Works on deleted frames. Adds `extra_metadata` and, for frames deleted
+/// with `memvid_delete_frame_with_reason`, the recorded reason and the
+/// deletion timestamp.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with frame metadata on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # JSON Schema
+///
+/// All `memvid_frame_by_id` fields, plus:
+///
+/// ```json
+/// {
+///   "extra_metadata": {"key": "value"},
+///   "delete_reason": "retention policy",
+///   "deleted_at": 1234567890
+/// }
+/// ```
+///
+/// `delete_reason` and `deleted_at` are null unless a reason was recorded.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_by_id_ex(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let frame = match handle.as_mut().frame_by_id(frame_id) {
+        Ok(f) => f,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let audit = handle
+        .as_mut()
+        .frame_by_uri(&deletion_uri(frame_id))
+        .ok()
+        .filter(|a| a.track.as_deref() == Some(DELETIONS_TRACK));
+
+    let frame_ex = FrameExJson {
+        frame: FrameJson::from(&frame),
+        extra_metadata: frame.extra_metadata.clone(),
+        delete_reason: audit
+            .as_ref()
+            .and_then(|a| a.extra_metadata.get(DELETE_REASON_KEY).cloned()),
+        deleted_at: audit.as_ref().map(|a| a.timestamp),
+    };
+    unsafe { json_to_cstr(&frame_ex, error) }
+}
+
 /// Get frame metadata by URI.
 ///
 /// # Parameters
@@ -144,10 +248,7 @@ pub unsafe extern "C" fn memvid_frame_by_uri(
         Ok(frame) => {
             let json_frame = FrameJson::from(&frame);
             match serde_json::to_string(&json_frame) {
-                Ok(json) => {
-                    unsafe { set_ok(error) };
-                    string_to_cstr(json)
-                }
+                Ok(json) => unsafe { string_to_cstr(json, None, error) },
                 Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
             }
         }
@@ -155,7 +256,41 @@ pub unsafe extern "C" fn memvid_frame_by_uri(
     }
 }
 
-/// Get frame text content by ID.
+/// Shared body of `memvid_frame_exists` and `memvid_frame_exists_ex`.
+unsafe fn frame_exists(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    include_deleted: bool,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => {
+            unsafe { set_error::<()>(error, MemvidError::invalid_handle()) };
+            return -1;
+        }
+    };
+
+    let exists = match handle.as_mut().frame_by_id(frame_id) {
+        Ok(frame) => {
+            include_deleted || matches!(frame.status, memvid_core::types::FrameStatus::Active)
+        }
+        Err(memvid_core::MemvidError::FrameNotFound { .. }) => false,
+        Err(e) => {
+            unsafe { set_error::<()>(error, MemvidError::from_core_error(e)) };
+            return -1;
+        }
+    };
+    unsafe { set_ok(error) };
+    i32::from(exists)
+}
+
+/// Check whether an active frame exists.
+///
+/// Reads the frame's entry in the table of contents only, without touching
+/// its payload or serializing metadata, so it is cheap enough for
+/// validating many references. Tombstoned frames count as missing; use
+/// `memvid_frame_exists_ex` to include them.
 ///
 /// # Parameters
 ///
@@ -165,17 +300,83 @@ pub unsafe extern "C" fn memvid_frame_by_uri(
 ///
 /// # Returns
 ///
-/// Frame text content on success, NULL on failure.
-/// Caller must free with `memvid_string_free()`.
+/// 1 if the frame exists and is active, 0 if not, -1 on failure (check
+/// `error`).
 ///
 /// # Safety
 ///
 /// - `handle` must be a valid handle
 /// - `error` must be a valid pointer or NULL
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn memvid_frame_content(
+pub unsafe extern "C" fn memvid_frame_exists(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    error: *mut MemvidError,
+) -> i32 {
+    unsafe { frame_exists(handle, frame_id, false, error) }
+}
+
+/// Check whether a frame exists, optionally counting tombstoned frames.
+///
+/// Same as `memvid_frame_exists`; with `include_deleted` nonzero, deleted
+/// frames count as existing too.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `include_deleted`: Nonzero to also report tombstoned frames
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 if the frame exists, 0 if not, -1 on failure (check `error`).
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_exists_ex(
     handle: *mut MemvidHandle,
     frame_id: u64,
+    include_deleted: i32,
+    error: *mut MemvidError,
+) -> i32 {
+    unsafe { frame_exists(handle, frame_id, include_deleted != 0, error) }
+}
+
+/// List the active frames on a track, oldest first.
+///
+/// memvid-core keeps no index by track, so this scans every frame's
+/// metadata (not its content). Frames are ordered by timestamp, then ID.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `track`: Track name (UTF-8 encoded, null-terminated)
+/// - `limit`: Maximum number of frames to return (0 for no limit)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON array of frame metadata (as in `memvid_frame_by_id`) on success,
+/// NULL on failure. Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `InvalidQuery` if `track` is NULL or empty
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `track` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frames_by_track(
+    handle: *mut MemvidHandle,
+    track: *const c_char,
+    limit: u32,
     error: *mut MemvidError,
 ) -> *mut c_char {
     let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
@@ -183,49 +384,1179 @@ pub unsafe extern "C" fn memvid_frame_content(
         None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
     };
 
-    match handle.as_mut().frame_text_by_id(frame_id) {
-        Ok(content) => {
-            unsafe { set_ok(error) };
-            string_to_cstr(content)
+    let track = match unsafe { crate::util::cstr_to_option_string(track, "track") } {
+        Ok(Some(t)) if !t.is_empty() => t,
+        Ok(_) => {
+            let err = MemvidError::new(MemvidErrorCode::InvalidQuery, "track must not be empty");
+            return unsafe { set_error_null(error, err) };
         }
-        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let mut frames: Vec<memvid_core::Frame> = handle
+        .frames()
+        .filter(|f| matches!(f.status, memvid_core::types::FrameStatus::Active))
+        .filter(|f| f.track.as_deref() == Some(track.as_str()))
+        .collect();
+    frames.sort_by_key(|f| (f.timestamp, f.id));
+    if limit > 0 {
+        frames.truncate(limit as usize);
     }
+
+    let frames: Vec<FrameJson> = frames.iter().map(FrameJson::from).collect();
+    unsafe { json_to_cstr(&frames, error) }
 }
 
-/// Soft-delete a frame.
+/// List the active frames within a time range, oldest first.
 ///
-/// Creates a tombstone entry; the frame data is not immediately removed.
+/// Uses the time index, like `memvid_timeline`, but returns full frame
+/// metadata instead of previews. Frames are ordered by timestamp, then ID.
+/// A range with `since` after `until`, or without frames, gives an empty
+/// array.
 ///
 /// # Parameters
 ///
 /// - `handle`: Valid Memvid handle
-/// - `frame_id`: Frame identifier to delete
+/// - `since`: Timestamp lower bound (inclusive, Unix seconds)
+/// - `until`: Timestamp upper bound (inclusive, Unix seconds)
+/// - `limit`: Maximum number of frames to return (0 for no limit)
 /// - `error`: Out-parameter for error information
 ///
 /// # Returns
 ///
-/// WAL sequence number on success, 0 on failure.
+/// JSON array of frame metadata (as in `memvid_frame_by_id`) on success,
+/// NULL on failure. Caller must free with `memvid_string_free()`.
 ///
 /// # Safety
 ///
 /// - `handle` must be a valid handle
 /// - `error` must be a valid pointer or NULL
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn memvid_delete_frame(
+pub unsafe extern "C" fn memvid_frames_in_range(
+    handle: *mut MemvidHandle,
+    since: i64,
+    until: i64,
+    limit: u32,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if since > until {
+        return unsafe { json_to_cstr(&Vec::<FrameJson>::new(), error) };
+    }
+
+    let mut builder = memvid_core::TimelineQueryBuilder::default()
+        .since(since)
+        .until(until);
+    if let Some(limit) = NonZeroU64::new(u64::from(limit)) {
+        builder = builder.limit(limit);
+    }
+    let entries = match handle.as_mut().timeline(builder.build()) {
+        Ok(entries) => entries,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let mut frames: Vec<memvid_core::Frame> = entries
+        .iter()
+        .filter_map(|entry| handle.as_mut().frame_by_id(entry.frame_id).ok())
+        .filter(|f| matches!(f.status, memvid_core::types::FrameStatus::Active))
+        .collect();
+    frames.sort_by_key(|f| (f.timestamp, f.id));
+
+    let frames: Vec<FrameJson> = frames.iter().map(FrameJson::from).collect();
+    unsafe { json_to_cstr(&frames, error) }
+}
+
+/// Get the metadata of several frames in one call.
+///
+/// Returns one entry per ID, in the order given, so a host can render the
+/// frames of a search result without a call per frame. Missing and
+/// tombstoned frames give `null` entries rather than failing the call.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `ids`: Array of frame identifiers (may be NULL if `count` is 0)
+/// - `count`: Number of IDs in `ids`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON array of frame metadata (as in `memvid_frame_by_id`) or `null`,
+/// one per ID, on success; NULL on failure. Caller must free with
+/// `memvid_string_free()`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `ids` must point to at least `count` values, or be NULL if `count` is 0
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frames_by_ids(
+    handle: *mut MemvidHandle,
+    ids: *const u64,
+    count: usize,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if ids.is_null() && count > 0 {
+        return unsafe { set_error_null(error, MemvidError::null_pointer("ids")) };
+    }
+
+    let ids = if count == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(ids, count) }
+    };
+
+    let frames: Vec<Option<FrameJson>> = ids
+        .iter()
+        .map(|&id| {
+            let frame = handle.as_mut().frame_by_id(id).ok()?;
+            matches!(frame.status, memvid_core::types::FrameStatus::Active)
+                .then(|| FrameJson::from(&frame))
+        })
+        .collect();
+    unsafe { json_to_cstr(&frames, error) }
+}
+
+/// List the chunk frames of a document, in chunk order.
+///
+/// memvid-core keeps no index from parent to children, so this scans every
+/// frame's metadata. Chunks are listed whatever their status, ordered by
+/// `chunk_index`. Chunks of a staged put are listed once it is committed.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Parent frame identifier
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON array of frame metadata (as in `memvid_frame_by_id`) on success,
+/// empty for a frame without chunks, NULL on failure (e.g. an unknown
+/// frame). Caller must free with `memvid_string_free()`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_children(
     handle: *mut MemvidHandle,
     frame_id: u64,
     error: *mut MemvidError,
-) -> u64 {
+) -> *mut c_char {
     let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
         Some(h) => h,
-        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
     };
 
-    match handle.as_mut().delete_frame(frame_id) {
-        Ok(seq) => {
-            unsafe { set_ok(error) };
-            seq
-        }
-        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    if let Err(e) = handle.as_mut().frame_by_id(frame_id) {
+        return unsafe { set_error_null(error, MemvidError::from_core_error(e)) };
+    }
+
+    let children: Vec<FrameJson> = handle
+        .children(frame_id)
+        .iter()
+        .map(FrameJson::from)
+        .collect();
+    unsafe { json_to_cstr(&children, error) }
+}
+
+/// Get the parent document of a chunk frame.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with the parent's metadata (as in `memvid_frame_by_id`), or
+/// NULL with `Ok` if the frame has no parent. NULL with an error code on
+/// failure. Caller must free with `memvid_string_free()`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_parent(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let parent_id = match handle.as_mut().frame_by_id(frame_id) {
+        Ok(frame) => frame.parent_id,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+    let Some(parent_id) = parent_id else {
+        unsafe { set_ok(error) };
+        return std::ptr::null_mut();
+    };
+
+    match handle.as_mut().frame_by_id(parent_id) {
+        Ok(parent) => unsafe { json_to_cstr(&FrameJson::from(&parent), error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Get frame text content by ID.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Frame text content on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `InteriorNul` if the text contains a null byte; read it with
+///   `memvid_frame_payload` instead
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_content(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    match handle.as_mut().frame_text_by_id(frame_id) {
+        Ok(content) => unsafe { string_to_cstr(content, Some(FRAME_TEXT_NUL_HINT), error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Get frame text content by URI.
+///
+/// Looks up the frame as `memvid_frame_by_uri` does and returns its text
+/// as `memvid_frame_content` does. For a chunked document this is the
+/// parent frame's full content, not its chunks joined: a chunk frame
+/// matched by URI resolves to its parent.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `uri`: Frame URI (null-terminated UTF-8 string)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Frame text content on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `FrameNotFoundByUri` if no frame has the URI
+/// - `InteriorNul` if the text contains a null byte; read it with
+///   `memvid_frame_payload` instead
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `uri` must be a valid null-terminated UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_content_by_uri(
+    handle: *mut MemvidHandle,
+    uri: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let uri = match unsafe { cstr_to_string(uri, "uri") } {
+        Ok(s) => s,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let frame_id = match handle.as_mut().frame_by_uri(&uri) {
+        Ok(frame) => frame.parent_id.unwrap_or(frame.id),
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+    match handle.as_mut().frame_text_by_id(frame_id) {
+        Ok(content) => unsafe { string_to_cstr(content, Some(FRAME_TEXT_NUL_HINT), error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Get the raw payload bytes of a frame.
+///
+/// Unlike `memvid_frame_content`, the payload is returned as a byte buffer
+/// with an explicit length, so content with embedded null bytes round-trips
+/// intact. Compressed payloads are returned decompressed.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `out_ptr`: Out-parameter for the payload buffer
+/// - `out_len`: Out-parameter for the payload length in bytes
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Ownership
+///
+/// Caller owns `*out_ptr`. Must call `memvid_bytes_free()` with `*out_len`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `out_ptr` and `out_len` must be valid pointers
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_payload(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let (out_ptr, out_len) = match unsafe { (out_ptr.as_mut(), out_len.as_mut()) } {
+        (Some(p), Some(l)) => (p, l),
+        (None, _) => return unsafe { set_error(error, MemvidError::null_pointer("out_ptr")) },
+        (_, None) => return unsafe { set_error(error, MemvidError::null_pointer("out_len")) },
+    };
+
+    match handle.as_mut().frame_canonical_payload(frame_id) {
+        Ok(bytes) => match bytes_to_raw(bytes, out_len) {
+            Ok(ptr) => {
+                *out_ptr = ptr;
+                unsafe { set_ok(error) };
+                1
+            }
+            Err(e) => unsafe { set_error(error, e) },
+        },
+        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Get a byte range of a frame's text content.
+///
+/// Lets a host page through a long frame instead of receiving its whole
+/// text in one allocation. Offsets are byte offsets into the UTF-8 text
+/// returned by `memvid_frame_content`, so a range may start or end inside
+/// a multi-byte character; the bytes are returned as-is. A range starting
+/// at or past the end gives a zero-length buffer with `Ok`.
+///
+/// memvid-core decodes frame text as a whole, so the library still holds
+/// the full text briefly; only the requested range is copied out.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `offset`: Byte offset of the first byte to return
+/// - `max_len`: Maximum number of bytes to return
+/// - `out_ptr`: Out-parameter for the range buffer
+/// - `out_len`: Out-parameter for the range length in bytes
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Ownership
+///
+/// Caller owns `*out_ptr`. Must call `memvid_bytes_free()` with `*out_len`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `out_ptr` and `out_len` must be valid pointers
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_content_range(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    offset: u64,
+    max_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let (out_ptr, out_len) = match unsafe { (out_ptr.as_mut(), out_len.as_mut()) } {
+        (Some(p), Some(l)) => (p, l),
+        (None, _) => return unsafe { set_error(error, MemvidError::null_pointer("out_ptr")) },
+        (_, None) => return unsafe { set_error(error, MemvidError::null_pointer("out_len")) },
+    };
+
+    match handle.as_mut().frame_text_by_id(frame_id) {
+        Ok(text) => {
+            let text = text.as_bytes();
+            let start = usize::try_from(offset).map_or(text.len(), |o| o.min(text.len()));
+            let end = start.saturating_add(max_len).min(text.len());
+            match bytes_to_raw(text[start..end].to_vec(), out_len) {
+                Ok(ptr) => {
+                    *out_ptr = ptr;
+                    unsafe { set_ok(error) };
+                    1
+                }
+                Err(e) => unsafe { set_error(error, e) },
+            }
+        }
+        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Get the binary sidecar attached to a frame.
+///
+/// Returns the bytes stored with the `sidecar_base64` put option. A frame
+/// without a sidecar succeeds with `*out_ptr` set to NULL and `*out_len`
+/// set to 0.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `out_ptr`: Out-parameter for the sidecar buffer
+/// - `out_len`: Out-parameter for the sidecar length in bytes
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Ownership
+///
+/// Caller owns a non-NULL `*out_ptr`. Must call `memvid_bytes_free()` with
+/// `*out_len`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `out_ptr` and `out_len` must be valid pointers
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_sidecar(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let (out_ptr, out_len) = match unsafe { (out_ptr.as_mut(), out_len.as_mut()) } {
+        (Some(p), Some(l)) => (p, l),
+        (None, _) => return unsafe { set_error(error, MemvidError::null_pointer("out_ptr")) },
+        (_, None) => return unsafe { set_error(error, MemvidError::null_pointer("out_len")) },
+    };
+
+    let frame = match handle.as_mut().frame_by_id(frame_id) {
+        Ok(f) => f,
+        Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    };
+
+    let bytes = match frame.extra_metadata.get(SIDECAR_KEY) {
+        Some(encoded) => match BASE64.decode(encoded) {
+            Ok(b) => b,
+            Err(e) => {
+                let err = MemvidError::new(
+                    MemvidErrorCode::Decode,
+                    format!("frame {frame_id} has a corrupt sidecar: {e}"),
+                );
+                return unsafe { set_error(error, err) };
+            }
+        },
+        None => {
+            *out_ptr = std::ptr::null_mut();
+            *out_len = 0;
+            unsafe { set_ok(error) };
+            return 1;
+        }
+    };
+
+    match bytes_to_raw(bytes, out_len) {
+        Ok(ptr) => {
+            *out_ptr = ptr;
+            unsafe { set_ok(error) };
+            1
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
+
+/// Stored size of a frame's payload, serialized for FFI.
+#[derive(Debug, Serialize)]
+struct FrameStorageJson {
+    /// Payload size as returned by `memvid_frame_payload`
+    payload_length: u64,
+    /// Bytes the payload occupies in the file
+    stored_bytes: u64,
+    /// Encoding of the stored bytes
+    codec: String,
+    /// `stored_bytes / payload_length` (1.0 for an empty payload)
+    ratio: f64,
+}
+
+impl From<&memvid_core::Frame> for FrameStorageJson {
+    fn from(f: &memvid_core::Frame) -> Self {
+        let payload_length = f.canonical_length.unwrap_or(f.payload_length);
+        let ratio = if payload_length == 0 {
+            1.0
+        } else {
+            f.payload_length as f64 / payload_length as f64
+        };
+        Self {
+            payload_length,
+            stored_bytes: f.payload_length,
+            codec: format!("{:?}", f.canonical_encoding).to_lowercase(),
+            ratio,
+        }
+    }
+}
+
+/// Get how a frame's payload is stored.
+///
+/// Read-only diagnostic for finding documents that compress poorly. Sizes
+/// come from the frame's manifest entry; the payload is not read.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with storage info on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # JSON Schema
+///
+/// ```json
+/// {
+///   "payload_length": 4096,
+///   "stored_bytes": 1024,
+///   "codec": "zstd",
+///   "ratio": 0.25
+/// }
+/// ```
+///
+/// `codec` is memvid-core's payload encoding: `"zstd"` or `"plain"`.
+/// `ratio` is stored bytes over payload bytes, so lower compresses better;
+/// a plain payload has a ratio of 1.0.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_storage_info(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    match handle.as_mut().frame_by_id(frame_id) {
+        Ok(frame) => unsafe { json_to_cstr(&FrameStorageJson::from(&frame), error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Soft-delete a frame.
+///
+/// Creates a tombstone entry; the frame data is not immediately removed.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier to delete
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// WAL sequence number on success, 0 on failure.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_delete_frame(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    match handle.as_mut().delete_frame(frame_id) {
+        Ok(seq) => {
+            handle.record_delete(frame_id);
+            handle.record_sequence(seq);
+            unsafe { set_ok(error) };
+            seq
+        }
+        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Soft-delete every frame with a URI.
+///
+/// Tombstones each active frame whose URI is `uri`, together with its
+/// chunk frames, as `memvid_delete_frame` would. Finding them scans all
+/// frames. The deletes are staged together and take effect on
+/// `memvid_commit`; if one fails, those before it stay staged.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `uri`: URI to delete (null-terminated UTF-8 string)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Highest WAL sequence number of the deletes on success, 0 on failure
+/// (`FrameNotFoundByUri` if no active frame has the URI).
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `uri` must be a valid null-terminated UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_delete_frame_by_uri(
+    handle: *mut MemvidHandle,
+    uri: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let uri = match unsafe { cstr_to_string(uri, "uri") } {
+        Ok(s) => s,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let active: Vec<memvid_core::Frame> = handle
+        .frames()
+        .filter(|f| matches!(f.status, memvid_core::types::FrameStatus::Active))
+        .collect();
+    let parents: Vec<u64> = active
+        .iter()
+        .filter(|f| f.uri.as_deref() == Some(uri.as_str()))
+        .map(|f| f.id)
+        .collect();
+    if parents.is_empty() {
+        let err = MemvidError::new(
+            MemvidErrorCode::FrameNotFoundByUri,
+            format!("no frame with uri {uri}"),
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    let mut last_seq = 0;
+    for frame in &active {
+        let is_chunk = frame.parent_id.is_some_and(|p| parents.contains(&p));
+        if !parents.contains(&frame.id) && !is_chunk {
+            continue;
+        }
+        match handle.as_mut().delete_frame(frame.id) {
+            Ok(seq) => {
+                handle.record_delete(frame.id);
+                handle.record_sequence(seq);
+                last_seq = last_seq.max(seq);
+            }
+            Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+        }
+    }
+
+    unsafe { set_ok(error) };
+    last_seq
+}
+
+/// Soft-delete a frame, recording why.
+///
+/// Deletes like `memvid_delete_frame` and stages an audit frame holding
+/// `reason`, readable via `memvid_frame_by_id_ex` on the deleted frame.
+///
+/// memvid-core tombstones carry no metadata, so the reason lives in a
+/// separate frame on the `_deletions` track (URI `mv2://_deleted/<id>`,
+/// empty payload, excluded from lexical search). The audit frame is an
+/// ordinary frame: it counts toward stats and appears in the timeline, and
+/// it is kept when vacuum removes the deleted frame.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier to delete
+/// - `reason`: Deletion reason (null-terminated UTF-8 string)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// WAL sequence number of the delete on success, 0 on failure.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `reason` must be a valid null-terminated UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_delete_frame_with_reason(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    reason: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let reason = match unsafe { cstr_to_string(reason, "reason") } {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let seq = match handle.as_mut().delete_frame(frame_id) {
+        Ok(seq) => {
+            handle.record_delete(frame_id);
+            handle.record_sequence(seq);
+            seq
+        }
+        Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    };
+
+    let mut options = memvid_core::PutOptions::builder()
+        .uri(deletion_uri(frame_id))
+        .track(DELETIONS_TRACK)
+        .kind(DELETION_KIND)
+        .search_text(String::new())
+        .build();
+    options
+        .extra_metadata
+        .insert(DELETE_REASON_KEY.to_string(), reason);
+
+    match handle.as_mut().put_bytes_with_options(&[], options) {
+        Ok(_) => {
+            unsafe { set_ok(error) };
+            seq
+        }
+        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Restore a soft-deleted frame.
+///
+/// memvid-core has no way to clear a tombstone, so the frame is restored
+/// as a copy: a new frame with the deleted frame's content and metadata
+/// (URI, title, kind, track, timestamp, tags, labels, extra metadata), plus
+/// `extra_metadata["restored_from"]` holding the deleted frame's ID. The
+/// deleted frame stays tombstoned. Content is re-chunked as on a put, so
+/// restore a chunked document through its parent frame. An idempotency key
+/// carried over now resolves to the copy.
+///
+/// The copy is staged like a put and takes effect on `memvid_commit`. The
+/// content must still be stored, so restore before vacuum removes it.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Identifier of the deleted frame
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// ID of the restored frame on success, 0 on failure (`FrameNotDeleted` if
+/// the frame is not deleted).
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_restore_frame(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let frame = match handle.as_mut().frame_by_id(frame_id) {
+        Ok(f) => f,
+        Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    };
+
+    if matches!(frame.status, memvid_core::types::FrameStatus::Active) {
+        let err = MemvidError::new(
+            MemvidErrorCode::FrameNotDeleted,
+            format!("frame {frame_id} is not deleted"),
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    let payload = match handle.as_mut().frame_canonical_payload(frame_id) {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    };
+
+    let mut options = FrameUpdateJson::default().into_put_options(&frame);
+    options
+        .extra_metadata
+        .insert(RESTORED_FROM_KEY.to_string(), frame_id.to_string());
+    let key = options.extra_metadata.get(IDEMPOTENCY_KEY).cloned();
+
+    match stage_put(handle, &payload, options) {
+        Ok(id) => {
+            handle.record_put();
+            if let Some(key) = key {
+                handle.record_idempotency_key(key, id);
+            }
+            unsafe { set_ok(error) };
+            id
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
+
+/// Metadata accepted by `memvid_update_frame`.
+#[derive(Debug, Default, Deserialize)]
+struct FrameUpdateJson {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    tags: Option<HashMap<String, String>>,
+    #[serde(default)]
+    labels: Option<Vec<String>>,
+    #[serde(default)]
+    kind: Option<String>,
+    /// Any other field; rejected, since only metadata can change
+    #[serde(flatten)]
+    other: BTreeMap<String, serde_json::Value>,
+}
+
+impl FrameUpdateJson {
+    /// Build put options that keep `frame`'s metadata except the updated fields.
+    fn into_put_options(self, frame: &memvid_core::Frame) -> PutOptions {
+        let mut builder = PutOptions::builder().timestamp(frame.timestamp);
+
+        if let Some(uri) = &frame.uri {
+            builder = builder.uri(uri.clone());
+        }
+        if let Some(track) = &frame.track {
+            builder = builder.track(track.clone());
+        }
+        if let Some(title) = self.title.or_else(|| frame.title.clone()) {
+            builder = builder.title(title);
+        }
+        if let Some(kind) = self.kind.or_else(|| frame.kind.clone()) {
+            builder = builder.kind(kind);
+        }
+        if let Some(tags) = &self.tags {
+            for (k, v) in tags {
+                builder = builder.tag(k, v);
+            }
+        }
+        for label in self.labels.unwrap_or_else(|| frame.labels.clone()) {
+            builder = builder.label(label);
+        }
+
+        let mut options = builder.build();
+        if self.tags.is_none() {
+            options.tags = frame.tags.clone();
+        }
+        options.extra_metadata = frame.extra_metadata.clone();
+        options
+    }
+}
+
+/// Update a frame's metadata without re-inserting its content.
+///
+/// Sets any of `title`, `tags`, `labels`, and `kind`; each given field
+/// replaces the stored value and the rest are kept. The frame keeps its ID
+/// and content. The update is staged like a put and takes effect on
+/// `memvid_commit`.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Frame identifier to update
+/// - `options_json`: JSON object with the fields to change
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// WAL sequence number on success, 0 on failure (`InvalidQuery` if the JSON
+/// has any other field, such as content or `search_text`).
+///
+/// # Options JSON Schema
+///
+/// ```json
+/// {
+///   "title": "string",
+///   "tags": {"key": "value"},
+///   "labels": ["label1", "label2"],
+///   "kind": "string"
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `options_json` must be a valid null-terminated UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_update_frame(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let update: FrameUpdateJson = match unsafe { cstr_to_json(options_json, "options_json") } {
+        Ok(u) => u,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    if let Some(field) = update.other.keys().next() {
+        let err = MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            format!("cannot update {field}; only title, tags, labels, and kind can change"),
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    let frame = match handle.as_mut().frame_by_id(frame_id) {
+        Ok(f) => f,
+        Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    };
+
+    let options = update.into_put_options(&frame);
+    match handle.as_mut().update_frame(frame_id, None, options, None) {
+        Ok(seq) => {
+            handle.record_put();
+            handle.record_sequence(seq);
+            unsafe { set_ok(error) };
+            seq
+        }
+        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Cursor over the frames of a handle, created by `memvid_frames_iter_new()`.
+///
+/// Must be freed with `memvid_frames_iter_free()`.
+pub struct MemvidFrameIter {
+    handle: *mut MemvidHandle,
+    /// Frame IDs from core's frame table when the cursor was created
+    ids: Vec<u64>,
+    /// Position of the next ID in `ids`
+    next: usize,
+    include_deleted: bool,
+    /// Handle generation the cursor was created in
+    generation: u64,
+}
+
+/// Create a cursor over all frames, in ID order.
+///
+/// The cursor covers the frame IDs that exist when it is created; frames
+/// added later are not visited. Each frame is read when the cursor reaches
+/// it, so a frame deleted in the meantime reflects that (and is skipped
+/// unless `include_deleted` is set). IDs are taken from core's frame
+/// table, so gaps left by a vacuum are not an issue.
+///
+/// The cursor borrows `handle`: the handle must outlive it, and the cursor
+/// must be used on the handle's thread.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `include_deleted`: Nonzero to also yield tombstoned frames
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Cursor on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned cursor. Must call `memvid_frames_iter_free()` to free.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle that outlives the cursor
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frames_iter_new(
+    handle: *mut MemvidHandle,
+    include_deleted: i32,
+    error: *mut MemvidError,
+) -> *mut MemvidFrameIter {
+    let (ids, generation) = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => {
+            let mut ids: Vec<u64> = h.frames().map(|f| f.id).collect();
+            ids.sort_unstable();
+            (ids, h.generation())
+        }
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    unsafe { set_ok(error) };
+    Box::into_raw(Box::new(MemvidFrameIter {
+        handle,
+        ids,
+        next: 0,
+        include_deleted: include_deleted != 0,
+        generation,
+    }))
+}
+
+/// Advance a frame cursor.
+///
+/// # Parameters
+///
+/// - `iter`: Cursor from `memvid_frames_iter_new()`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with the next frame's metadata (same schema as
+/// `memvid_frame_by_id`), or NULL with `Ok` at the end. NULL with an error
+/// code on failure. Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `InvalidCursor` if the handle was refreshed or vacuumed after the
+///   cursor was created
+///
+/// # Safety
+///
+/// - `iter` must be a valid cursor whose handle is still open
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frames_iter_next(
+    iter: *mut MemvidFrameIter,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let iter = match unsafe { iter.as_mut() } {
+        Some(i) => i,
+        None => return unsafe { set_error_null(error, MemvidError::null_pointer("iter")) },
+    };
+
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(iter.handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if handle.generation() != iter.generation {
+        let e = MemvidError::new(
+            MemvidErrorCode::InvalidCursor,
+            "frame cursor was invalidated by memvid_refresh or memvid_vacuum",
+        );
+        return unsafe { set_error_null(error, e) };
+    }
+
+    while let Some(&id) = iter.ids.get(iter.next) {
+        iter.next += 1;
+
+        let frame = match handle.as_mut().frame_by_id(id) {
+            Ok(f) => f,
+            Err(_) => continue,
+        };
+        if iter.include_deleted || matches!(frame.status, memvid_core::types::FrameStatus::Active) {
+            return unsafe { json_to_cstr(&FrameJson::from(&frame), error) };
+        }
+    }
+
+    unsafe { set_ok(error) };
+    std::ptr::null_mut()
+}
+
+/// Free a frame cursor.
+///
+/// # Safety
+///
+/// - `iter` must be a cursor returned by `memvid_frames_iter_new`, or NULL
+/// - The cursor must not be used after this call
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frames_iter_free(iter: *mut MemvidFrameIter) {
+    if !iter.is_null() {
+        unsafe {
+            drop(Box::from_raw(iter));
+        }
     }
 }
@@ -1,6 +1,29 @@
 //! Opaque handle wrapper for Memvid instances.
 
+use crate::embedder::HostEmbedder;
+use crate::error::{MemvidError, MemvidErrorCode};
+use memvid_core::types::FrameStatus;
 use memvid_core::Memvid;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Frame metadata key holding a put's idempotency key.
+pub const IDEMPOTENCY_KEY: &str = "idempotency_key";
+
+/// Frame metadata key holding a base64-encoded binary sidecar.
+pub const SIDECAR_KEY: &str = "sidecar_base64";
+
+/// Frame metadata key holding the hex BLAKE3 hash of a put's payload.
+pub const CONTENT_HASH_KEY: &str = "content_blake3";
+
+/// Liveness tag of open handles ("MVHD"), cleared when a handle is closed.
+const HANDLE_MAGIC: u32 = 0x4D56_4844;
+
+thread_local! {
+    /// Address of the handle whose host callback is running on this thread.
+    static IN_CALLBACK: Cell<usize> = const { Cell::new(0) };
+}
 
 /// Opaque handle to a Memvid instance.
 ///
@@ -11,32 +34,427 @@ use memvid_core::Memvid;
 ///
 /// `MemvidHandle` is NOT thread-safe. All operations on a handle must occur
 /// from the same thread that created it, or external synchronization must be used.
+/// Multi-threaded hosts can use `MemvidSharedHandle` (`memvid_open_shared()`) instead.
 pub struct MemvidHandle {
-    inner: Memvid,
+    /// `HANDLE_MAGIC` while the handle is open
+    magic: u32,
+    /// `None` only if reopening failed in `reopen_after`
+    inner: Option<Memvid>,
+    /// Path the memory was created or opened from
+    path: PathBuf,
+    /// Idempotency key -> put result, built lazily on first keyed put
+    idempotency_keys: Option<HashMap<String, u64>>,
+    /// Content hash -> active frame IDs, built lazily by `find_content`
+    content_index: Option<HashMap<String, Vec<u64>>>,
+    /// Whether commits are deferred until indexing is resumed
+    indexing_paused: bool,
+    /// Puts staged since the last commit
+    pending_puts: u64,
+    /// Deletes staged since the last commit
+    pending_deletes: u64,
+    /// Pending puts that trigger a commit; 0 disables autocommit
+    autocommit_every: u64,
+    /// Failure of the last autocommit, reported by the next put
+    autocommit_error: Option<memvid_core::MemvidError>,
+    /// Whether the memory was opened without the write lock
+    read_only: bool,
+    /// Whether `memvid_set_write_locked` locked the handle against writes
+    write_locked: bool,
+    /// Host embedder registered with `memvid_register_embedder`
+    embedder: Option<HostEmbedder>,
+    /// Bumped each time the memory is reopened; cursors from an older
+    /// generation are rejected
+    generation: u64,
+    /// Highest WAL sequence seen on this handle; advanced by every change
+    /// made through it and by every reopen
+    sequence: u64,
+    /// Options JSON object merged under every put's own options
+    default_put_options: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl MemvidHandle {
-    /// Create a new handle wrapping a Memvid instance.
-    pub fn new(memvid: Memvid) -> Box<Self> {
-        Box::new(Self { inner: memvid })
+    /// Create a new handle wrapping a Memvid instance opened from `path`.
+    pub fn new(memvid: Memvid, path: PathBuf) -> Box<Self> {
+        Box::new(Self {
+            magic: HANDLE_MAGIC,
+            inner: Some(memvid),
+            path,
+            idempotency_keys: None,
+            content_index: None,
+            indexing_paused: false,
+            pending_puts: 0,
+            pending_deletes: 0,
+            autocommit_every: 0,
+            autocommit_error: None,
+            read_only: false,
+            write_locked: false,
+            embedder: None,
+            generation: 0,
+            sequence: 0,
+            default_put_options: None,
+        })
+    }
+
+    /// Create a read-only handle wrapping a Memvid opened with `open_read_only`.
+    pub fn new_read_only(memvid: Memvid, path: PathBuf) -> Box<Self> {
+        let mut handle = Self::new(memvid, path);
+        handle.read_only = true;
+        handle
+    }
+
+    /// Path the memory was created or opened from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the handle rejects mutations.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Fail with `ReadOnly` if the handle rejects mutations.
+    pub fn check_writable(&self) -> Result<(), MemvidError> {
+        if self.read_only {
+            return Err(MemvidError::new(
+                MemvidErrorCode::ReadOnly,
+                format!("{} was opened read-only", self.path.display()),
+            ));
+        }
+        if self.write_locked {
+            return Err(MemvidError::new(
+                MemvidErrorCode::ReadOnly,
+                format!(
+                    "{} is write-locked; unlock it with memvid_set_write_locked",
+                    self.path.display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the handle is locked against writes.
+    pub fn write_locked(&self) -> bool {
+        self.write_locked
+    }
+
+    /// Lock or unlock the handle against writes.
+    pub fn set_write_locked(&mut self, locked: bool) {
+        self.write_locked = locked;
     }
 
     /// Get a reference to the inner Memvid.
     pub fn as_ref(&self) -> &Memvid {
-        &self.inner
+        self.inner.as_ref().expect("handle memory is open")
     }
 
     /// Get a mutable reference to the inner Memvid.
     pub fn as_mut(&mut self) -> &mut Memvid {
-        &mut self.inner
+        self.inner.as_mut().expect("handle memory is open")
+    }
+
+    /// Number of times the memory has been reopened on this handle.
+    ///
+    /// Cursors record the generation they were issued in and are invalid
+    /// once it changes.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Latest change marker for this handle.
+    ///
+    /// At least the highest WAL sequence number a mutation through this
+    /// handle returned, and strictly greater after every later change or
+    /// reopen. Starts at the generation at open (0).
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Note a WAL sequence number returned by memvid-core.
+    pub fn record_sequence(&mut self, seq: u64) {
+        self.sequence = self.sequence.max(seq);
+    }
+
+    /// Close the memory while `f` runs on its path, then reopen it.
+    ///
+    /// Lets file-level maintenance run on an open handle. Staged changes
+    /// must be committed first. If reopening fails, the handle stays closed
+    /// and `from_ptr_mut` rejects it; only `memvid_close` still applies.
+    ///
+    /// Caches built from the old contents are dropped and the generation
+    /// is bumped, invalidating outstanding cursors.
+    pub fn reopen_after<T>(
+        &mut self,
+        f: impl FnOnce(&Path) -> Result<T, MemvidError>,
+    ) -> Result<T, MemvidError> {
+        self.inner = None;
+        self.idempotency_keys = None;
+        self.content_index = None;
+        self.generation += 1;
+        self.sequence += 1;
+        let result = f(&self.path);
+        let reopened = if self.read_only {
+            Memvid::open_read_only(&self.path)
+        } else {
+            Memvid::open(&self.path)
+        };
+        self.inner = Some(reopened.map_err(MemvidError::from_core_error)?);
+        result
+    }
+
+    /// Iterate over every frame in the memory, including tombstoned ones.
+    ///
+    /// Walks core's table of contents, so gaps in the ID space left by
+    /// compaction are not an issue.
+    pub fn frames(&self) -> impl Iterator<Item = memvid_core::Frame> + '_ {
+        self.as_ref().toc().frames.iter().cloned()
+    }
+
+    /// Chunk frames of `parent_id` in chunk order, whatever their status.
+    ///
+    /// memvid-core keeps no index from parent to children, so this scans
+    /// every frame's `parent_id`. Staged chunks appear once committed.
+    pub fn children(&self, parent_id: u64) -> Vec<memvid_core::Frame> {
+        let mut children: Vec<memvid_core::Frame> = self
+            .frames()
+            .filter(|f| f.parent_id == Some(parent_id))
+            .collect();
+        children.sort_by_key(|f| (f.chunk_index, f.id));
+        children
+    }
+
+    /// Look up the result of an earlier put with the same idempotency key.
+    ///
+    /// The first call scans active committed frames for stored keys; later
+    /// puts in this session are recorded with `record_idempotency_key` and
+    /// deletes drop theirs in `record_delete`.
+    pub fn idempotency_key(&mut self, key: &str) -> Option<u64> {
+        if self.idempotency_keys.is_none() {
+            let keys = self
+                .frames()
+                .filter(|f| matches!(f.status, FrameStatus::Active))
+                .filter_map(|f| {
+                    let key = f.extra_metadata.get(IDEMPOTENCY_KEY)?.clone();
+                    Some((key, f.id))
+                })
+                .collect();
+            self.idempotency_keys = Some(keys);
+        }
+        self.idempotency_keys.as_ref()?.get(key).copied()
+    }
+
+    /// Remember the result of a keyed put.
+    pub fn record_idempotency_key(&mut self, key: String, id: u64) {
+        self.idempotency_keys
+            .get_or_insert_with(HashMap::new)
+            .insert(key, id);
+    }
+
+    /// Host embedder, if one is registered.
+    ///
+    /// Returned by value so it can be passed to core alongside `as_mut`.
+    pub fn embedder(&self) -> Option<HostEmbedder> {
+        self.embedder
+    }
+
+    /// Register or clear the host embedder.
+    pub fn set_embedder(&mut self, embedder: Option<HostEmbedder>) {
+        self.embedder = embedder;
+    }
+
+    /// Find an active frame whose payload hashes to `hash`.
+    ///
+    /// The first call indexes the content hashes stored in active document
+    /// frames' metadata, reading no payloads; later puts in this session
+    /// are recorded with `record_content` and deletes dropped in
+    /// `record_delete`.
+    pub fn find_content(&mut self, hash: &str) -> Option<u64> {
+        if self.content_index.is_none() {
+            let mut index: HashMap<String, Vec<u64>> = HashMap::new();
+            for frame in self
+                .frames()
+                .filter(|f| matches!(f.status, FrameStatus::Active) && f.parent_id.is_none())
+            {
+                if let Some(hash) = frame.extra_metadata.get(CONTENT_HASH_KEY) {
+                    index.entry(hash.clone()).or_default().push(frame.id);
+                }
+            }
+            self.content_index = Some(index);
+        }
+        self.content_index.as_ref()?.get(hash)?.first().copied()
+    }
+
+    /// Remember the content hash of a staged put, once `find_content` has run.
+    pub fn record_content(&mut self, hash: String, id: u64) {
+        if let Some(index) = &mut self.content_index {
+            index.entry(hash).or_default().push(id);
+        }
+    }
+
+    /// Whether indexing is paused.
+    pub fn indexing_paused(&self) -> bool {
+        self.indexing_paused
+    }
+
+    /// Pause or resume indexing.
+    pub fn set_indexing_paused(&mut self, paused: bool) {
+        self.indexing_paused = paused;
+    }
+
+    /// Default put options set with `memvid_set_default_put_options`.
+    pub fn default_put_options(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.default_put_options.as_ref()
+    }
+
+    /// Replace the default put options; `None` clears them.
+    pub fn set_default_put_options(
+        &mut self,
+        options: Option<serde_json::Map<String, serde_json::Value>>,
+    ) {
+        self.default_put_options = options;
+    }
+
+    /// Number of puts staged since the last commit.
+    pub fn pending_puts(&self) -> u64 {
+        self.pending_puts
+    }
+
+    /// Note a staged put, committing if it reaches the autocommit threshold.
+    ///
+    /// Commits are deferred while indexing is paused. A failed commit
+    /// leaves the puts staged and is kept for `take_autocommit_error`.
+    pub fn record_put(&mut self) {
+        self.pending_puts += 1;
+        self.sequence += 1;
+        if self.autocommit_every > 0
+            && self.pending_puts >= self.autocommit_every
+            && !self.indexing_paused
+        {
+            if let Err(e) = self.commit() {
+                self.autocommit_error = Some(e);
+            }
+        }
+    }
+
+    /// Commit after every `every` pending puts; 0 disables autocommit.
+    pub fn set_autocommit(&mut self, every: u64) {
+        self.autocommit_every = every;
+    }
+
+    /// Take the failure of the last autocommit, if any.
+    pub fn take_autocommit_error(&mut self) -> Option<memvid_core::MemvidError> {
+        self.autocommit_error.take()
+    }
+
+    /// Note a staged delete of frame `id`.
+    ///
+    /// An idempotency key that resolved to the frame is forgotten, so a
+    /// later put with the key stores new content, and so is its content
+    /// hash.
+    pub fn record_delete(&mut self, id: u64) {
+        self.pending_deletes += 1;
+        self.sequence += 1;
+        if let Some(keys) = &mut self.idempotency_keys {
+            keys.retain(|_, frame_id| *frame_id != id);
+        }
+        if let Some(index) = &mut self.content_index {
+            for ids in index.values_mut() {
+                ids.retain(|&frame_id| frame_id != id);
+            }
+            index.retain(|_, ids| !ids.is_empty());
+        }
+    }
+
+    /// Whether puts or deletes have been staged since the last commit.
+    pub fn has_pending_changes(&self) -> bool {
+        self.pending_puts > 0 || self.pending_deletes > 0
+    }
+
+    /// Commit staged puts, which indexes them.
+    pub fn commit(&mut self) -> Result<(), memvid_core::MemvidError> {
+        self.as_mut().commit()?;
+        self.pending_puts = 0;
+        self.pending_deletes = 0;
+        self.autocommit_error = None;
+        Ok(())
+    }
+
+    /// Fsync the bytes memvid-core has already written to the memory file.
+    ///
+    /// Opens a second descriptor, since memvid-core does not expose its
+    /// own; `fsync` applies to the file, not the descriptor. Anything core
+    /// still buffers in memory is not written.
+    pub fn fsync_written(&self) -> std::io::Result<()> {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&self.path)?
+            .sync_data()
+    }
+
+    /// Whether search misses puts staged since the last commit.
+    ///
+    /// memvid-core only indexes on commit, so this holds whether or not
+    /// indexing is paused.
+    pub fn index_stale(&self) -> bool {
+        self.pending_puts > 0
+    }
+
+    /// Run a host callback, rejecting calls back into this handle meanwhile.
+    ///
+    /// While `f` runs, `from_ptr_mut` returns `None` for this handle on the
+    /// calling thread, so re-entrant FFI calls fail with `InvalidHandle`.
+    pub fn run_callback<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        run_callback_for(self as *mut Self as usize, f)
+    }
+
+    /// Whether `ptr` is non-null and carries the liveness tag.
+    ///
+    /// Catches NULL and handles that were closed, as long as their memory
+    /// has not been reused. It cannot detect arbitrary garbage pointers.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be null or point to memory readable as a handle.
+    pub unsafe fn is_live(ptr: *const MemvidHandle) -> bool {
+        !ptr.is_null()
+            && unsafe { std::ptr::read_volatile(std::ptr::addr_of!((*ptr).magic)) } == HANDLE_MAGIC
     }
 
     /// Convert a raw pointer to a mutable reference.
     ///
+    /// Returns `None` for a handle that is not live (see `is_live`), whose
+    /// callback is running (see `run_callback`), or that failed to reopen
+    /// (see `reopen_after`).
+    ///
     /// # Safety
     ///
-    /// The pointer must be valid and non-null.
+    /// The pointer must be null or point to memory readable as a handle.
     pub unsafe fn from_ptr_mut<'a>(ptr: *mut MemvidHandle) -> Option<&'a mut Self> {
-        unsafe { ptr.as_mut() }
+        if IN_CALLBACK.get() == ptr as usize || !unsafe { Self::is_live(ptr) } {
+            return None;
+        }
+        unsafe { ptr.as_mut() }.filter(|handle| handle.inner.is_some())
+    }
+}
+
+/// Run a host callback on behalf of the handle at address `handle`.
+///
+/// Same as `MemvidHandle::run_callback`, for callbacks invoked by core
+/// while the handle is already borrowed.
+pub fn run_callback_for<R>(handle: usize, f: impl FnOnce() -> R) -> R {
+    let previous = IN_CALLBACK.replace(handle);
+    let result = f();
+    IN_CALLBACK.set(previous);
+    result
+}
+
+/// Hex BLAKE3 hash of a payload, stored under `CONTENT_HASH_KEY`.
+pub fn content_hash(data: &[u8]) -> String {
+    blake3::hash(data).to_hex().to_string()
+}
+
+impl Drop for MemvidHandle {
+    fn drop(&mut self) {
+        // Volatile so the store is not elided before the memory is freed.
+        unsafe { std::ptr::write_volatile(&mut self.magic, 0) };
     }
 }
@@ -0,0 +1,107 @@
+//! Raw `.mv2` header inspection.
+//!
+//! These helpers read only the fixed-size file header and never open a full
+//! `Memvid` instance, so they are cheap and work on files this library cannot
+//! otherwise load. Decoding is left to memvid-core's header codec, so the
+//! layout and the set of readable versions are core's, not guessed here.
+
+use memvid_core::constants::{HEADER_SIZE, MAGIC, SPEC_MAJOR, SPEC_MINOR};
+use memvid_core::footer::{CommitFooter, FOOTER_SIZE};
+use memvid_core::io::header::HeaderCodec;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Parsed header fields.
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderInfo {
+    /// Whether the magic bytes matched
+    pub magic_ok: bool,
+    /// Encoded format version, if core could decode the header
+    pub version: Option<u16>,
+    /// Offset of the manifest (TOC), if core could decode the header
+    pub footer_offset: Option<u64>,
+}
+
+impl HeaderInfo {
+    /// Format version as a "major.minor" string, or "unknown".
+    pub fn format_version(&self) -> String {
+        match self.version {
+            Some(v) => format!("{}.{}", v >> 8, v & 0xff),
+            None => "unknown".to_string(),
+        }
+    }
+
+    /// Oldest reader version able to open this file, or "unknown".
+    ///
+    /// Derived, not read: the header stores no minimum reader version, but
+    /// minor versions are additive, so any reader with the same major
+    /// version can open the file.
+    pub fn min_reader_version(&self) -> String {
+        match self.version {
+            Some(v) => format!("{}.0", v >> 8),
+            None => "unknown".to_string(),
+        }
+    }
+
+    /// Whether this library can read the file.
+    ///
+    /// True exactly when core's header codec accepted the header.
+    pub fn compatible(&self) -> bool {
+        self.version.is_some()
+    }
+}
+
+/// Format version this library reads, as a "major.minor" string.
+pub fn reader_version() -> String {
+    format!("{SPEC_MAJOR}.{SPEC_MINOR}")
+}
+
+/// Read the header of the file at `path`.
+///
+/// The header block is decoded with core's `HeaderCodec`. A header core
+/// rejects (bad magic or an unsupported version) is still reported, with
+/// `version` unset.
+pub fn read_header(path: &Path) -> std::io::Result<HeaderInfo> {
+    let mut buf = [0u8; HEADER_SIZE];
+    File::open(path)?.read_exact(&mut buf)?;
+
+    let decoded = HeaderCodec::decode(&buf).ok();
+    Ok(HeaderInfo {
+        magic_ok: buf.starts_with(&MAGIC),
+        version: decoded.as_ref().map(|h| h.version),
+        footer_offset: decoded.map(|h| h.footer_offset),
+    })
+}
+
+/// Check the manifest the header points to against its commit footer.
+///
+/// Reads the manifest starting at `footer_offset` and the commit footer
+/// after it, and returns whether the footer decodes, the manifest ends
+/// where the footer starts, and the manifest's hash matches the one
+/// recorded in the footer. Only the manifest and footer are read.
+pub fn manifest_checksum_ok(path: &Path, footer_offset: u64) -> std::io::Result<bool> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let Some(footer_start) = len.checked_sub(FOOTER_SIZE as u64) else {
+        return Ok(false);
+    };
+    if footer_offset > footer_start {
+        return Ok(false);
+    }
+
+    let mut footer = [0u8; FOOTER_SIZE];
+    file.seek(SeekFrom::Start(footer_start))?;
+    file.read_exact(&mut footer)?;
+    let Some(footer) = CommitFooter::decode(&footer) else {
+        return Ok(false);
+    };
+    if footer_offset.checked_add(footer.toc_len) != Some(footer_start) {
+        return Ok(false);
+    }
+
+    let mut toc = vec![0u8; footer.toc_len as usize];
+    file.seek(SeekFrom::Start(footer_offset))?;
+    file.read_exact(&mut toc)?;
+    Ok(footer.hash_matches(&toc))
+}
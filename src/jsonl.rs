@@ -0,0 +1,360 @@
+//! JSONL export and import.
+
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::handle::MemvidHandle;
+use crate::mutation::{put_with_options, PutOptionsJson};
+use crate::util::{cstr_to_option_json, cstr_to_path, set_error, set_ok};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use memvid_core::types::FrameStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::os::raw::c_char;
+
+/// Options for `memvid_export_jsonl`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExportOptionsJson {
+    #[serde(default)]
+    include_deleted: bool,
+    #[serde(default)]
+    include_payload: bool,
+}
+
+/// One exported frame, written as a single JSONL line.
+#[derive(Debug, Serialize)]
+struct ExportLineJson {
+    id: u64,
+    status: String,
+    timestamp: i64,
+    uri: Option<String>,
+    title: Option<String>,
+    track: Option<String>,
+    kind: Option<String>,
+    tags: Vec<String>,
+    labels: Vec<String>,
+    extra_metadata: BTreeMap<String, String>,
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload_base64: Option<String>,
+}
+
+/// Write `handle`'s frames to `out`, one JSON object per line.
+fn export_frames(
+    handle: &mut MemvidHandle,
+    out: &mut impl Write,
+    options: &ExportOptionsJson,
+) -> Result<u64, MemvidError> {
+    let mut written = 0;
+    let frames: Vec<memvid_core::Frame> = handle.frames().collect();
+    for frame in frames {
+        let id = frame.id;
+        // Chunks are re-created when their parent document is put again.
+        if frame.parent_id.is_some() {
+            continue;
+        }
+        if !options.include_deleted && !matches!(frame.status, FrameStatus::Active) {
+            continue;
+        }
+
+        let payload_base64 = if options.include_payload {
+            let bytes = handle
+                .as_mut()
+                .frame_canonical_payload(id)
+                .map_err(MemvidError::from_core_error)?;
+            Some(BASE64.encode(bytes))
+        } else {
+            None
+        };
+        let line = ExportLineJson {
+            id,
+            status: format!("{:?}", frame.status),
+            timestamp: frame.timestamp,
+            uri: frame.uri,
+            title: frame.title,
+            track: frame.track,
+            kind: frame.kind,
+            tags: frame.tags,
+            labels: frame.labels,
+            extra_metadata: frame.extra_metadata,
+            text: handle.as_mut().frame_text_by_id(id).ok(),
+            payload_base64,
+        };
+
+        serde_json::to_writer(&mut *out, &line).map_err(MemvidError::json_serialize)?;
+        out.write_all(b"\n").map_err(MemvidError::io)?;
+        written += 1;
+    }
+    out.flush().map_err(MemvidError::io)?;
+    Ok(written)
+}
+
+/// Export frames to a JSONL file.
+///
+/// Writes one JSON object per frame (metadata plus text content) to
+/// `out_path`, replacing any existing file. Lines are streamed to disk as
+/// frames are read, so the dump is never held in memory. Chunk frames are
+/// skipped: their parent document carries the full content.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `out_path`: Output file path (UTF-8 encoded, null-terminated)
+/// - `options_json`: JSON options (may be NULL for defaults)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Number of frames written on success, 0 on failure (check `error`).
+///
+/// # Options JSON Schema
+///
+/// ```json
+/// {
+///   "include_deleted": false,  // also export deleted frames
+///   "include_payload": false   // add the raw payload as base64
+/// }
+/// ```
+///
+/// # Line JSON Schema
+///
+/// ```json
+/// {
+///   "id": 1,
+///   "status": "Active",
+///   "timestamp": 1234567890,
+///   "uri": "mv2://doc",
+///   "title": "Title",
+///   "track": "notes",
+///   "kind": "text/plain",
+///   "tags": ["tag1"],
+///   "labels": ["label1"],
+///   "extra_metadata": {"key": "value"},
+///   "text": "Frame text",
+///   "payload_base64": "SGVsbG8="  // only with include_payload
+/// }
+/// ```
+///
+/// `text` is null for frames whose text cannot be extracted.
+///
+/// # Errors
+///
+/// - `Io` if the file cannot be written; a partial file may remain
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `out_path` must be a valid null-terminated UTF-8 string
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_export_jsonl(
+    handle: *mut MemvidHandle,
+    out_path: *const c_char,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let path = match unsafe { cstr_to_path(out_path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let options: ExportOptionsJson =
+        match unsafe { cstr_to_option_json(options_json, "options_json") } {
+            Ok(o) => o,
+            Err(e) => return unsafe { set_error(error, e) },
+        };
+
+    let file = match File::create(&path) {
+        Ok(f) => f,
+        Err(e) => return unsafe { set_error(error, MemvidError::io(e)) },
+    };
+
+    match export_frames(handle, &mut BufWriter::new(file), &options) {
+        Ok(written) => {
+            unsafe { set_ok(error) };
+            written
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
+
+/// Options for `memvid_import_jsonl`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ImportOptionsJson {
+    #[serde(default)]
+    strict: bool,
+}
+
+/// One line read by `memvid_import_jsonl`.
+///
+/// Put options come from the line's fields; the rest of an exported line
+/// (such as `id`) is ignored.
+#[derive(Debug, Deserialize)]
+struct ImportLineJson {
+    #[serde(flatten)]
+    options: PutOptionsJson,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    extra_metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    payload_base64: Option<String>,
+}
+
+/// Parse one line into its content and put options.
+///
+/// Returns `None` for lines of frames that were not active.
+fn parse_line(line: &str) -> Result<Option<(Vec<u8>, PutOptionsJson)>, String> {
+    let line: ImportLineJson = serde_json::from_str(line).map_err(|e| e.to_string())?;
+    if line.status.as_deref().is_some_and(|s| s != "Active") {
+        return Ok(None);
+    }
+
+    let content = match (line.payload_base64, line.text) {
+        (Some(payload), _) => BASE64
+            .decode(payload)
+            .map_err(|e| format!("payload_base64 is not valid base64: {e}"))?,
+        (None, Some(text)) => text.into_bytes(),
+        (None, None) => return Err("line has neither text nor payload_base64".to_string()),
+    };
+    let options = line
+        .options
+        .with_frame_metadata(line.tags, line.extra_metadata);
+    Ok(Some((content, options)))
+}
+
+/// Put every line of `input` into `handle`.
+fn import_lines(
+    handle: &mut MemvidHandle,
+    input: impl BufRead,
+    options: &ImportOptionsJson,
+) -> Result<u64, MemvidError> {
+    let mut imported = 0;
+    for (index, line) in input.lines().enumerate() {
+        let line = line.map_err(MemvidError::io)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (content, put_options) = match parse_line(&line) {
+            Ok(Some(parsed)) => parsed,
+            Ok(None) => continue,
+            Err(msg) if options.strict => {
+                return Err(MemvidError::new(
+                    MemvidErrorCode::JsonParse,
+                    format!("line {}: {msg}", index + 1),
+                ));
+            }
+            Err(_) => continue,
+        };
+
+        let (_, deduplicated) = put_with_options(handle, &content, put_options)?;
+        if !deduplicated {
+            imported += 1;
+        }
+    }
+    Ok(imported)
+}
+
+/// Import frames from a JSONL file.
+///
+/// Reads the format written by `memvid_export_jsonl` and puts each line as
+/// a new frame, with the line's fields as put options (see
+/// `memvid_put_bytes_with_options`). Content comes from `payload_base64`
+/// when present, otherwise from `text`. Stored `tags` and `extra_metadata`
+/// are carried over, including idempotency keys, so importing the same
+/// file twice does not duplicate keyed frames. Lines whose `status` is not
+/// `"Active"` are skipped. Frame IDs are assigned anew.
+///
+/// A line is malformed if it is not a JSON object or has neither `text`
+/// nor valid `payload_base64`. Malformed lines are skipped unless `strict`
+/// is set; every non-blank line that was not imported was skipped.
+///
+/// Imported frames are staged; call `memvid_commit()` to persist them.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `in_path`: Input file path (UTF-8 encoded, null-terminated)
+/// - `options_json`: JSON options (may be NULL for defaults)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Number of frames imported on success, 0 on failure (check `error`).
+///
+/// # Options JSON Schema
+///
+/// ```json
+/// {
+///   "strict": false  // abort on the first malformed line
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `Io` if the file cannot be read
+/// - `JsonParse` for a malformed line in strict mode, naming the line
+/// - `ReadOnly` if the handle was opened read-only
+///
+/// On failure, frames imported before the failing line remain staged.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `in_path` must be a valid null-terminated UTF-8 string
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_import_jsonl(
+    handle: *mut MemvidHandle,
+    in_path: *const c_char,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let path = match unsafe { cstr_to_path(in_path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let options: ImportOptionsJson =
+        match unsafe { cstr_to_option_json(options_json, "options_json") } {
+            Ok(o) => o,
+            Err(e) => return unsafe { set_error(error, e) },
+        };
+
+    let file = match File::open(&path) {
+        Ok(f) => f,
+        Err(e) => return unsafe { set_error(error, MemvidError::io(e)) },
+    };
+
+    match import_lines(handle, BufReader::new(file), &options) {
+        Ok(imported) => {
+            unsafe { set_ok(error) };
+            imported
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
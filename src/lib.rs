@@ -5,9 +5,9 @@
 //!
 //! # Thread Safety
 //!
-//! `MemvidHandle` is NOT `Send` or `Sync`. All operations on a handle must
-//! occur from the same thread that created it, or external synchronization
-//! must be provided.
+//! `MemvidHandle` is `Send` but NOT `Sync`. A handle may be moved to another
+//! thread, but operations on it must not overlap; provide external
+//! synchronization or use `MemvidSharedHandle`, which locks every call.
 //!
 //! # Memory Management
 //!
@@ -22,35 +22,99 @@
 //! - `lex` (default): Lexical/full-text search via Tantivy
 //! - `vec`: Vector similarity search via HNSW
 //! - `clip`: CLIP visual embeddings (requires `vec`)
+//! - `temporal_track`: Temporal queries over time-tracked frames
+//! - `logic_mesh`: Logic mesh entity graph
 //! - `full`: All features enabled
 
 #![allow(clippy::missing_safety_doc)]
 
+mod alloc;
 mod ask;
 mod doctor;
+mod embedder;
 mod error;
 mod frame;
 mod handle;
+mod header;
+mod jsonl;
 mod lifecycle;
+mod logging;
+mod merge;
 mod mutation;
+mod schema;
 mod search;
+mod shared;
 mod state;
 mod timeline;
 mod util;
+mod vector;
 mod verify;
+mod watch;
 
 // Re-export all public FFI types and functions
-pub use ask::memvid_ask;
-pub use doctor::{memvid_doctor, memvid_doctor_apply, memvid_doctor_plan};
-pub use error::{memvid_error_free, MemvidError, MemvidErrorCode};
-pub use frame::{memvid_delete_frame, memvid_frame_by_id, memvid_frame_by_uri, memvid_frame_content};
+pub use alloc::{memvid_set_allocator, MemvidFreeFn, MemvidMallocFn};
+pub use ask::{
+    memvid_ask, memvid_ask_with_reranker, memvid_ask_with_synthesis, MemvidRerankFn,
+    MemvidSynthesizeFn,
+};
+pub use doctor::{
+    memvid_doctor, memvid_doctor_apply, memvid_doctor_cancellable, memvid_doctor_plan,
+    memvid_rebuild_index, memvid_vacuum, memvid_vacuum_report,
+};
+pub use embedder::{memvid_register_embedder, MemvidEmbedFn};
+pub use error::{
+    memvid_error_cause_json, memvid_error_code_str, memvid_error_free, memvid_last_error_code,
+    memvid_last_error_message, MemvidError, MemvidErrorCode,
+};
+pub use frame::{
+    memvid_delete_frame, memvid_delete_frame_by_uri, memvid_delete_frame_with_reason,
+    memvid_frame_by_id, memvid_frame_by_id_ex, memvid_frame_by_uri, memvid_frame_children,
+    memvid_frame_content, memvid_frame_content_by_uri, memvid_frame_content_range,
+    memvid_frame_exists, memvid_frame_exists_ex, memvid_frame_parent, memvid_frame_payload,
+    memvid_frame_sidecar, memvid_frame_storage_info, memvid_frames_by_ids, memvid_frames_by_track,
+    memvid_frames_in_range, memvid_frames_iter_free, memvid_frames_iter_new,
+    memvid_frames_iter_next, memvid_restore_frame, memvid_update_frame, MemvidFrameIter,
+};
 pub use handle::MemvidHandle;
-pub use lifecycle::{memvid_close, memvid_create, memvid_open};
-pub use mutation::{memvid_commit, memvid_put_bytes, memvid_put_bytes_with_options};
-pub use search::{memvid_search, memvid_string_free};
-pub use state::{memvid_frame_count, memvid_stats, MemvidStats};
+pub use jsonl::{memvid_export_jsonl, memvid_import_jsonl};
+pub use lifecycle::{
+    memvid_close, memvid_create, memvid_create_from_template, memvid_create_with_options,
+    memvid_handle_is_valid, memvid_open, memvid_open_readonly, memvid_open_with_options,
+    memvid_refresh, memvid_set_write_locked,
+};
+#[cfg(windows)]
+pub use lifecycle::{memvid_create_w, memvid_open_w};
+pub use logging::{memvid_set_log_callback, MemvidLogFn};
+pub use merge::memvid_merge;
+pub use mutation::{
+    memvid_commit, memvid_commit_detailed, memvid_commit_with_progress, memvid_contains_hash,
+    memvid_estimate_put, memvid_flush, memvid_put_batch, memvid_put_bytes,
+    memvid_put_bytes_detailed, memvid_put_bytes_idempotent, memvid_put_bytes_with_options,
+    memvid_put_file, memvid_set_autocommit, memvid_set_default_put_options,
+    memvid_set_indexing_paused, memvid_u64_array_free, MemvidCommitProgressFn, MemvidPutItem,
+};
+pub use schema::memvid_request_schema;
+pub use search::{
+    memvid_bytes_free, memvid_search, memvid_search_bytes, memvid_search_diff, memvid_search_into,
+    memvid_search_multi, memvid_search_stream, memvid_string_free, MemvidSearchHitFn,
+};
+pub use shared::{
+    memvid_close_shared, memvid_open_shared, memvid_shared_commit, memvid_shared_frame_count,
+    memvid_shared_put_bytes_with_options, memvid_shared_search, memvid_shared_stats,
+    MemvidSharedHandle,
+};
+pub use state::{
+    memvid_current_sequence, memvid_frame_count, memvid_frame_count_checked, memvid_list_tags,
+    memvid_ping, memvid_stats, memvid_stats_history, memvid_stats_json, MemvidStats,
+};
 pub use timeline::memvid_timeline;
-pub use verify::memvid_verify;
+pub use vector::{
+    memvid_clip_search, memvid_find_near_duplicates, memvid_put_image, memvid_similar_frames,
+};
+#[cfg(windows)]
+pub use verify::memvid_verify_w;
+pub use verify::{memvid_format_info, memvid_inspect, memvid_verify, memvid_verify_with_options};
+pub use watch::{memvid_watch, memvid_watch_stop, MemvidWatchFn, MemvidWatcher};
 
 use std::os::raw::c_char;
 
@@ -67,6 +131,69 @@ pub extern "C" fn memvid_version() -> *const c_char {
     VERSION.as_ptr() as *const c_char
 }
 
+/// ABI version of the C interface.
+///
+/// Bumped by hand whenever a `repr(C)` struct changes, so bindings can
+/// refuse a library built with a different layout.
+pub const MEMVID_ABI_VERSION: u32 = 1;
+
+/// Structured library version.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemvidVersionInfo {
+    /// Major version
+    pub major: u32,
+    /// Minor version
+    pub minor: u32,
+    /// Patch version
+    pub patch: u32,
+    /// ABI version (`MEMVID_ABI_VERSION`)
+    pub abi_version: u32,
+}
+
+/// Parse a decimal version component at compile time.
+const fn parse_version_part(s: &str) -> u32 {
+    let bytes = s.as_bytes();
+    let mut value = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        value = value * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    value
+}
+
+const VERSION_INFO: MemvidVersionInfo = MemvidVersionInfo {
+    major: parse_version_part(env!("CARGO_PKG_VERSION_MAJOR")),
+    minor: parse_version_part(env!("CARGO_PKG_VERSION_MINOR")),
+    patch: parse_version_part(env!("CARGO_PKG_VERSION_PATCH")),
+    abi_version: MEMVID_ABI_VERSION,
+};
+
+/// Library version as numbers, plus the ABI version.
+///
+/// # Parameters
+///
+/// - `out`: Out-parameter for the version
+///
+/// # Returns
+///
+/// 1 on success, 0 if `out` is NULL.
+///
+/// # Safety
+///
+/// - `out` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_version_info(out: *mut MemvidVersionInfo) -> i32 {
+    match unsafe { out.as_mut() } {
+        Some(out) => {
+            *out = VERSION_INFO;
+            1
+        }
+        None => 0,
+    }
+}
+
 /// Feature flags bitmask.
 ///
 /// # Returns
@@ -75,6 +202,8 @@ pub extern "C" fn memvid_version() -> *const c_char {
 /// - Bit 0 (0x01): `lex` - Lexical search
 /// - Bit 1 (0x02): `vec` - Vector search
 /// - Bit 2 (0x04): `clip` - CLIP embeddings
+/// - Bit 3 (0x08): `temporal_track` - Temporal queries
+/// - Bit 4 (0x10): `logic_mesh` - Logic mesh entity graph
 ///
 /// # Example
 ///
@@ -83,6 +212,8 @@ pub extern "C" fn memvid_version() -> *const c_char {
 /// if (features & 0x01) { /* lex enabled */ }
 /// if (features & 0x02) { /* vec enabled */ }
 /// if (features & 0x04) { /* clip enabled */ }
+/// if (features & 0x08) { /* temporal_track enabled */ }
+/// if (features & 0x10) { /* logic_mesh enabled */ }
 /// ```
 #[unsafe(no_mangle)]
 pub extern "C" fn memvid_features() -> u32 {
@@ -103,6 +234,16 @@ pub extern "C" fn memvid_features() -> u32 {
         flags |= 1 << 2;
     }
 
+    #[cfg(feature = "temporal_track")]
+    {
+        flags |= 1 << 3;
+    }
+
+    #[cfg(feature = "logic_mesh")]
+    {
+        flags |= 1 << 4;
+    }
+
     flags
 }
 
@@ -110,6 +251,58 @@ pub extern "C" fn memvid_features() -> u32 {
 mod tests {
     use super::*;
     use std::ffi::CString;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Temp file path unique to this test process and call.
+    ///
+    /// Tests run in parallel and share the temp dir, so every path gets the
+    /// process ID and a counter. The file is removed on drop.
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let n = NEXT.fetch_add(1, Ordering::Relaxed);
+            let file = format!("test_ffi_{}_{n}_{name}", std::process::id());
+            Self(std::env::temp_dir().join(file))
+        }
+    }
+
+    impl std::ops::Deref for TempPath {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl AsRef<Path> for TempPath {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    /// Create a memory at `path`, put each of `contents`, and commit.
+    fn create_with(path: &TempPath, contents: &[&[u8]]) -> *mut MemvidHandle {
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        for content in contents {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        handle
+    }
 
     #[test]
     fn test_version() {
@@ -119,18 +312,57 @@ mod tests {
         assert!(!version_str.to_str().unwrap().is_empty());
     }
 
+    #[test]
+    fn test_version_info() {
+        let mut info = MemvidVersionInfo::default();
+        assert_eq!(unsafe { memvid_version_info(&mut info) }, 1);
+        let version = format!("{}.{}.{}", info.major, info.minor, info.patch);
+        assert_eq!(version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.abi_version, MEMVID_ABI_VERSION);
+        assert_eq!(unsafe { memvid_version_info(std::ptr::null_mut()) }, 0);
+    }
+
     #[test]
     fn test_features() {
         let features = memvid_features();
         // At minimum, lex should be enabled (default feature)
         #[cfg(feature = "lex")]
         assert!(features & 0x01 != 0);
+        #[cfg(feature = "temporal_track")]
+        assert!(features & 0x08 != 0);
+        #[cfg(not(feature = "temporal_track"))]
+        assert!(features & 0x08 == 0);
+        #[cfg(feature = "logic_mesh")]
+        assert!(features & 0x10 != 0);
+        #[cfg(not(feature = "logic_mesh"))]
+        assert!(features & 0x10 == 0);
+    }
+
+    #[test]
+    fn test_request_schema() {
+        for kind in ["search", "ask", "put_options", "timeline", "doctor"] {
+            let kind = CString::new(kind).unwrap();
+            let schema = unsafe { memvid_request_schema(kind.as_ptr()) };
+            assert!(!schema.is_null());
+            let schema = unsafe { std::ffi::CStr::from_ptr(schema) };
+            let schema: serde_json::Value = serde_json::from_slice(schema.to_bytes()).unwrap();
+            assert!(schema["properties"].is_object());
+        }
+
+        let kind = CString::new("search").unwrap();
+        let schema = unsafe { std::ffi::CStr::from_ptr(memvid_request_schema(kind.as_ptr())) };
+        let schema: serde_json::Value = serde_json::from_slice(schema.to_bytes()).unwrap();
+        assert!(schema["properties"]["query"].is_object());
+        assert_eq!(schema["required"], serde_json::json!(["query"]));
+
+        let unknown = CString::new("frobnicate").unwrap();
+        assert!(unsafe { memvid_request_schema(unknown.as_ptr()) }.is_null());
+        assert!(unsafe { memvid_request_schema(std::ptr::null()) }.is_null());
     }
 
     #[test]
     fn test_create_and_close() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_create.mv2");
+        let path = TempPath::new("create.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -140,15 +372,11 @@ mod tests {
         assert_eq!(error.code, MemvidErrorCode::Ok);
 
         unsafe { memvid_close(handle) };
-
-        // Cleanup
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
     fn test_put_and_search() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_put_search.mv2");
+        let path = TempPath::new("put_search.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -182,16 +410,21 @@ mod tests {
         assert_eq!(result, 1);
         assert!(stats.frame_count >= 1);
 
-        unsafe { memvid_close(handle) };
+        // JSON stats carry the same values
+        let stats_ptr = unsafe { memvid_stats_json(handle, &mut error) };
+        assert!(!stats_ptr.is_null());
+        let stats_str = unsafe { std::ffi::CStr::from_ptr(stats_ptr) };
+        let json: serde_json::Value = serde_json::from_str(stats_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["frame_count"], stats.frame_count);
+        assert_eq!(json["capacity_bytes"], stats.capacity_bytes);
+        unsafe { memvid_string_free(stats_ptr) };
 
-        // Cleanup
-        let _ = std::fs::remove_file(&path);
+        unsafe { memvid_close(handle) };
     }
 
     #[test]
     fn test_open() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_open.mv2");
+        let path = TempPath::new("open.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -214,15 +447,11 @@ mod tests {
         assert_eq!(count, 1);
 
         unsafe { memvid_close(handle) };
-
-        // Cleanup
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
     fn test_put_bytes_with_options() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_put_options.mv2");
+        let path = TempPath::new("put_options.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -246,15 +475,75 @@ mod tests {
 
         unsafe { memvid_commit(handle, &mut error) };
         unsafe { memvid_close(handle) };
+    }
 
-        // Cleanup
-        let _ = std::fs::remove_file(&path);
+    #[test]
+    fn test_put_idempotency_key() {
+        let path = TempPath::new("put_idempotent.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"Retried document.";
+        let options = CString::new(r#"{"idempotency_key": "ingest-1"}"#).unwrap();
+        let mut deduplicated = -1;
+        let first = unsafe {
+            memvid_put_bytes_idempotent(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut deduplicated,
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(deduplicated, 0);
+
+        // Retry with the same key creates nothing
+        let second = unsafe {
+            memvid_put_bytes_idempotent(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut deduplicated,
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(deduplicated, 1);
+        assert_eq!(first, second);
+
+        unsafe { memvid_commit(handle, &mut error) };
+        let count = unsafe { memvid_frame_count(handle, &mut error) };
+        assert_eq!(count, 1);
+
+        // Deleting the frame frees its key
+        unsafe { memvid_delete_frame(handle, first, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let third = unsafe {
+            memvid_put_bytes_idempotent(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut deduplicated,
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(deduplicated, 0);
+        assert_ne!(third, first);
+
+        unsafe { memvid_close(handle) };
     }
 
     #[test]
     fn test_frame_count() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_frame_count.mv2");
+        let path = TempPath::new("frame_count.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -279,10 +568,18 @@ mod tests {
         let count = unsafe { memvid_frame_count(handle, &mut error) };
         assert_eq!(count, 3);
 
-        unsafe { memvid_close(handle) };
+        // Checked variant separates the count from success
+        let mut count = 0u64;
+        let ok = unsafe { memvid_frame_count_checked(handle, &mut count, &mut error) };
+        assert_eq!(ok, 1);
+        assert_eq!(count, 3);
+        let ok =
+            unsafe { memvid_frame_count_checked(std::ptr::null_mut(), &mut count, &mut error) };
+        assert_eq!(ok, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidHandle);
+        unsafe { memvid_error_free(&mut error) };
 
-        // Cleanup
-        let _ = std::fs::remove_file(&path);
+        unsafe { memvid_close(handle) };
     }
 
     #[test]
@@ -304,10 +601,201 @@ mod tests {
         assert!(error.message.is_null());
     }
 
+    #[test]
+    fn test_json_serialize_error_code() {
+        let mut error = MemvidError::ok();
+
+        // Non-string map keys cannot be serialized to JSON
+        let output: std::collections::HashMap<(u8, u8), u8> = [((1, 2), 3)].into();
+        let json_ptr = unsafe { util::json_to_cstr(&output, &mut error) };
+        assert!(json_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::JsonSerialize);
+
+        unsafe { memvid_error_free(&mut error) };
+    }
+
+    #[test]
+    fn test_set_allocator() {
+        extern "C" fn host_malloc(size: libc::size_t) -> *mut std::os::raw::c_void {
+            unsafe { libc::malloc(size) }
+        }
+
+        extern "C" fn host_free(ptr: *mut std::os::raw::c_void) {
+            unsafe { libc::free(ptr) }
+        }
+
+        let mut error = MemvidError::ok();
+        let result = unsafe { memvid_set_allocator(None, Some(host_free), &mut error) };
+        assert_eq!(result, 0);
+        assert_eq!(error.code, MemvidErrorCode::NullPointer);
+        unsafe { memvid_error_free(&mut error) };
+
+        // Too late once a string has been returned; the allocator is global,
+        // so this test never installs one
+        let chain = unsafe { memvid_error_cause_json(&error) };
+        unsafe { memvid_string_free(chain) };
+        let result =
+            unsafe { memvid_set_allocator(Some(host_malloc), Some(host_free), &mut error) };
+        assert_eq!(result, 0);
+        assert_eq!(error.code, MemvidErrorCode::FeatureUnavailable);
+        unsafe { memvid_error_free(&mut error) };
+    }
+
+    #[test]
+    fn test_last_error() {
+        // Failures are recorded even without an error out-parameter
+        let handle = unsafe { memvid_open(std::ptr::null(), std::ptr::null_mut()) };
+        assert!(handle.is_null());
+        assert_eq!(memvid_last_error_code(), MemvidErrorCode::NullPointer);
+        let message = memvid_last_error_message();
+        assert!(!message.is_null());
+        let message = unsafe { std::ffi::CStr::from_ptr(message) };
+        assert!(message.to_str().unwrap().contains("path"));
+
+        // Successful calls leave it unchanged
+        let mut error = MemvidError::ok();
+        unsafe { memvid_frame_count(std::ptr::null_mut(), &mut error) };
+        assert_eq!(memvid_last_error_code(), MemvidErrorCode::InvalidHandle);
+        unsafe { memvid_error_free(&mut error) };
+        assert!(!memvid_version().is_null());
+        assert_eq!(memvid_last_error_code(), MemvidErrorCode::InvalidHandle);
+    }
+
+    #[test]
+    fn test_error_code_str() {
+        let name = |code| unsafe { std::ffi::CStr::from_ptr(memvid_error_code_str(code)) };
+        assert_eq!(name(MemvidErrorCode::Ok).to_str().unwrap(), "Ok");
+        assert_eq!(
+            name(MemvidErrorCode::VecNotEnabled).to_str().unwrap(),
+            "VecNotEnabled"
+        );
+        assert_eq!(
+            name(MemvidErrorCode::JsonSerialize).to_str().unwrap(),
+            "JsonSerialize"
+        );
+    }
+
+    extern "C" fn record_warning(level: u32, msg: *const c_char, user_data: *mut std::ffi::c_void) {
+        let msg = unsafe { std::ffi::CStr::from_ptr(msg) };
+        if level == 2 && msg.to_str().unwrap().contains("log callback test") {
+            let seen = unsafe { &*(user_data as *const std::sync::atomic::AtomicBool) };
+            seen.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    extern "C" fn length_embedding(
+        text: *const c_char,
+        out: *mut f32,
+        dim: u32,
+        _user_data: *mut std::ffi::c_void,
+    ) -> i32 {
+        let text = unsafe { std::ffi::CStr::from_ptr(text) }.to_bytes();
+        if text.is_empty() {
+            return 0;
+        }
+        let out = unsafe { std::slice::from_raw_parts_mut(out, dim as usize) };
+        out.fill(text.len() as f32);
+        1
+    }
+
+    #[test]
+    fn test_register_embedder() {
+        let path = TempPath::new("register_embedder.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let null = std::ptr::null_mut();
+        let embed: Option<MemvidEmbedFn> = Some(length_embedding);
+
+        let ok = unsafe { memvid_register_embedder(handle, 0, embed, null, &mut error) };
+        assert_eq!(ok, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+
+        let ok = unsafe { memvid_register_embedder(handle, 3, embed, null, &mut error) };
+        assert_eq!(ok, 1);
+        let embedder = unsafe { MemvidHandle::from_ptr_mut(handle) }
+            .unwrap()
+            .embedder()
+            .unwrap();
+        assert_eq!(embedder.embed("four").unwrap(), vec![4.0; 3]);
+        let mut err = embedder.embed("").unwrap_err();
+        assert_eq!(err.code, MemvidErrorCode::EmbeddingFailed);
+        unsafe { memvid_error_free(&mut err) };
+
+        let ok = unsafe { memvid_register_embedder(handle, 0, None, null, &mut error) };
+        assert_eq!(ok, 1);
+        assert!(unsafe { MemvidHandle::from_ptr_mut(handle) }
+            .unwrap()
+            .embedder()
+            .is_none());
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_set_log_callback() {
+        static SEEN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+        let mut error = MemvidError::ok();
+        let user_data = &SEEN as *const _ as *mut std::ffi::c_void;
+
+        let ok = unsafe { memvid_set_log_callback(Some(record_warning), user_data, &mut error) };
+        assert_eq!(ok, 1);
+        tracing::warn!(answer = 42, "log callback test");
+        assert!(SEEN.load(std::sync::atomic::Ordering::SeqCst));
+
+        let ok = unsafe { memvid_set_log_callback(None, std::ptr::null_mut(), &mut error) };
+        assert_eq!(ok, 1);
+    }
+
+    #[test]
+    fn test_string_to_cstr() {
+        let mut error = MemvidError::ok();
+        let ptr = unsafe { util::string_to_cstr("text".to_string(), None, &mut error) };
+        assert!(!ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_string_free(ptr) };
+
+        let hint = Some("use memvid_frame_payload");
+        let ptr = unsafe { util::string_to_cstr("a\0b".to_string(), hint, &mut error) };
+        assert!(ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InteriorNul);
+        let message = unsafe { std::ffi::CStr::from_ptr(error.message) };
+        let message = message.to_str().unwrap();
+        assert!(message.ends_with("; use memvid_frame_payload"));
+        unsafe { memvid_error_free(&mut error) };
+    }
+
+    #[test]
+    fn test_error_cause_json() {
+        let mut error = MemvidError::ok();
+
+        // No message yields an empty chain
+        let chain_ptr = unsafe { memvid_error_cause_json(&error) };
+        assert!(!chain_ptr.is_null());
+        let chain = unsafe { std::ffi::CStr::from_ptr(chain_ptr) };
+        assert_eq!(chain.to_str().unwrap(), "[]");
+        unsafe { memvid_string_free(chain_ptr) };
+
+        // Core error from opening a missing file
+        let path = CString::new("/nonexistent/path/to/cause.mv2").unwrap();
+        let handle = unsafe { memvid_open(path.as_ptr(), &mut error) };
+        assert!(handle.is_null());
+
+        let chain_ptr = unsafe { memvid_error_cause_json(&error) };
+        assert!(!chain_ptr.is_null());
+        let chain = unsafe { std::ffi::CStr::from_ptr(chain_ptr) };
+        let messages: Vec<String> = serde_json::from_str(chain.to_str().unwrap()).unwrap();
+        assert!(!messages.is_empty());
+
+        unsafe { memvid_string_free(chain_ptr) };
+        unsafe { memvid_error_free(&mut error) };
+    }
+
     #[test]
     fn test_frame_by_id() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_frame_by_id.mv2");
+        let path = TempPath::new("frame_by_id.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -355,13 +843,11 @@ mod tests {
 
         unsafe { memvid_string_free(frame_json) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
     fn test_frame_by_uri() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_frame_by_uri.mv2");
+        let path = TempPath::new("frame_by_uri.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -394,13 +880,11 @@ mod tests {
 
         unsafe { memvid_string_free(frame_json) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
     fn test_frame_content() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_frame_content.mv2");
+        let path = TempPath::new("frame_content.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -432,330 +916,3558 @@ mod tests {
 
         unsafe { memvid_string_free(content_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn test_delete_frame() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_delete_frame.mv2");
+    fn test_frame_content_by_uri() {
+        let path = TempPath::new("frame_content_by_uri.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
-        assert!(!handle.is_null());
-
-        // Put content
-        let content = b"Document to be deleted.";
-        let _seq =
-            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        let content = b"Content read straight by URI.";
+        let options = CString::new(r#"{"uri": "test://by-uri"}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
         unsafe { memvid_commit(handle, &mut error) };
-        unsafe { memvid_close(handle) };
-
-        // Reopen
-        let handle = unsafe { memvid_open(path_cstr.as_ptr(), &mut error) };
-        assert!(!handle.is_null());
-
-        // Verify we have 1 frame
-        let mut stats = MemvidStats::default();
-        unsafe { memvid_stats(handle, &mut stats, &mut error) };
-        assert_eq!(stats.active_frame_count, 1);
 
-        // Delete frame (frame IDs are 0-indexed, first frame has id=0)
-        let seq = unsafe { memvid_delete_frame(handle, 0, &mut error) };
-        if seq == 0 && error.code != MemvidErrorCode::Ok {
-            let msg = if !error.message.is_null() {
-                unsafe { std::ffi::CStr::from_ptr(error.message) }
-                    .to_str()
-                    .unwrap_or("unknown")
-            } else {
-                "no message"
-            };
-            panic!("delete_frame failed: {:?} - {}", error.code, msg);
-        }
-        assert!(seq > 0);
-        unsafe { memvid_commit(handle, &mut error) };
+        let uri = CString::new("test://by-uri").unwrap();
+        let content_ptr = unsafe { memvid_frame_content_by_uri(handle, uri.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let content_str = unsafe { std::ffi::CStr::from_ptr(content_ptr) };
+        assert!(content_str
+            .to_str()
+            .unwrap()
+            .starts_with("Content read straight by URI."));
+        unsafe { memvid_string_free(content_ptr) };
 
-        // Verify active count is now 0
-        unsafe { memvid_stats(handle, &mut stats, &mut error) };
-        assert_eq!(stats.active_frame_count, 0);
+        let missing = CString::new("test://missing").unwrap();
+        let content_ptr =
+            unsafe { memvid_frame_content_by_uri(handle, missing.as_ptr(), &mut error) };
+        assert!(content_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::FrameNotFoundByUri);
+        unsafe { memvid_error_free(&mut error) };
 
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn test_timeline() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_timeline.mv2");
+    fn test_frame_storage_info() {
+        let path = TempPath::new("frame_storage_info.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
-        assert!(!handle.is_null());
-
-        // Put multiple documents
-        let content1 = b"First document in timeline.";
-        let content2 = b"Second document in timeline.";
-        let content3 = b"Third document in timeline.";
-        unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
-        unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
-        unsafe { memvid_put_bytes(handle, content3.as_ptr(), content3.len(), &mut error) };
+        let content = "Repetitive content compresses well. ".repeat(200);
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
         unsafe { memvid_commit(handle, &mut error) };
 
-        // Query timeline with default options
-        let timeline_ptr = unsafe { memvid_timeline(handle, std::ptr::null(), &mut error) };
-        assert!(!timeline_ptr.is_null());
+        let info_ptr = unsafe { memvid_frame_storage_info(handle, 0, &mut error) };
         assert_eq!(error.code, MemvidErrorCode::Ok);
+        let info_str = unsafe { std::ffi::CStr::from_ptr(info_ptr) };
+        let info: serde_json::Value = serde_json::from_str(info_str.to_str().unwrap()).unwrap();
+        let payload_length = info["payload_length"].as_u64().unwrap();
+        let stored_bytes = info["stored_bytes"].as_u64().unwrap();
+        assert!(payload_length > 0 && stored_bytes > 0);
+        assert!(["zstd", "plain"].contains(&info["codec"].as_str().unwrap()));
+        let ratio = stored_bytes as f64 / payload_length as f64;
+        assert!((info["ratio"].as_f64().unwrap() - ratio).abs() < 1e-9);
+        unsafe { memvid_string_free(info_ptr) };
+
+        let info_ptr = unsafe { memvid_frame_storage_info(handle, 999, &mut error) };
+        assert!(info_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::FrameNotFound);
+        unsafe { memvid_error_free(&mut error) };
 
-        let timeline_str = unsafe { std::ffi::CStr::from_ptr(timeline_ptr) };
-        let json = timeline_str.to_str().unwrap();
-        assert!(json.contains("\"entries\""));
-        assert!(json.contains("\"count\":3"));
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_stats_history() {
+        let path = TempPath::new("stats_history.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Growth chart sample."]);
+
+        let history_ptr = unsafe { memvid_stats_history(handle, 0, &mut error) };
+        assert!(!history_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        // Only the current snapshot is available
+        let history_str = unsafe { std::ffi::CStr::from_ptr(history_ptr) };
+        let json: serde_json::Value = serde_json::from_str(history_str.to_str().unwrap()).unwrap();
+        let snapshots = json.as_array().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0]["frame_count"], 1);
+        assert!(snapshots[0]["size_bytes"].as_u64().unwrap() > 0);
+
+        unsafe { memvid_string_free(history_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_default_put_options() {
+        let path = TempPath::new("default_put_options.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let defaults = CString::new(r#"{"track": "ingest", "title": "Default"}"#).unwrap();
+        let ok = unsafe { memvid_set_default_put_options(handle, defaults.as_ptr(), &mut error) };
+        assert_eq!(ok, 1);
+
+        let content = b"Put with the default options.";
+        let id = unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        // Per-call fields override the defaults one by one.
+        let options = CString::new(r#"{"title": "Own"}"#).unwrap();
+        let other = b"Put with its own title.";
+        let overridden = unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                other.as_ptr(),
+                other.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        // NULL clears the defaults.
+        let ok = unsafe { memvid_set_default_put_options(handle, std::ptr::null(), &mut error) };
+        assert_eq!(ok, 1);
+        let bare = b"Put after clearing.";
+        let cleared = unsafe { memvid_put_bytes(handle, bare.as_ptr(), bare.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let frame = |id: u64| {
+            let ptr = unsafe { memvid_frame_by_id(handle, id, &mut MemvidError::ok()) };
+            let json = unsafe { std::ffi::CStr::from_ptr(ptr) }.to_str().unwrap();
+            let frame: serde_json::Value = serde_json::from_str(json).unwrap();
+            unsafe { memvid_string_free(ptr) };
+            frame
+        };
+        let first = frame(id);
+        assert_eq!(first["track"], "ingest");
+        assert_eq!(first["title"], "Default");
+        let second = frame(overridden);
+        assert_eq!(second["track"], "ingest");
+        assert_eq!(second["title"], "Own");
+        assert!(frame(cleared)["track"].is_null());
+
+        // A shared idempotency key would deduplicate every put.
+        let keyed = CString::new(r#"{"idempotency_key": "k"}"#).unwrap();
+        let ok = unsafe { memvid_set_default_put_options(handle, keyed.as_ptr(), &mut error) };
+        assert_eq!(ok, 0);
+        assert_eq!(error.code, MemvidErrorCode::SchemaValidation);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_put_verbatim() {
+        let path = TempPath::new("put_verbatim.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content = "Meeting moved to 2024-03-01 with Alice.";
+        let options = CString::new(r#"{"title": "Exact", "verbatim": true}"#).unwrap();
+        let id = unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let content_ptr = unsafe { memvid_frame_content(handle, id, &mut error) };
+        let stored = unsafe { std::ffi::CStr::from_ptr(content_ptr) };
+        assert_eq!(stored.to_str().unwrap(), content);
+        unsafe { memvid_string_free(content_ptr) };
+
+        // Enrichment options contradict verbatim
+        let options = CString::new(r#"{"verbatim": true, "auto_tag": true}"#).unwrap();
+        let id = unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(id, 0);
+        assert_eq!(error.code, MemvidErrorCode::SchemaValidation);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frame_payload() {
+        let path = TempPath::new("frame_payload.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Embedded nulls survive the round trip
+        let content = b"binary\0blob\0\xff";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let mut ptr = std::ptr::null_mut();
+        let mut len = 0usize;
+        let ok = unsafe { memvid_frame_payload(handle, 0, &mut ptr, &mut len, &mut error) };
+        assert_eq!(ok, 1);
+        let payload = unsafe { std::slice::from_raw_parts(ptr, len) };
+        assert_eq!(payload, content);
+        unsafe { memvid_bytes_free(ptr, len) };
+
+        // Null out-params
+        let ok =
+            unsafe { memvid_frame_payload(handle, 0, std::ptr::null_mut(), &mut len, &mut error) };
+        assert_eq!(ok, 0);
+        assert_eq!(error.code, MemvidErrorCode::NullPointer);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frame_content_range() {
+        let path = TempPath::new("frame_content_range.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content = "A long transcript, paged through in pieces. ".repeat(20);
+        let id = unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let full_ptr = unsafe { memvid_frame_content(handle, id, &mut error) };
+        let full = unsafe { std::ffi::CStr::from_ptr(full_ptr) };
+        let full = full.to_bytes().to_vec();
+        unsafe { memvid_string_free(full_ptr) };
+
+        // Pages reassemble the full text; past the end is empty.
+        let mut paged = Vec::new();
+        loop {
+            let mut ptr = std::ptr::null_mut();
+            let mut len = 0usize;
+            let offset = paged.len() as u64;
+            let ok = unsafe {
+                memvid_frame_content_range(handle, id, offset, 100, &mut ptr, &mut len, &mut error)
+            };
+            assert_eq!(ok, 1);
+            if len == 0 {
+                unsafe { memvid_bytes_free(ptr, len) };
+                break;
+            }
+            paged.extend_from_slice(unsafe { std::slice::from_raw_parts(ptr, len) });
+            unsafe { memvid_bytes_free(ptr, len) };
+        }
+        assert_eq!(paged, full);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frame_sidecar() {
+        let path = TempPath::new("frame_sidecar.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // "AAEC/w==" is [0x00, 0x01, 0x02, 0xff]
+        let content = b"Document with a thumbnail.";
+        let options = CString::new(r#"{"sidecar_base64": "AAEC/w=="}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let mut ptr = std::ptr::null_mut();
+        let mut len = 0usize;
+        let ok = unsafe { memvid_frame_sidecar(handle, 0, &mut ptr, &mut len, &mut error) };
+        assert_eq!(ok, 1);
+        let sidecar = unsafe { std::slice::from_raw_parts(ptr, len) };
+        assert_eq!(sidecar, &[0x00, 0x01, 0x02, 0xff]);
+        unsafe { memvid_bytes_free(ptr, len) };
+
+        // A frame without a sidecar yields an empty result
+        let ok = unsafe { memvid_frame_sidecar(handle, 1, &mut ptr, &mut len, &mut error) };
+        assert_eq!(ok, 1);
+        assert!(ptr.is_null());
+        assert_eq!(len, 0);
+
+        // Oversized sidecars are rejected
+        let big = format!(r#"{{"sidecar_base64": "{}"}}"#, "A".repeat(32 * 1024));
+        let options = CString::new(big).unwrap();
+        let result = unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(result, 0);
+        assert_eq!(error.code, MemvidErrorCode::SchemaValidation);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_delete_frame() {
+        let path = TempPath::new("delete_frame.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Put content
+        let content = b"Document to be deleted.";
+        let _seq =
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        // Reopen
+        let handle = unsafe { memvid_open(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Verify we have 1 frame
+        let mut stats = MemvidStats::default();
+        unsafe { memvid_stats(handle, &mut stats, &mut error) };
+        assert_eq!(stats.active_frame_count, 1);
+
+        // Delete frame (frame IDs are 0-indexed, first frame has id=0)
+        let seq = unsafe { memvid_delete_frame(handle, 0, &mut error) };
+        if seq == 0 && error.code != MemvidErrorCode::Ok {
+            let msg = if !error.message.is_null() {
+                unsafe { std::ffi::CStr::from_ptr(error.message) }
+                    .to_str()
+                    .unwrap_or("unknown")
+            } else {
+                "no message"
+            };
+            panic!("delete_frame failed: {:?} - {}", error.code, msg);
+        }
+        assert!(seq > 0);
+        unsafe { memvid_commit(handle, &mut error) };
+
+        // Verify active count is now 0
+        unsafe { memvid_stats(handle, &mut stats, &mut error) };
+        assert_eq!(stats.active_frame_count, 0);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_delete_frame_by_uri() {
+        let path = TempPath::new("delete_by_uri.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let options = CString::new(r#"{"uri": "mv2://sync/doc.txt"}"#).unwrap();
+        for content in [&b"Version one."[..], b"Version two."] {
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            };
+        }
+        let other = b"Unrelated document.";
+        unsafe { memvid_put_bytes(handle, other.as_ptr(), other.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let uri = CString::new("mv2://sync/doc.txt").unwrap();
+        let seq = unsafe { memvid_delete_frame_by_uri(handle, uri.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert!(seq > 0);
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let mut stats = MemvidStats::default();
+        unsafe { memvid_stats(handle, &mut stats, &mut error) };
+        assert_eq!(stats.active_frame_count, 1);
+
+        // Nothing left to delete
+        let seq = unsafe { memvid_delete_frame_by_uri(handle, uri.as_ptr(), &mut error) };
+        assert_eq!(seq, 0);
+        assert_eq!(error.code, MemvidErrorCode::FrameNotFoundByUri);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_delete_frame_with_reason() {
+        let path = TempPath::new("delete_reason.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Document removed for retention."]);
+
+        let reason = CString::new("retention policy").unwrap();
+        let seq =
+            unsafe { memvid_delete_frame_with_reason(handle, 0, reason.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert!(seq > 0);
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        // The reason survives reopening
+        let handle = unsafe { memvid_open(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        let frame_ptr = unsafe { memvid_frame_by_id_ex(handle, 0, &mut error) };
+        assert!(!frame_ptr.is_null());
+        let frame_str = unsafe { std::ffi::CStr::from_ptr(frame_ptr) };
+        let json: serde_json::Value = serde_json::from_str(frame_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["id"], 0);
+        assert_eq!(json["delete_reason"], "retention policy");
+        assert!(json["deleted_at"].is_i64());
+
+        unsafe { memvid_string_free(frame_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_create_with_options() {
+        let path = TempPath::new("create_with_options.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let options = CString::new(r#"{"tier": "free"}"#).unwrap();
+        let handle =
+            unsafe { memvid_create_with_options(path_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_close(handle) };
+
+        // Empty options open like memvid_open
+        let empty = CString::new("").unwrap();
+        let handle =
+            unsafe { memvid_open_with_options(path_cstr.as_ptr(), empty.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        unsafe { memvid_close(handle) };
+
+        let options = CString::new(r#"{"encryption_key": "c2VjcmV0"}"#).unwrap();
+        let handle =
+            unsafe { memvid_open_with_options(path_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert!(handle.is_null());
+        assert_eq!(error.code, MemvidErrorCode::FeatureUnavailable);
+        unsafe { memvid_error_free(&mut error) };
+        let _ = std::fs::remove_file(&path);
+
+        let options = CString::new(r#"{"capacity_bytes": 1}"#).unwrap();
+        let handle =
+            unsafe { memvid_create_with_options(path_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert!(handle.is_null());
+        assert_eq!(error.code, MemvidErrorCode::TicketRequired);
+        assert!(!path.exists());
+        unsafe { memvid_error_free(&mut error) };
+    }
+
+    #[test]
+    fn test_flush() {
+        let path = TempPath::new("flush.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"Staged until the next commit.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        assert_eq!(unsafe { memvid_flush(handle, &mut error) }, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        assert_eq!(unsafe { memvid_commit(handle, &mut error) }, 1);
+
+        assert_eq!(unsafe { memvid_flush(std::ptr::null_mut(), &mut error) }, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidHandle);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_list_tags() {
+        let path = TempPath::new("list_tags.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"Tagged content.";
+        let options = CString::new(r#"{"labels": ["draft", "reviewed"]}"#).unwrap();
+        for _ in 0..2 {
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_delete_frame(handle, 1, &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let result_ptr = unsafe { memvid_list_tags(handle, &mut error) };
+        assert!(!result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result.to_str().unwrap()).unwrap();
+        // The deleted frame is not counted
+        assert_eq!(json["labels"]["draft"], 1);
+        assert_eq!(json["labels"]["reviewed"], 1);
+        assert!(json["tags"].is_object());
+        unsafe { memvid_string_free(result_ptr) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_open_readonly() {
+        let path = TempPath::new("open_readonly.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let content = b"Read-only handles still search.";
+        let handle = create_with(&path, &[content]);
+        unsafe { memvid_close(handle) };
+
+        let handle = unsafe { memvid_open_readonly(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let count = unsafe { memvid_frame_count(handle, &mut error) };
+        assert_eq!(count, 1);
+
+        let request = CString::new(r#"{"query": "search", "top_k": 5}"#).unwrap();
+        let result = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+        assert!(!result.is_null());
+        unsafe { memvid_string_free(result) };
+
+        let frame_id = unsafe { memvid_put_bytes(handle, content.as_ptr(), 1, &mut error) };
+        assert_eq!(frame_id, 0);
+        assert_eq!(error.code, MemvidErrorCode::ReadOnly);
+        unsafe { memvid_error_free(&mut error) };
+
+        assert_eq!(unsafe { memvid_commit(handle, &mut error) }, 0);
+        assert_eq!(error.code, MemvidErrorCode::ReadOnly);
+        unsafe { memvid_error_free(&mut error) };
+
+        assert_eq!(unsafe { memvid_delete_frame(handle, 0, &mut error) }, 0);
+        assert_eq!(error.code, MemvidErrorCode::ReadOnly);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_ping() {
+        let path = TempPath::new("ping.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert_eq!(unsafe { memvid_ping(handle, &mut error) }, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        // The file disappearing under the handle is detected (Windows
+        // cannot remove a file that is open).
+        #[cfg(unix)]
+        {
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(unsafe { memvid_ping(handle, &mut error) }, 0);
+            assert_eq!(error.code, MemvidErrorCode::Io);
+            unsafe { memvid_error_free(&mut error) };
+        }
+
+        assert_eq!(unsafe { memvid_ping(std::ptr::null_mut(), &mut error) }, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidHandle);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_current_sequence() {
+        let path = TempPath::new("current_sequence.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let initial = unsafe { memvid_current_sequence(handle, &mut error) };
+        assert_eq!(initial, 0);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let content = b"Sequence marker content.";
+        let id = unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        let after_put = unsafe { memvid_current_sequence(handle, &mut error) };
+        assert!(after_put > initial);
+
+        // Unchanged between polls
+        let polled = unsafe { memvid_current_sequence(handle, &mut error) };
+        assert_eq!(polled, after_put);
+
+        let seq = unsafe { memvid_delete_frame(handle, id, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let after_delete = unsafe { memvid_current_sequence(handle, &mut error) };
+        assert!(after_delete > after_put);
+        assert!(after_delete >= seq);
+
+        let closed = unsafe { memvid_current_sequence(std::ptr::null_mut(), &mut error) };
+        assert_eq!(closed, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidHandle);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_set_write_locked() {
+        let path = TempPath::new("write_locked.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content = b"Staged before locking.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+
+        // Locking commits what was staged
+        assert_eq!(unsafe { memvid_set_write_locked(handle, 1, &mut error) }, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(unsafe { memvid_set_write_locked(handle, 1, &mut error) }, 1);
+        assert_eq!(unsafe { memvid_frame_count(handle, &mut error) }, 1);
+
+        let id = unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        assert_eq!(id, 0);
+        assert_eq!(error.code, MemvidErrorCode::ReadOnly);
+        unsafe { memvid_error_free(&mut error) };
+        unsafe { memvid_delete_frame(handle, 0, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::ReadOnly);
+        unsafe { memvid_error_free(&mut error) };
+
+        assert_eq!(unsafe { memvid_set_write_locked(handle, 0, &mut error) }, 1);
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_close(handle) };
+
+        // Read-only handles cannot be locked
+        let reader = unsafe { memvid_open_readonly(path_cstr.as_ptr(), &mut error) };
+        assert_eq!(unsafe { memvid_set_write_locked(reader, 1, &mut error) }, 0);
+        assert_eq!(error.code, MemvidErrorCode::ReadOnly);
+        unsafe { memvid_error_free(&mut error) };
+        unsafe { memvid_close(reader) };
+    }
+
+    #[test]
+    fn test_refresh() {
+        let path = TempPath::new("refresh.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let writer = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let first = b"Committed before the reader opened.";
+        unsafe { memvid_put_bytes(writer, first.as_ptr(), first.len(), &mut error) };
+        unsafe { memvid_commit(writer, &mut error) };
+
+        let reader = unsafe { memvid_open_readonly(path_cstr.as_ptr(), &mut error) };
+        assert!(!reader.is_null());
+        let iter = unsafe { memvid_frames_iter_new(reader, 0, &mut error) };
+
+        let second = b"Committed while the reader was open.";
+        unsafe { memvid_put_bytes(writer, second.as_ptr(), second.len(), &mut error) };
+        unsafe { memvid_commit(writer, &mut error) };
+        assert_eq!(unsafe { memvid_frame_count(reader, &mut error) }, 1);
+
+        assert_eq!(unsafe { memvid_refresh(reader, &mut error) }, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(unsafe { memvid_frame_count(reader, &mut error) }, 2);
+
+        // Cursors from before the refresh are rejected.
+        let next = unsafe { memvid_frames_iter_next(iter, &mut error) };
+        assert!(next.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidCursor);
+        unsafe { memvid_error_free(&mut error) };
+        unsafe { memvid_frames_iter_free(iter) };
+
+        let request = CString::new(r#"{"query": "reader", "cursor": "0.stale"}"#).unwrap();
+        let result = unsafe { memvid_search(reader, request.as_ptr(), &mut error) };
+        assert!(result.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidCursor);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(reader) };
+        unsafe { memvid_close(writer) };
+    }
+
+    #[test]
+    fn test_restore_frame() {
+        let path = TempPath::new("restore_frame.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"Live data deleted by mistake.";
+        let options = CString::new(r#"{"uri": "mv2://live.txt", "title": "Live"}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        // Active frames cannot be restored
+        let result = unsafe { memvid_restore_frame(handle, 0, &mut error) };
+        assert_eq!(result, 0);
+        assert_eq!(error.code, MemvidErrorCode::FrameNotDeleted);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_delete_frame(handle, 0, &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let restored = unsafe { memvid_restore_frame(handle, 0, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_ne!(restored, 0);
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let frame_ptr = unsafe { memvid_frame_by_id_ex(handle, restored, &mut error) };
+        assert!(!frame_ptr.is_null());
+        let frame_str = unsafe { std::ffi::CStr::from_ptr(frame_ptr) };
+        let json: serde_json::Value = serde_json::from_str(frame_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["status"], "Active");
+        assert_eq!(json["uri"], "mv2://live.txt");
+        assert_eq!(json["title"], "Live");
+        assert_eq!(json["extra_metadata"]["restored_from"], "0");
+        unsafe { memvid_string_free(frame_ptr) };
+
+        let text_ptr = unsafe { memvid_frame_content(handle, restored, &mut error) };
+        let text = unsafe { std::ffi::CStr::from_ptr(text_ptr) };
+        assert_eq!(text.to_str().unwrap(), "Live data deleted by mistake.");
+        unsafe { memvid_string_free(text_ptr) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_update_frame() {
+        let path = TempPath::new("update_frame.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"Quarterly report draft.";
+        let options = CString::new(r#"{"uri": "mv2://report.txt", "title": "Draft"}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let update = CString::new(r#"{"title": "Final", "labels": ["reviewed"]}"#).unwrap();
+        let seq = unsafe { memvid_update_frame(handle, 0, update.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert!(seq > 0);
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let frame_ptr = unsafe { memvid_frame_by_id(handle, 0, &mut error) };
+        assert!(!frame_ptr.is_null());
+        let frame_str = unsafe { std::ffi::CStr::from_ptr(frame_ptr) };
+        let json: serde_json::Value = serde_json::from_str(frame_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["title"], "Final");
+        assert_eq!(json["uri"], "mv2://report.txt");
+        assert_eq!(json["labels"], serde_json::json!(["reviewed"]));
+        unsafe { memvid_string_free(frame_ptr) };
+
+        // Content fields are rejected
+        let update = CString::new(r#"{"search_text": "replacement"}"#).unwrap();
+        let seq = unsafe { memvid_update_frame(handle, 0, update.as_ptr(), &mut error) };
+        assert_eq!(seq, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frames_by_track() {
+        let path = TempPath::new("frames_by_track.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for (track, timestamp) in [("notes", 300), ("email", 100), ("notes", 200)] {
+            let options = format!(r#"{{"track": "{track}", "timestamp": {timestamp}}}"#);
+            let options = CString::new(options).unwrap();
+            let content = format!("{track} at {timestamp}");
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let track = CString::new("notes").unwrap();
+        let result_ptr = unsafe { memvid_frames_by_track(handle, track.as_ptr(), 0, &mut error) };
+        assert!(!result_ptr.is_null());
+        let result = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result.to_str().unwrap()).unwrap();
+        let ids: Vec<u64> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["id"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ids, vec![2, 0]);
+        unsafe { memvid_string_free(result_ptr) };
+
+        let empty = CString::new("").unwrap();
+        let result_ptr = unsafe { memvid_frames_by_track(handle, empty.as_ptr(), 0, &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frame_exists() {
+        let path = TempPath::new("frame_exists.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content = b"Referenced frame.";
+        let id = unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        assert_eq!(unsafe { memvid_frame_exists(handle, id, &mut error) }, 1);
+        let exists = unsafe { memvid_frame_exists(handle, id + 100, &mut error) };
+        assert_eq!(exists, 0);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        unsafe { memvid_delete_frame(handle, id, &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        assert_eq!(unsafe { memvid_frame_exists(handle, id, &mut error) }, 0);
+        let exists = unsafe { memvid_frame_exists_ex(handle, id, 1, &mut error) };
+        assert_eq!(exists, 1);
+
+        let exists = unsafe { memvid_frame_exists(std::ptr::null_mut(), id, &mut error) };
+        assert_eq!(exists, -1);
+        assert_eq!(error.code, MemvidErrorCode::InvalidHandle);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frames_by_ids() {
+        let path = TempPath::new("frames_by_ids.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let mut ids = Vec::new();
+        for title in ["Kept", "Deleted"] {
+            let options = CString::new(format!(r#"{{"title": "{title}"}}"#)).unwrap();
+            let content = format!("{title} frame.");
+            ids.push(unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            });
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_delete_frame(handle, ids[1], &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let lookup = [ids[1], 999, ids[0]];
+        let result_ptr =
+            unsafe { memvid_frames_by_ids(handle, lookup.as_ptr(), lookup.len(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result.to_str().unwrap()).unwrap();
+        let frames = json.as_array().unwrap();
+        assert_eq!(frames.len(), 3);
+        assert!(frames[0].is_null());
+        assert!(frames[1].is_null());
+        assert_eq!(frames[2]["title"], "Kept");
+        unsafe { memvid_string_free(result_ptr) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frames_in_range() {
+        let path = TempPath::new("frames_in_range.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        for timestamp in [300, 100, 200] {
+            let options = format!(r#"{{"title": "At {timestamp}", "timestamp": {timestamp}}}"#);
+            let options = CString::new(options).unwrap();
+            let content = format!("Entry at {timestamp}");
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let result_ptr = unsafe { memvid_frames_in_range(handle, 150, 300, 0, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result.to_str().unwrap()).unwrap();
+        let titles: Vec<&str> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, vec!["At 200", "At 300"]);
+        unsafe { memvid_string_free(result_ptr) };
+
+        let result_ptr = unsafe { memvid_frames_in_range(handle, 400, 500, 0, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        assert_eq!(result.to_str().unwrap(), "[]");
+        unsafe { memvid_string_free(result_ptr) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_export_jsonl() {
+        let path = TempPath::new("export_jsonl.mv2");
+        let out = TempPath::new("export_jsonl.jsonl");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+        let out_cstr = CString::new(out.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for content in [&b"Kept frame."[..], b"Deleted frame."] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_delete_frame(handle, 1, &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let written =
+            unsafe { memvid_export_jsonl(handle, out_cstr.as_ptr(), std::ptr::null(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(written, 1);
+        let dump = std::fs::read_to_string(&out).unwrap();
+        let line: serde_json::Value = serde_json::from_str(dump.lines().next().unwrap()).unwrap();
+        assert_eq!(line["id"], 0);
+        assert!(line["text"].as_str().unwrap().contains("Kept frame."));
+        assert!(line.get("payload_base64").is_none());
+
+        let options =
+            CString::new(r#"{"include_deleted": true, "include_payload": true}"#).unwrap();
+        let written =
+            unsafe { memvid_export_jsonl(handle, out_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert_eq!(written, 2);
+        let dump = std::fs::read_to_string(&out).unwrap();
+        assert!(dump.lines().all(|l| l.contains("payload_base64")));
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_import_jsonl() {
+        let path = TempPath::new("import_jsonl.mv2");
+        let input = TempPath::new("import_jsonl.jsonl");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+        let input_cstr = CString::new(input.to_str().unwrap()).unwrap();
+        let lines = [
+            r#"{"id": 7, "status": "Active", "uri": "mv2://a", "text": "Imported text."}"#,
+            "not json",
+            r#"{"status": "Deleted", "text": "Deleted text."}"#,
+            r#"{"payload_base64": "UGF5bG9hZCB0ZXh0Lg==", "labels": ["l1"]}"#,
+        ];
+        std::fs::write(&input, lines.join("\n")).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let options = CString::new(r#"{"strict": true}"#).unwrap();
+        let imported = unsafe {
+            memvid_import_jsonl(handle, input_cstr.as_ptr(), options.as_ptr(), &mut error)
+        };
+        assert_eq!(imported, 0);
+        assert_eq!(error.code, MemvidErrorCode::JsonParse);
+        unsafe { memvid_error_free(&mut error) };
+
+        let imported = unsafe {
+            memvid_import_jsonl(handle, input_cstr.as_ptr(), std::ptr::null(), &mut error)
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(imported, 2);
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let uri = CString::new("mv2://a").unwrap();
+        let frame_ptr = unsafe { memvid_frame_by_uri(handle, uri.as_ptr(), &mut error) };
+        assert!(!frame_ptr.is_null());
+        unsafe { memvid_string_free(frame_ptr) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frame_parent_children() {
+        let path = TempPath::new("frame_parent_children.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"A short root document."]);
+
+        let parent_ptr = unsafe { memvid_frame_parent(handle, 0, &mut error) };
+        assert!(parent_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let children_ptr = unsafe { memvid_frame_children(handle, 0, &mut error) };
+        assert!(!children_ptr.is_null());
+        let children = unsafe { std::ffi::CStr::from_ptr(children_ptr) };
+        assert_eq!(children.to_str().unwrap(), "[]");
+        unsafe { memvid_string_free(children_ptr) };
+
+        let children_ptr = unsafe { memvid_frame_children(handle, 99, &mut error) };
+        assert!(children_ptr.is_null());
+        assert_ne!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_frames_iter() {
+        let path = TempPath::new("frames_iter.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for content in [&b"First frame."[..], b"Second frame.", b"Third frame."] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_delete_frame(handle, 1, &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let collect_ids = |include_deleted: i32| {
+            let mut error = MemvidError::ok();
+            let iter = unsafe { memvid_frames_iter_new(handle, include_deleted, &mut error) };
+            assert!(!iter.is_null());
+            let mut ids = Vec::new();
+            loop {
+                let frame_ptr = unsafe { memvid_frames_iter_next(iter, &mut error) };
+                assert_eq!(error.code, MemvidErrorCode::Ok);
+                if frame_ptr.is_null() {
+                    break;
+                }
+                let frame_str = unsafe { std::ffi::CStr::from_ptr(frame_ptr) };
+                let json: serde_json::Value =
+                    serde_json::from_str(frame_str.to_str().unwrap()).unwrap();
+                ids.push(json["id"].as_u64().unwrap());
+                unsafe { memvid_string_free(frame_ptr) };
+            }
+            unsafe { memvid_frames_iter_free(iter) };
+            ids
+        };
+
+        // Tombstoned frames are skipped unless requested
+        assert_eq!(collect_ids(0), vec![0, 2]);
+        assert_eq!(collect_ids(1), vec![0, 1, 2]);
+
+        // Vacuum drops the tombstone but keeps IDs, leaving a gap
+        assert_eq!(unsafe { memvid_vacuum(handle, &mut error) }, 1);
+        assert_eq!(collect_ids(0), vec![0, 2]);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_timeline() {
+        let path = TempPath::new("timeline.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Put multiple documents
+        let content1 = b"First document in timeline.";
+        let content2 = b"Second document in timeline.";
+        let content3 = b"Third document in timeline.";
+        unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
+        unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
+        unsafe { memvid_put_bytes(handle, content3.as_ptr(), content3.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        // Query timeline with default options
+        let timeline_ptr = unsafe { memvid_timeline(handle, std::ptr::null(), &mut error) };
+        assert!(!timeline_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let timeline_str = unsafe { std::ffi::CStr::from_ptr(timeline_ptr) };
+        let json = timeline_str.to_str().unwrap();
+        assert!(json.contains("\"entries\""));
+        assert!(json.contains("\"count\":3"));
+
+        unsafe { memvid_string_free(timeline_ptr) };
+
+        // Query with limit
+        let query = CString::new(r#"{"limit": 2}"#).unwrap();
+        let timeline_ptr = unsafe { memvid_timeline(handle, query.as_ptr(), &mut error) };
+        assert!(!timeline_ptr.is_null());
+
+        let timeline_str = unsafe { std::ffi::CStr::from_ptr(timeline_ptr) };
+        let json = timeline_str.to_str().unwrap();
+        assert!(json.contains("\"count\":2"));
 
         unsafe { memvid_string_free(timeline_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_timeline_cursor() {
+        let path = TempPath::new("timeline_cursor.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        for content in [&b"Page one."[..], b"Page two.", b"Page three."] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        for reverse in [false, true] {
+            let mut seen = Vec::new();
+            let mut cursor = serde_json::Value::Null;
+            loop {
+                let query = serde_json::json!({"limit": 2, "reverse": reverse, "cursor": cursor});
+                let query = CString::new(query.to_string()).unwrap();
+                let ptr = unsafe { memvid_timeline(handle, query.as_ptr(), &mut error) };
+                assert_eq!(error.code, MemvidErrorCode::Ok);
+                let page_str = unsafe { std::ffi::CStr::from_ptr(ptr) };
+                let page: serde_json::Value =
+                    serde_json::from_str(page_str.to_str().unwrap()).unwrap();
+                unsafe { memvid_string_free(ptr) };
+
+                for entry in page["entries"].as_array().unwrap() {
+                    seen.push(entry["frame_id"].as_u64().unwrap());
+                }
+                cursor = page["next_cursor"].clone();
+                if cursor.is_null() {
+                    break;
+                }
+            }
+            let mut expected = vec![0, 1, 2];
+            if reverse {
+                expected.reverse();
+            }
+            assert_eq!(seen, expected);
+        }
+
+        let query = CString::new(r#"{"limit": 2, "cursor": "bogus"}"#).unwrap();
+        let ptr = unsafe { memvid_timeline(handle, query.as_ptr(), &mut error) };
+        assert!(ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_timeline_preview_chars() {
+        let path = TempPath::new("timeline_preview.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content = "記憶の断片を保存する".as_bytes();
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let query = CString::new(r#"{"preview_chars": 4}"#).unwrap();
+        let ptr = unsafe { memvid_timeline(handle, query.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let json_str = unsafe { std::ffi::CStr::from_ptr(ptr) };
+        let json: serde_json::Value = serde_json::from_str(json_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["entries"][0]["preview"], "記憶の断");
+        unsafe { memvid_string_free(ptr) };
+
+        // Longer than the text: the whole text
+        let query = CString::new(r#"{"preview_chars": 1000}"#).unwrap();
+        let ptr = unsafe { memvid_timeline(handle, query.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let json_str = unsafe { std::ffi::CStr::from_ptr(ptr) };
+        let json: serde_json::Value = serde_json::from_str(json_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["entries"][0]["preview"], "記憶の断片を保存する");
+        unsafe { memvid_string_free(ptr) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_merge() {
+        let dest_path = TempPath::new("merge_dest.mv2");
+        let src_path = TempPath::new("merge_src.mv2");
+        let dest_cstr = CString::new(dest_path.to_str().unwrap()).unwrap();
+        let src_cstr = CString::new(src_path.to_str().unwrap()).unwrap();
+
+        let put = |path: &CString, items: &[(&str, &str)]| {
+            let mut error = MemvidError::ok();
+            let handle = unsafe { memvid_create(path.as_ptr(), &mut error) };
+            for (uri, text) in items {
+                let options = CString::new(format!(r#"{{"uri": "{uri}"}}"#)).unwrap();
+                unsafe {
+                    memvid_put_bytes_with_options(
+                        handle,
+                        text.as_ptr(),
+                        text.len(),
+                        options.as_ptr(),
+                        &mut error,
+                    )
+                };
+            }
+            unsafe { memvid_commit(handle, &mut error) };
+            unsafe { memvid_close(handle) };
+        };
+        put(&dest_cstr, &[("mv2://a", "Original alpha.")]);
+        put(
+            &src_cstr,
+            &[("mv2://a", "Replacement alpha."), ("mv2://b", "Bravo.")],
+        );
+
+        let mut error = MemvidError::ok();
+        let options = CString::new(r#"{"on_uri_conflict": "skip"}"#).unwrap();
+        let merged = unsafe {
+            memvid_merge(
+                dest_cstr.as_ptr(),
+                src_cstr.as_ptr(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(merged, 1);
+
+        let options = CString::new(r#"{"on_uri_conflict": "replace"}"#).unwrap();
+        let merged = unsafe {
+            memvid_merge(
+                dest_cstr.as_ptr(),
+                src_cstr.as_ptr(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(merged, 2);
+
+        let handle = unsafe { memvid_open(dest_cstr.as_ptr(), &mut error) };
+        let h = unsafe { MemvidHandle::from_ptr_mut(handle) }.unwrap();
+        let active: Vec<u64> = h
+            .frames()
+            .filter(|f| matches!(f.status, memvid_core::types::FrameStatus::Active))
+            .filter(|f| f.uri.as_deref() == Some("mv2://a") && f.parent_id.is_none())
+            .map(|f| f.id)
+            .collect();
+        assert_eq!(active.len(), 1);
+        let text = h.as_mut().frame_text_by_id(active[0]).unwrap();
+        assert_eq!(text, "Replacement alpha.");
+        unsafe { memvid_close(handle) };
+
+        let merged = unsafe {
+            memvid_merge(
+                dest_cstr.as_ptr(),
+                dest_cstr.as_ptr(),
+                std::ptr::null(),
+                &mut error,
+            )
+        };
+        assert_eq!(merged, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+    }
+
+    #[test]
+    fn test_verify() {
+        let path = TempPath::new("verify.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+
+        // Create a valid memory file
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        let content = b"Content for verification test.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        // Verify the file (shallow)
+        let report_ptr = unsafe { memvid_verify(path_cstr.as_ptr(), 0, &mut error) };
+        assert!(!report_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json = report_str.to_str().unwrap();
+        assert!(json.contains("\"overall_status\":\"passed\""));
+        assert!(json.contains("\"checks\""));
+
+        unsafe { memvid_string_free(report_ptr) };
+
+        // Verify the file (deep)
+        let report_ptr = unsafe { memvid_verify(path_cstr.as_ptr(), 1, &mut error) };
+        assert!(!report_ptr.is_null());
+
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json = report_str.to_str().unwrap();
+        assert!(json.contains("\"overall_status\":\"passed\""));
+
+        unsafe { memvid_string_free(report_ptr) };
+
+        // Verify the file (quick, header and manifest only)
+        let report_ptr = unsafe { memvid_verify(path_cstr.as_ptr(), 2, &mut error) };
+        assert!(!report_ptr.is_null());
+
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json: serde_json::Value = serde_json::from_str(report_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["overall_status"], "passed");
+        let names: Vec<&str> = json["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["HeaderMagic", "HeaderVersion", "ManifestChecksum"]);
+
+        unsafe { memvid_string_free(report_ptr) };
+
+        // A truncated commit footer fails the manifest check
+        let len = std::fs::metadata(&path).unwrap().len();
+        let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(len - 1).unwrap();
+        drop(file);
+
+        let report_ptr = unsafe { memvid_verify(path_cstr.as_ptr(), 2, &mut error) };
+        assert!(!report_ptr.is_null());
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json: serde_json::Value = serde_json::from_str(report_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["overall_status"], "failed");
+        assert_eq!(json["checks"][2]["status"], "failed");
+
+        unsafe { memvid_string_free(report_ptr) };
+    }
+
+    #[test]
+    fn test_create_from_template() {
+        let template = TempPath::new("template_src.mv2");
+        let path = TempPath::new("template_dst.mv2");
+        let template_cstr = CString::new(template.to_str().unwrap()).unwrap();
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(template_cstr.as_ptr(), &mut error) };
+        let content = b"Template content that must not be copied.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        let handle = unsafe {
+            memvid_create_from_template(path_cstr.as_ptr(), template_cstr.as_ptr(), &mut error)
+        };
+        assert!(!handle.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(unsafe { memvid_frame_count(handle, &mut error) }, 0);
+        unsafe { memvid_close(handle) };
+
+        // Unreadable template
+        let missing = CString::new("/nonexistent/template.mv2").unwrap();
+        let other = TempPath::new("template_missing.mv2");
+        let other_cstr = CString::new(other.to_str().unwrap()).unwrap();
+        let handle = unsafe {
+            memvid_create_from_template(other_cstr.as_ptr(), missing.as_ptr(), &mut error)
+        };
+        assert!(handle.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Io);
+        assert!(!other.exists());
+        unsafe { memvid_error_free(&mut error) };
+    }
+
+    #[test]
+    fn test_format_info() {
+        let path = TempPath::new("format_info.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        let info_ptr = unsafe { memvid_format_info(path_cstr.as_ptr(), &mut error) };
+        assert!(!info_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let info_str = unsafe { std::ffi::CStr::from_ptr(info_ptr) };
+        let json = info_str.to_str().unwrap();
+        assert!(json.contains("\"format_version\""));
+        assert!(json.contains("\"min_reader_version\""));
+        assert!(json.contains("\"compatible\":true"));
+        unsafe { memvid_string_free(info_ptr) };
+
+        // A header core rejects is reported, not guessed at
+        std::fs::write(&path, vec![0xabu8; 8192]).unwrap();
+        let info_ptr = unsafe { memvid_format_info(path_cstr.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let info_str = unsafe { std::ffi::CStr::from_ptr(info_ptr) };
+        let json: serde_json::Value = serde_json::from_str(info_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["compatible"], false);
+        assert_eq!(json["format_version"], "unknown");
+        unsafe { memvid_string_free(info_ptr) };
+    }
+
+    #[test]
+    fn test_inspect() {
+        let path = TempPath::new("inspect.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Inspected without a handle."]);
+        unsafe { memvid_close(handle) };
+
+        let inspect = |path: &CString| -> serde_json::Value {
+            let mut error = MemvidError::ok();
+            let info_ptr = unsafe { memvid_inspect(path.as_ptr(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let info_str = unsafe { std::ffi::CStr::from_ptr(info_ptr) };
+            let json = serde_json::from_str(info_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(info_ptr) };
+            json
+        };
+
+        let json = inspect(&path_cstr);
+        assert_eq!(json["encrypted"], false);
+        assert_eq!(json["header"]["compatible"], true);
+        assert_eq!(json["manifest"]["frame_count"], 1);
+        assert_eq!(json["errors"], serde_json::json!([]));
+
+        // A bad header is reported, and the manifest is not read
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[..4].copy_from_slice(b"XXXX");
+        std::fs::write(&path, bytes).unwrap();
+        let json = inspect(&path_cstr);
+        assert_eq!(json["header"]["magic_ok"], false);
+        assert!(json["manifest"].is_null());
+        assert_eq!(json["errors"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_ask() {
+        let path = TempPath::new("ask.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Add some content to search
+        let content1 = b"The capital of France is Paris. It is known for the Eiffel Tower.";
+        let content2 = b"Berlin is the capital of Germany. It has a famous wall.";
+        let content3 = b"Tokyo is the capital of Japan. It is a very large city.";
+        unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
+        unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
+        unsafe { memvid_put_bytes(handle, content3.as_ptr(), content3.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        // Ask a question (context_only mode - no LLM synthesis)
+        let ask_json =
+            CString::new(r#"{"question": "What is the capital of France?", "top_k": 5}"#).unwrap();
+        let result_ptr = unsafe { memvid_ask(handle, ask_json.as_ptr(), &mut error) };
+
+        if result_ptr.is_null() {
+            let msg = if !error.message.is_null() {
+                unsafe { std::ffi::CStr::from_ptr(error.message) }
+                    .to_str()
+                    .unwrap_or("unknown")
+            } else {
+                "no message"
+            };
+            panic!("memvid_ask failed: {:?} - {}", error.code, msg);
+        }
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json = result_str.to_str().unwrap();
+
+        // Verify response structure
+        assert!(json.contains("\"question\""));
+        assert!(json.contains("\"context_only\":true"));
+        assert!(json.contains("\"retrieval\""));
+        assert!(json.contains("\"stats\""));
+        assert!(json.contains("\"context_fragments\""));
+
+        // A retriever other than the requested hybrid one is explained
+        let json: serde_json::Value = serde_json::from_str(json).unwrap();
+        let reason = json["retriever_reason"].as_str();
+        match json["retriever"].as_str().unwrap() {
+            "hybrid" => assert!(reason.is_none()),
+            "timeline_fallback" => assert!(reason.unwrap().contains("timeline")),
+            _ => assert!(reason.unwrap().starts_with("hybrid mode requested")),
+        }
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_ask_order_fragments() {
+        let path = TempPath::new("ask_order.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content1 = b"Chapter one: the river story begins.";
+        let content2 = b"Chapter two: the river story river continues river.";
+        unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
+        unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let ask_json =
+            CString::new(r#"{"question": "river story", "order_fragments": "document"}"#).unwrap();
+        let result_ptr = unsafe { memvid_ask(handle, ask_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let fragments = json["context_fragments"].as_array().unwrap();
+        let ids: Vec<u64> = fragments
+            .iter()
+            .map(|f| f["frame_id"].as_u64().unwrap())
+            .collect();
+        assert!(ids.windows(2).all(|w| w[0] <= w[1]));
+
+        // Citations follow the fragment order
+        let citations = json["citations"].as_array().unwrap();
+        assert_eq!(citations.len(), fragments.len());
+        for (i, (c, f)) in citations.iter().zip(fragments).enumerate() {
+            assert_eq!(c["index"], i + 1);
+            assert_eq!(c["frame_id"], f["frame_id"]);
+        }
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_ask_compact() {
+        let path = TempPath::new("ask_compact.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(
+            &path,
+            &[b"Compact answers for mobile clients over a bridge."],
+        );
+
+        for compact in [false, true] {
+            let ask_json = format!(r#"{{"question": "mobile clients", "compact": {compact}}}"#);
+            let ask_json = CString::new(ask_json).unwrap();
+            let result_ptr = unsafe { memvid_ask(handle, ask_json.as_ptr(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            let retrieval = &json["retrieval"];
+            assert_eq!(retrieval.get("hits").is_none(), compact);
+            assert!(retrieval["total_hits"].is_u64());
+            assert!(retrieval["elapsed_ms"].is_u64());
+            unsafe { memvid_string_free(result_ptr) };
+        }
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_ask_dedup_citations() {
+        let path = TempPath::new("ask_dedup.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content1 = b"The lighthouse keeper trims the lamp.";
+        let content2 = b"The lighthouse keeper climbs the lighthouse stairs.";
+        let opts = CString::new(r#"{"uri": "mv2://keeper.txt"}"#).unwrap();
+        for content in [&content1[..], &content2[..]] {
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    opts.as_ptr(),
+                    &mut error,
+                )
+            };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let ask_json =
+            CString::new(r#"{"question": "lighthouse keeper", "dedup_citations": true}"#).unwrap();
+        let result_ptr = unsafe { memvid_ask(handle, ask_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let citations = json["citations"].as_array().unwrap();
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0]["index"], 1);
+        assert_eq!(citations[0]["uri"], "mv2://keeper.txt");
+        // Fragments stay granular
+        let fragments = json["context_fragments"].as_array().unwrap();
+        assert_eq!(fragments.len(), 2);
+
+        let mut ids: Vec<u64> = fragments
+            .iter()
+            .map(|f| f["frame_id"].as_u64().unwrap())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        let source_ids: Vec<u64> = json["source_frame_ids"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_u64().unwrap())
+            .collect();
+        assert_eq!(source_ids, ids);
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    extern "C" fn reverse_reranker(
+        _query: *const c_char,
+        candidates_json: *const c_char,
+        _user_data: *mut std::ffi::c_void,
+    ) -> *mut c_char {
+        let candidates = unsafe { std::ffi::CStr::from_ptr(candidates_json) };
+        let parsed: Vec<serde_json::Value> =
+            serde_json::from_str(candidates.to_str().unwrap()).unwrap();
+        let order: Vec<serde_json::Value> = (0..parsed.len())
+            .rev()
+            .map(|i| serde_json::json!({"index": i, "score": 1.0 / (i as f32 + 1.0)}))
+            .collect();
+        let out = CString::new(serde_json::to_string(&order).unwrap()).unwrap();
+        unsafe { libc::strdup(out.as_ptr()) }
+    }
+
+    #[test]
+    fn test_ask_with_reranker() {
+        let path = TempPath::new("ask_rerank.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content1 = b"The capital of France is Paris.";
+        let content2 = b"Paris is also a city in Texas.";
+        unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
+        unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let ask_json =
+            CString::new(r#"{"question": "Paris", "top_k": 1, "rerank_candidates": 5}"#).unwrap();
+        let result_ptr = unsafe {
+            memvid_ask_with_reranker(
+                handle,
+                ask_json.as_ptr(),
+                Some(reverse_reranker),
+                std::ptr::null_mut(),
+                &mut error,
+            )
+        };
+        assert!(!result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert!(json["context_fragments"].as_array().unwrap().len() <= 1);
+        assert_eq!(
+            json["citations"].as_array().unwrap().len(),
+            json["context_fragments"].as_array().unwrap().len()
+        );
+        let hits = json["retrieval"]["hits"].as_array().unwrap();
+        assert!(hits.len() <= 1);
+        if let (Some(hit), Some(fragment)) = (hits.first(), json["context_fragments"].get(0)) {
+            assert_eq!(hit["frame_id"], fragment["frame_id"]);
+            assert_eq!(json["retrieval"]["context"], hit["text"]);
+        }
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    extern "C" fn echo_synthesizer(
+        prompt: *const c_char,
+        _user_data: *mut std::ffi::c_void,
+    ) -> *mut c_char {
+        let prompt = unsafe { std::ffi::CStr::from_ptr(prompt) };
+        assert!(prompt.to_str().unwrap().contains("Question: capital"));
+        unsafe { libc::strdup(c"Paris [1]".as_ptr()) }
+    }
+
+    #[test]
+    fn test_ask_with_synthesis() {
+        let path = TempPath::new("ask_synthesis.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"The capital of France is Paris."]);
+
+        let ask_json = CString::new(r#"{"question": "capital"}"#).unwrap();
+        let result_ptr = unsafe {
+            memvid_ask_with_synthesis(
+                handle,
+                ask_json.as_ptr(),
+                Some(echo_synthesizer),
+                std::ptr::null_mut(),
+                &mut error,
+            )
+        };
+        assert!(!result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["answer"], "Paris [1]");
+        assert_eq!(json["context_only"], false);
+        assert_eq!(
+            json["citations"].as_array().unwrap().len(),
+            json["context_fragments"].as_array().unwrap().len()
+        );
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_doctor() {
+        let path = TempPath::new("doctor.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+
+        // Create a valid memory file
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        let content = b"Content for doctor test.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        // Run doctor (should report clean)
+        let report_ptr = unsafe { memvid_doctor(path_cstr.as_ptr(), std::ptr::null(), &mut error) };
+        assert!(!report_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json = report_str.to_str().unwrap();
+        assert!(json.contains("\"status\""));
+        assert!(json.contains("\"plan\""));
+        assert!(json.contains("\"metrics\""));
+
+        unsafe { memvid_string_free(report_ptr) };
+    }
+
+    #[test]
+    fn test_vacuum() {
+        let path = TempPath::new("vacuum.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let doomed = "Deleted soon. ".repeat(200);
+        let kept = b"Kept across vacuum.";
+        unsafe { memvid_put_bytes(handle, doomed.as_ptr(), doomed.len(), &mut error) };
+        let kept_id = unsafe { memvid_put_bytes(handle, kept.as_ptr(), kept.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_delete_frame(handle, 0, &mut error) };
+
+        let report_ptr = unsafe { memvid_vacuum_report(handle, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let report: serde_json::Value = serde_json::from_str(report_str.to_str().unwrap()).unwrap();
+        let before = report["bytes_before"].as_u64().unwrap();
+        assert!(before >= report["bytes_after"].as_u64().unwrap());
+        unsafe { memvid_string_free(report_ptr) };
+
+        // The handle stays usable and the kept frame keeps its ID.
+        let content = unsafe { memvid_frame_content(handle, kept_id, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let content_str = unsafe { std::ffi::CStr::from_ptr(content) };
+        assert_eq!(content_str.to_bytes(), kept);
+        unsafe { memvid_string_free(content) };
+        assert_eq!(unsafe { memvid_vacuum(handle, &mut error) }, 1);
+        assert!(!Path::new(&format!("{}.vacuum", path.display())).exists());
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_doctor_cancellable() {
+        let path = TempPath::new("doctor_cancellable.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        unsafe { memvid_close(handle) };
+
+        let cancel: u8 = 1;
+        let report_ptr = unsafe {
+            memvid_doctor_cancellable(path_cstr.as_ptr(), std::ptr::null(), &cancel, &mut error)
+        };
+        assert!(report_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Cancelled);
+        unsafe { memvid_error_free(&mut error) };
+
+        let cancel: u8 = 0;
+        let report_ptr = unsafe {
+            memvid_doctor_cancellable(path_cstr.as_ptr(), std::ptr::null(), &cancel, &mut error)
+        };
+        assert!(!report_ptr.is_null());
+        let report = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json: serde_json::Value = serde_json::from_str(report.to_str().unwrap()).unwrap();
+        assert_eq!(json["steps"][0]["step"], "diagnose");
+
+        unsafe { memvid_string_free(report_ptr) };
+    }
+
+    #[test]
+    fn test_doctor_plan() {
+        let path = TempPath::new("doctor_plan.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+
+        // Create a valid memory file
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        let content = b"Content for doctor plan test.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        // Get doctor plan
+        let plan_ptr =
+            unsafe { memvid_doctor_plan(path_cstr.as_ptr(), std::ptr::null(), &mut error) };
+        assert!(!plan_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let plan_str = unsafe { std::ffi::CStr::from_ptr(plan_ptr) };
+        let json = plan_str.to_str().unwrap();
+        assert!(json.contains("\"version\""));
+        assert!(json.contains("\"file_path\""));
+        assert!(json.contains("\"phases\""));
+
+        unsafe { memvid_string_free(plan_ptr) };
+    }
+
+    #[test]
+    fn test_doctor_finding_codes() {
+        let path = TempPath::new("doctor_finding_codes.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Content for doctor finding codes."]);
+        unsafe { memvid_delete_frame(handle, 0, &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        let plan_ptr =
+            unsafe { memvid_doctor_plan(path_cstr.as_ptr(), std::ptr::null(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let plan_str = unsafe { std::ffi::CStr::from_ptr(plan_ptr) };
+        let plan: serde_json::Value = serde_json::from_str(plan_str.to_str().unwrap()).unwrap();
+        let findings = plan["findings"].as_array().cloned().unwrap_or_default();
+        for finding in &findings {
+            let code = finding["finding_code"].as_str().unwrap();
+            assert!(!code.is_empty());
+            assert_eq!(code, code.to_uppercase());
+        }
+
+        // The plan, finding codes included, is accepted back as is.
+        let report_ptr = unsafe { memvid_doctor_apply(path_cstr.as_ptr(), plan_ptr, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_string_free(report_ptr) };
+        unsafe { memvid_string_free(plan_ptr) };
+    }
+
+    // ==========================================================================
+    // Edge Case Tests
+    // ==========================================================================
+
+    #[test]
+    fn test_rebuild_index() {
+        let path = TempPath::new("rebuild_index.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Content for targeted rebuild."]);
+        unsafe { memvid_close(handle) };
+
+        let index = CString::new("time").unwrap();
+        let report_ptr =
+            unsafe { memvid_rebuild_index(path_cstr.as_ptr(), index.as_ptr(), &mut error) };
+        assert!(!report_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json: serde_json::Value = serde_json::from_str(report_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["index"], "time");
+        assert!(json["doctor"]["status"].is_string());
+        for check in json["verification"]["checks"].as_array().unwrap() {
+            assert!(check["name"].as_str().unwrap().starts_with("TimeIndex"));
+        }
+        unsafe { memvid_string_free(report_ptr) };
+
+        // Unknown index names are rejected
+        let index = CString::new("bogus").unwrap();
+        let report_ptr =
+            unsafe { memvid_rebuild_index(path_cstr.as_ptr(), index.as_ptr(), &mut error) };
+        assert!(report_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+    }
+
+    #[test]
+    fn test_empty_content() {
+        let path = TempPath::new("empty_content.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Put empty content
+        let content = b"";
+        let frame_id =
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        // Should succeed even with empty content
+        assert!(frame_id > 0 || error.code == MemvidErrorCode::Ok);
+
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_unicode_content() {
+        let path = TempPath::new("unicode.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Put unicode content
+        let content = "Hello 世界! 🎉 Привет мир! مرحبا بالعالم";
+        let content_bytes = content.as_bytes();
+        let _frame_id = unsafe {
+            memvid_put_bytes(handle, content_bytes.as_ptr(), content_bytes.len(), &mut error)
+        };
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+
+        // Reopen and verify content
+        let handle = unsafe { memvid_open(path_cstr.as_ptr(), &mut error) };
+        let content_ptr = unsafe { memvid_frame_content(handle, 0, &mut error) };
+        assert!(!content_ptr.is_null());
+
+        let content_str = unsafe { std::ffi::CStr::from_ptr(content_ptr) };
+        let retrieved = content_str.to_str().unwrap();
+        assert!(retrieved.contains("世界"));
+        assert!(retrieved.contains("🎉"));
+
+        unsafe { memvid_string_free(content_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_field_boosts() {
+        let path = TempPath::new("search_boosts.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let titled = b"A short guide that mentions rust once.";
+        let options = CString::new(r#"{"title": "Rust Guide", "uri": "test://titled"}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                titled.as_ptr(),
+                titled.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        let body = b"Rust rust rust: a body that repeats rust many times.";
+        let options = CString::new(r#"{"uri": "test://body"}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                body.as_ptr(),
+                body.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let search_json =
+            CString::new(r#"{"query": "rust", "top_k": 2, "field_boosts": {"title": 100.0}}"#)
+                .unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["hits"][0]["uri"], "test://titled");
+        assert_eq!(json["hits"][0]["rank"], 1);
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_boosted_paging() {
+        let path = TempPath::new("search_boosted_paging.mv2");
+        let contents: Vec<String> = (0..5).map(|i| format!("Paging note number {i}.")).collect();
+        let contents: Vec<&[u8]> = contents.iter().map(|c| c.as_bytes()).collect();
+        let handle = create_with(&path, &contents);
+        let mut error = MemvidError::ok();
+
+        // Every hit is returned exactly once across pages, and each page's
+        // context holds only that page's hits.
+        let mut seen = Vec::new();
+        let mut cursor = serde_json::Value::Null;
+        loop {
+            let request = serde_json::json!({
+                "query": "paging",
+                "top_k": 2,
+                "field_boosts": {"body": 2.0},
+                "cursor": cursor,
+            });
+            let request = CString::new(request.to_string()).unwrap();
+            let result_ptr = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let page: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(result_ptr) };
+
+            let hits = page["hits"].as_array().unwrap();
+            assert!(hits.len() <= 2);
+            let texts: Vec<&str> = hits.iter().map(|h| h["text"].as_str().unwrap()).collect();
+            assert_eq!(page["context"], texts.join("\n\n"));
+            seen.extend(hits.iter().map(|h| h["frame_id"].as_u64().unwrap()));
+            cursor = page["next_cursor"].clone();
+            if cursor.is_null() {
+                break;
+            }
+        }
+        seen.sort_unstable();
+        assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_into() {
+        let path = TempPath::new("search_into.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Reusable buffers avoid allocation churn."]);
+
+        let search_json = CString::new(r#"{"query": "allocation churn"}"#).unwrap();
+        let request = search_json.as_ptr();
+
+        // Size query
+        let mut needed = 0usize;
+        let null = std::ptr::null_mut();
+        let ok = unsafe { memvid_search_into(handle, request, null, 0, &mut needed, &mut error) };
+        assert_eq!(ok, 0);
+        assert_eq!(error.code, MemvidErrorCode::BufferTooSmall);
+        assert!(needed > 1);
+        unsafe { memvid_error_free(&mut error) };
+
+        let mut buf = vec![0 as c_char; needed];
+        let (out, out_len) = (buf.as_mut_ptr(), buf.len());
+        let ok =
+            unsafe { memvid_search_into(handle, request, out, out_len, &mut needed, &mut error) };
+        assert_eq!(ok, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let json = unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) };
+        let json: serde_json::Value = serde_json::from_str(json.to_str().unwrap()).unwrap();
+        assert_eq!(json["hits"].as_array().unwrap().len(), 1);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_bytes_compressed() {
+        let path = TempPath::new("search_bytes.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(
+            &path,
+            &[b"Compressed snippets keep mobile transfers small and fast."],
+        );
+
+        // memvid_search cannot return binary output
+        let search_json =
+            CString::new(r#"{"query": "compressed snippets", "compress_context": true}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+
+        let mut len = 0usize;
+        let buf =
+            unsafe { memvid_search_bytes(handle, search_json.as_ptr(), &mut len, &mut error) };
+        assert!(!buf.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let bytes = unsafe { std::slice::from_raw_parts(buf, len) };
+        let env_len = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        let envelope: serde_json::Value = serde_json::from_slice(&bytes[4..4 + env_len]).unwrap();
+        assert_eq!(envelope["payload"]["codec"], "zstd");
+
+        let decoded = zstd::bulk::decompress(
+            &bytes[4 + env_len..],
+            envelope["payload"]["uncompressed_len"].as_u64().unwrap() as usize,
+        )
+        .unwrap();
+        let hits = envelope["hits"].as_array().unwrap();
+        assert!(!hits.is_empty());
+        let range = &envelope["payload"]["texts"][0];
+        let start = range[0].as_u64().unwrap() as usize;
+        let end = start + range[1].as_u64().unwrap() as usize;
+        let text = std::str::from_utf8(&decoded[start..end]).unwrap();
+        assert!(text.contains("Compressed snippets"));
+
+        unsafe { memvid_bytes_free(buf, len) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_timeout() {
+        let path = TempPath::new("search_timeout.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Deadline checks run between search phases."]);
+
+        // A zero budget always expires before post-processing
+        let search_json = CString::new(
+            r#"{"query": "deadline", "top_k": 1, "timeout_ms": 0, "field_boosts": {"body": 2.0}}"#,
+        )
+        .unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["timed_out"], true);
+        assert!(json["hits"].as_array().unwrap().len() <= 1);
+        unsafe { memvid_string_free(result_ptr) };
+
+        // Without a budget the search completes normally
+        let search_json = CString::new(r#"{"query": "deadline"}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["timed_out"], false);
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_track_filters() {
+        let path = TempPath::new("search_tracks.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for (track, content) in [
+            ("email", "Quarterly budget review email"),
+            ("chat", "Budget chat with the team"),
+            ("logs", "Budget service log line"),
+        ] {
+            let options = CString::new(format!(r#"{{"track": "{track}"}}"#)).unwrap();
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let search_json =
+            CString::new(r#"{"query": "budget", "exclude_tracks": ["logs"]}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let hits = json["hits"].as_array().unwrap();
+        assert!(hits.iter().all(|h| h["track"] != "logs"));
+        assert_eq!(json["total_hits"].as_u64().unwrap() as usize, hits.len());
+        unsafe { memvid_string_free(result_ptr) };
+
+        let search_json =
+            CString::new(r#"{"query": "budget", "include_tracks": ["chat"]}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let hits = json["hits"].as_array().unwrap();
+        assert!(hits.iter().all(|h| h["track"] == "chat"));
+        unsafe { memvid_string_free(result_ptr) };
+
+        // Filters still apply when the deadline has passed
+        let search_json =
+            CString::new(r#"{"query": "budget", "include_tracks": ["chat"], "timeout_ms": 0}"#)
+                .unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["timed_out"], true);
+        let hits = json["hits"].as_array().unwrap();
+        assert!(hits.iter().all(|h| h["track"] == "chat"));
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_tag_label_filters() {
+        let path = TempPath::new("search_tags.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for (options, content) in [
+            (
+                r#"{"labels": ["reviewed"], "tags": {"topic": "rust"}}"#,
+                "Reviewed rust notes",
+            ),
+            (
+                r#"{"labels": ["draft"], "tags": {"topic": "rust"}}"#,
+                "Draft rust notes",
+            ),
+            (r#"{"labels": ["reviewed"]}"#, "Reviewed untagged notes"),
+        ] {
+            let options = CString::new(options).unwrap();
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let search_json = CString::new(
+            r#"{"query": "notes", "labels": ["reviewed"], "tags": {"topic": "rust"}}"#,
+        )
+        .unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let hits = json["hits"].as_array().unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["frame_id"], 0);
+        assert_eq!(json["context"], hits[0]["text"]);
+        unsafe { memvid_string_free(result_ptr) };
+
+        // The deadline skips optional work, never the filters
+        let search_json =
+            CString::new(r#"{"query": "notes", "labels": ["draft"], "timeout_ms": 0}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["timed_out"], true);
+        let hits = json["hits"].as_array().unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["frame_id"], 1);
+        assert_eq!(json["context"], hits[0]["text"]);
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_min_score() {
+        let path = TempPath::new("search_min_score.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for content in [&b"Lantern lantern lantern."[..], b"A lantern in the hall."] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let search_json = CString::new(r#"{"query": "lantern", "min_score": 1e30}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let hits = json["hits"].as_array().unwrap();
+        assert!(hits.iter().all(|h| h["score"].is_null()));
+        assert_eq!(json["total_hits"].as_u64().unwrap(), hits.len() as u64);
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_highlights() {
+        let path = TempPath::new("search_highlights.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content = "Die Laterne: a Lantern, then another lantern.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let search_json = CString::new(r#"{"query": "lantern"}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let hit = &json["hits"][0];
+        let text: Vec<char> = hit["text"].as_str().unwrap().chars().collect();
+        let highlights = hit["highlights"].as_array().unwrap();
+        assert_eq!(highlights.len(), 2);
+        for range in highlights {
+            let start = range[0].as_u64().unwrap() as usize;
+            let end = range[1].as_u64().unwrap() as usize;
+            let word: String = text[start..end].iter().collect();
+            assert_eq!(word.to_lowercase(), "lantern");
+        }
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_count_unfiltered() {
+        let path = TempPath::new("search_unfiltered.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for (uri, content) in [
+            ("mv2://notes/a.txt", "Harbor notes about the harbor"),
+            ("mv2://mail/b.txt", "Harbor mail"),
+            ("mv2://mail/c.txt", "More harbor mail"),
+        ] {
+            let options = CString::new(format!(r#"{{"uri": "{uri}"}}"#)).unwrap();
+            unsafe {
+                memvid_put_bytes_with_options(
+                    handle,
+                    content.as_ptr(),
+                    content.len(),
+                    options.as_ptr(),
+                    &mut error,
+                )
+            };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let search = |request: &str| -> serde_json::Value {
+            let search_json = CString::new(request).unwrap();
+            let mut error = MemvidError::ok();
+            let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+            assert!(!result_ptr.is_null());
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(result_ptr) };
+            json
+        };
+
+        let json =
+            search(r#"{"query": "harbor", "scope": "mv2://notes/", "count_unfiltered": true}"#);
+        assert_eq!(json["total_hits"], 1);
+        assert_eq!(json["unfiltered_total"], 3);
+
+        // Opt-in only
+        let json = search(r#"{"query": "harbor", "scope": "mv2://notes/"}"#);
+        assert!(json["unfiltered_total"].is_null());
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_shared_handle() {
+        let path = TempPath::new("shared_handle.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        unsafe { memvid_close(handle) };
+
+        let shared = unsafe { memvid_open_shared(path_cstr.as_ptr(), &mut error) };
+        assert!(!shared.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let content = b"Shared handles serialize writes.";
+        let options = CString::new(r#"{"uri": "mv2://shared/1.txt"}"#).unwrap();
+        unsafe {
+            memvid_shared_put_bytes_with_options(
+                shared,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(unsafe { memvid_shared_commit(shared, &mut error) }, 1);
+
+        // Readers and searchers from several threads at once
+        let addr = shared as usize;
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(move || {
+                    let shared = addr as *const MemvidSharedHandle;
+                    let mut error = MemvidError::ok();
+                    let count = unsafe { memvid_shared_frame_count(shared, &mut error) };
+                    assert_eq!(count, 1);
+
+                    let mut stats = MemvidStats::default();
+                    let ok = unsafe { memvid_shared_stats(shared, &mut stats, &mut error) };
+                    assert_eq!(ok, 1);
+
+                    let request = CString::new(r#"{"query": "serialize", "top_k": 5}"#).unwrap();
+                    let result =
+                        unsafe { memvid_shared_search(shared, request.as_ptr(), &mut error) };
+                    assert_eq!(error.code, MemvidErrorCode::Ok);
+                    assert!(!result.is_null());
+                    unsafe { memvid_string_free(result) };
+                });
+            }
+        });
+
+        // Null handle
+        let count = unsafe { memvid_shared_frame_count(std::ptr::null(), &mut error) };
+        assert_eq!(count, 0);
+        assert_eq!(error.code, MemvidErrorCode::InvalidHandle);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close_shared(shared) };
+        unsafe { memvid_close_shared(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_commit_with_progress() {
+        use std::os::raw::c_void;
+
+        extern "C" fn record(phase: u32, done: u64, total: u64, user_data: *mut c_void) -> i32 {
+            let calls = unsafe { &mut *(user_data as *mut Vec<(u32, u64, u64)>) };
+            calls.push((phase, done, total));
+            0
+        }
+
+        extern "C" fn cancel(_phase: u32, _done: u64, _total: u64, _user_data: *mut c_void) -> i32 {
+            1
+        }
+
+        extern "C" fn reenter(_phase: u32, _done: u64, _total: u64, user_data: *mut c_void) -> i32 {
+            let mut error = MemvidError::ok();
+            let count = unsafe { memvid_frame_count(user_data as *mut MemvidHandle, &mut error) };
+            assert_eq!(count, 0);
+            assert_eq!(error.code, MemvidErrorCode::InvalidHandle);
+            unsafe { memvid_error_free(&mut error) };
+            0
+        }
+
+        let path = TempPath::new("commit_progress.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"Progress reporting.";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+
+        // Cancelled before committing
+        let result = unsafe {
+            memvid_commit_with_progress(handle, Some(cancel), std::ptr::null_mut(), &mut error)
+        };
+        assert_eq!(result, 0);
+        assert_eq!(error.code, MemvidErrorCode::Cancelled);
+        unsafe { memvid_error_free(&mut error) };
+
+        let mut calls: Vec<(u32, u64, u64)> = Vec::new();
+        let user_data = &mut calls as *mut _ as *mut c_void;
+        let result =
+            unsafe { memvid_commit_with_progress(handle, Some(record), user_data, &mut error) };
+        assert_eq!(result, 1);
+        assert_eq!(calls, vec![(0, 0, 2), (1, 2, 2)]);
+
+        // The callback cannot re-enter the handle
+        let user_data = handle as *mut c_void;
+        let result =
+            unsafe { memvid_commit_with_progress(handle, Some(reenter), user_data, &mut error) };
+        assert_eq!(result, 1);
+        assert_eq!(unsafe { memvid_frame_count(handle, &mut error) }, 2);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_put_bytes_detailed() {
+        let path = TempPath::new("put_detailed.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"A short note that fits in one frame.";
+        let mut result_ptr = std::ptr::null_mut();
+        let frame_id = unsafe {
+            memvid_put_bytes_detailed(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                std::ptr::null(),
+                &mut result_ptr,
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert!(!result_ptr.is_null());
+
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["parent_id"], frame_id);
+        assert_eq!(json["chunk_count"], 0);
+        assert_eq!(json["chunk_ids"], serde_json::json!([]));
+        unsafe { memvid_string_free(result_ptr) };
+
+        // A long document is split, and every chunk points at its parent
+        let long = "Section on retention policy and audit scope. ".repeat(2000);
+        let parent_id = unsafe {
+            memvid_put_bytes_detailed(
+                handle,
+                long.as_ptr(),
+                long.len(),
+                std::ptr::null(),
+                &mut result_ptr,
+                &mut error,
+            )
+        };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let chunk_ids = json["chunk_ids"].as_array().unwrap();
+        assert!(chunk_ids.len() > 1);
+        assert_eq!(json["chunk_count"], chunk_ids.len());
+        for chunk_id in chunk_ids {
+            let frame_ptr =
+                unsafe { memvid_frame_by_id(handle, chunk_id.as_u64().unwrap(), &mut error) };
+            let frame_str = unsafe { std::ffi::CStr::from_ptr(frame_ptr) };
+            let frame: serde_json::Value =
+                serde_json::from_str(frame_str.to_str().unwrap()).unwrap();
+            assert_eq!(frame["parent_id"], parent_id);
+            unsafe { memvid_string_free(frame_ptr) };
+        }
+        unsafe { memvid_string_free(result_ptr) };
+
+        // Other staged changes are not committed behind the caller's back
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        let result = unsafe {
+            memvid_put_bytes_detailed(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                std::ptr::null(),
+                &mut result_ptr,
+                &mut error,
+            )
+        };
+        assert_eq!(result, 0);
+        assert_eq!(error.code, MemvidErrorCode::FeatureUnavailable);
+        unsafe { memvid_error_free(&mut error) };
 
-        // Query with limit
-        let query = CString::new(r#"{"limit": 2}"#).unwrap();
-        let timeline_ptr = unsafe { memvid_timeline(handle, query.as_ptr(), &mut error) };
-        assert!(!timeline_ptr.is_null());
+        // The result out-parameter is required
+        let result = unsafe {
+            memvid_put_bytes_detailed(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                &mut error,
+            )
+        };
+        assert_eq!(result, 0);
+        assert_eq!(error.code, MemvidErrorCode::NullPointer);
+        unsafe { memvid_error_free(&mut error) };
 
-        let timeline_str = unsafe { std::ffi::CStr::from_ptr(timeline_ptr) };
-        let json = timeline_str.to_str().unwrap();
-        assert!(json.contains("\"count\":2"));
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_estimate_put() {
+        let path = TempPath::new("estimate_put.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content = b"A short note that fits in one frame.";
+        let estimate = |options: *const c_char| -> serde_json::Value {
+            let mut error = MemvidError::ok();
+            let result_ptr = unsafe {
+                memvid_estimate_put(handle, content.as_ptr(), content.len(), options, &mut error)
+            };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(result_ptr) };
+            json
+        };
+
+        let json = estimate(std::ptr::null());
+        assert_eq!(json["estimated_frames"], 1);
+        assert!(json["estimated_stored_bytes"].as_u64().unwrap() > 0);
+        assert_eq!(json["would_exceed_capacity"], false);
+        assert_eq!(estimate(std::ptr::null()), json);
+
+        // Nothing was written next to the memory
+        assert_eq!(unsafe { memvid_frame_count(handle, &mut error) }, 0);
+        let dir = path.parent().unwrap();
+        let prefix = path.file_name().unwrap().to_str().unwrap();
+        let siblings = std::fs::read_dir(dir)
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_name().to_string_lossy().starts_with(prefix))
+            .count();
+        assert_eq!(siblings, 1);
+
+        // A key the handle has seen would be deduplicated
+        let options = CString::new(r#"{"idempotency_key": "note-1"}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(estimate(options.as_ptr())["estimated_frames"], 0);
 
-        unsafe { memvid_string_free(timeline_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn test_verify() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_verify.mv2");
+    fn test_commit_detailed() {
+        let path = TempPath::new("commit_detailed.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        for content in ["First staged note.", "Second staged note."] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
 
-        // Create a valid memory file
+        let mut result_ptr = std::ptr::null_mut();
+        let ok = unsafe { memvid_commit_detailed(handle, &mut result_ptr, &mut error) };
+        assert_eq!(ok, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["frames_added"], 2);
+        assert!(json["bytes_written"].is_u64());
+        assert!(json["wal_truncated"].is_boolean());
+        unsafe { memvid_string_free(result_ptr) };
+
+        let ok = unsafe { memvid_commit_detailed(handle, std::ptr::null_mut(), &mut error) };
+        assert_eq!(ok, 0);
+        assert_eq!(error.code, MemvidErrorCode::NullPointer);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_put_file() {
+        let path = TempPath::new("put_file.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+        let source = TempPath::new("put_file_source.txt");
+        std::fs::write(&source, "Ledger entries streamed from a file.").unwrap();
+        let source_cstr = CString::new(source.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
         assert!(!handle.is_null());
-        let content = b"Content for verification test.";
+
+        let options = CString::new(r#"{"uri": "mv2://ledger.txt"}"#).unwrap();
+        unsafe { memvid_put_file(handle, source_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let uri = CString::new("mv2://ledger.txt").unwrap();
+        let frame_ptr = unsafe { memvid_frame_by_uri(handle, uri.as_ptr(), &mut error) };
+        assert!(!frame_ptr.is_null());
+        unsafe { memvid_string_free(frame_ptr) };
+
+        // Missing file and NULL path
+        let missing = CString::new("/nonexistent/source.txt").unwrap();
+        unsafe { memvid_put_file(handle, missing.as_ptr(), std::ptr::null(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Io);
+        unsafe { memvid_error_free(&mut error) };
+        unsafe { memvid_put_file(handle, std::ptr::null(), std::ptr::null(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::NullPointer);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_put_batch() {
+        let path = TempPath::new("put_batch.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content1 = b"First batch item.";
+        let content2 = b"Second batch item.";
+        let options = CString::new(r#"{"uri": "mv2://batch/2.txt"}"#).unwrap();
+        let bad_options = CString::new("{not json").unwrap();
+        let items = [
+            MemvidPutItem {
+                data: content1.as_ptr(),
+                len: content1.len(),
+                options_json: std::ptr::null(),
+            },
+            MemvidPutItem {
+                data: content2.as_ptr(),
+                len: content2.len(),
+                options_json: options.as_ptr(),
+            },
+            MemvidPutItem {
+                data: content2.as_ptr(),
+                len: content2.len(),
+                options_json: bad_options.as_ptr(),
+            },
+        ];
+
+        // All good items
+        let mut count = 0;
+        let ids = unsafe { memvid_put_batch(handle, items.as_ptr(), 2, &mut count, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(count, 2);
+        assert!(!ids.is_null());
+        unsafe { memvid_u64_array_free(ids, count) };
+
+        // Stops at the failing item and reports what was put
+        let ids = unsafe { memvid_put_batch(handle, items.as_ptr(), 3, &mut count, &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::JsonParse);
+        assert_eq!(count, 2);
+        assert!(!ids.is_null());
+        unsafe { memvid_u64_array_free(ids, count) };
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_commit(handle, &mut error) };
+        assert_eq!(unsafe { memvid_frame_count(handle, &mut error) }, 4);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_indexing_paused() {
+        let path = TempPath::new("indexing_paused.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let search = |handle: *mut MemvidHandle| -> serde_json::Value {
+            let search_json = CString::new(r#"{"query": "meadow"}"#).unwrap();
+            let mut error = MemvidError::ok();
+            let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+            assert!(!result_ptr.is_null());
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(result_ptr) };
+            json
+        };
+
+        let ok = unsafe { memvid_set_indexing_paused(handle, 1, &mut error) };
+        assert_eq!(ok, 1);
+        for content in ["A quiet meadow", "Meadow flowers in spring"] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
+        let json = search(handle);
+        assert_eq!(json["index_stale"], true);
+        assert_eq!(json["hits"].as_array().unwrap().len(), 0);
+
+        // Resuming catches the index up
+        let ok = unsafe { memvid_set_indexing_paused(handle, 0, &mut error) };
+        assert_eq!(ok, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let json = search(handle);
+        assert_eq!(json["index_stale"], false);
+        assert_eq!(json["hits"].as_array().unwrap().len(), 2);
+
+        // Staged puts are reported without a pause too
+        let content = b"Meadow grass before a commit";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        assert_eq!(search(handle)["index_stale"], true);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_autocommit() {
+        let path = TempPath::new("autocommit.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let hits = |handle: *mut MemvidHandle| -> usize {
+            let search_json = CString::new(r#"{"query": "harbor"}"#).unwrap();
+            let mut error = MemvidError::ok();
+            let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(result_ptr) };
+            json["hits"].as_array().unwrap().len()
+        };
+        let put = |content: &str| {
+            let mut error = MemvidError::ok();
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+        };
+
+        assert_eq!(unsafe { memvid_set_autocommit(handle, 3, &mut error) }, 1);
+        put("Boats in the harbor");
+        put("Harbor lights at dusk");
+        assert_eq!(hits(handle), 0);
+        put("The harbor master's log");
+        assert_eq!(hits(handle), 3);
+
+        // 0 disables it again
+        assert_eq!(unsafe { memvid_set_autocommit(handle, 0, &mut error) }, 1);
+        for content in ["Harbor fog", "Harbor gulls", "Harbor tides"] {
+            put(content);
+        }
+        assert_eq!(hits(handle), 3);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_byte_offsets() {
+        let path = TempPath::new("search_byte_offsets.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        // Multi-byte characters make char and byte offsets diverge
+        let content = "Café über naïve résumé with a lantern inside.";
         unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
         unsafe { memvid_commit(handle, &mut error) };
+
+        let search_json = CString::new(r#"{"query": "lantern", "byte_offsets": true}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let hit = &json["hits"][0];
+
+        let offset = |v: &serde_json::Value| v.as_u64().unwrap() as usize;
+        let (start, end) = (offset(&hit["range"][0]), offset(&hit["range"][1]));
+        let (byte_start, byte_end) = (
+            offset(&hit["payload_range"][0]),
+            offset(&hit["payload_range"][1]),
+        );
+        let expected: String = content.chars().skip(start).take(end - start).collect();
+        assert_eq!(&content[byte_start..byte_end], expected);
+
+        unsafe { memvid_string_free(result_ptr) };
         unsafe { memvid_close(handle) };
+    }
 
-        // Verify the file (shallow)
-        let report_ptr = unsafe { memvid_verify(path_cstr.as_ptr(), 0, &mut error) };
-        assert!(!report_ptr.is_null());
+    #[test]
+    fn test_search_mode() {
+        let path = TempPath::new("search_mode.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Compass and map for the mountain trail."]);
+
+        // sem never falls back silently
+        let search_json = CString::new(r#"{"query": "compass", "mode": "sem"}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+        assert!(result_ptr.is_null());
+        let expected = if cfg!(feature = "vec") {
+            MemvidErrorCode::InvalidQuery
+        } else {
+            MemvidErrorCode::VecNotEnabled
+        };
+        assert_eq!(error.code, expected);
+        unsafe { memvid_error_free(&mut error) };
+
+        // hybrid without an embedding runs lexically and says so
+        if cfg!(feature = "vec") {
+            let search_json = CString::new(r#"{"query": "compass", "mode": "hybrid"}"#).unwrap();
+            let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+            assert!(!result_ptr.is_null());
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            assert_eq!(json["hits"].as_array().unwrap().len(), 1);
+            assert!(!json["engine"].as_str().unwrap().contains("Vector"));
+            unsafe { memvid_string_free(result_ptr) };
+        }
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_pinned_frames() {
+        let path = TempPath::new("search_pinned.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        for content in [
+            "Garden guide: garden soil, garden beds, garden tools.",
+            "Featured garden article.",
+            "Unrelated recipe for bread.",
+        ] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let hit_ids = |request: &str| -> Vec<u64> {
+            let mut error = MemvidError::ok();
+            let search_json = CString::new(request).unwrap();
+            let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
+            assert!(!result_ptr.is_null());
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(result_ptr) };
+            let hits = json["hits"].as_array().unwrap();
+            hits.iter().map(|h| h["frame_id"].as_u64().unwrap()).collect()
+        };
+
+        // A matching pinned frame moves to the top
+        let ids = hit_ids(r#"{"query": "garden", "pinned_frames": [1]}"#);
+        assert_eq!(ids.first(), Some(&1));
+
+        // A non-matching pinned frame is only shown with pin_unmatched
+        let ids = hit_ids(r#"{"query": "garden", "pinned_frames": [2]}"#);
+        assert!(!ids.contains(&2));
+        let ids = hit_ids(r#"{"query": "garden", "pinned_frames": [2], "pin_unmatched": true}"#);
+        assert_eq!(ids.first(), Some(&2));
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_diff() {
+        let path = TempPath::new("search_diff.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let content1 = b"Quarterly report for the audit team.";
+        let first =
+            unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        let content2 = b"Second quarterly report for the audit team.";
+        let second =
+            unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let request = format!(
+            r#"{{"query": "quarterly report", "top_k": 10, "as_of_a": {{"frame": {first}}}, "as_of_b": {{"frame": {second}}}}}"#
+        );
+        let request = CString::new(request).unwrap();
+        let result_ptr = unsafe { memvid_search_diff(handle, request.as_ptr(), &mut error) };
+        assert!(!result_ptr.is_null());
         assert_eq!(error.code, MemvidErrorCode::Ok);
 
-        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
-        let json = report_str.to_str().unwrap();
-        assert!(json.contains("\"overall_status\":\"passed\""));
-        assert!(json.contains("\"checks\""));
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert!(json["removed"].as_array().unwrap().is_empty());
+        assert!(json["added"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|h| h["frame_id"] != first));
+        unsafe { memvid_string_free(result_ptr) };
 
-        unsafe { memvid_string_free(report_ptr) };
+        // Filters apply to both snapshots
+        let request = format!(
+            r#"{{"query": "quarterly report", "include_tracks": ["none"], "as_of_a": {{"frame": {first}}}, "as_of_b": {{"frame": {second}}}}}"#
+        );
+        let request = CString::new(request).unwrap();
+        let result_ptr = unsafe { memvid_search_diff(handle, request.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        assert!(json["added"].as_array().unwrap().is_empty());
+        assert!(json["unchanged"].as_array().unwrap().is_empty());
+        unsafe { memvid_string_free(result_ptr) };
 
-        // Verify the file (deep)
-        let report_ptr = unsafe { memvid_verify(path_cstr.as_ptr(), 1, &mut error) };
-        assert!(!report_ptr.is_null());
+        // A diff is not paged
+        let request = format!(
+            r#"{{"query": "report", "cursor": "0", "as_of_a": {{"frame": {first}}}, "as_of_b": {{"frame": {second}}}}}"#
+        );
+        let request = CString::new(request).unwrap();
+        let result_ptr = unsafe { memvid_search_diff(handle, request.as_ptr(), &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
 
-        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
-        let json = report_str.to_str().unwrap();
-        assert!(json.contains("\"overall_status\":\"passed\""));
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_as_of() {
+        let path = TempPath::new("search_as_of.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content1 = b"Audit log entry one.";
+        let first =
+            unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+        let content2 = b"Audit log entry two.";
+        let second =
+            unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let request = format!(r#"{{"query": "audit", "as_of_frame": {first}}}"#);
+        let request = CString::new(request).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let hits = json["hits"].as_array().unwrap();
+        assert!(hits.iter().any(|h| h["frame_id"] == first));
+        assert!(hits.iter().all(|h| h["frame_id"] != second));
+
+        unsafe { memvid_string_free(result_ptr) };
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_no_sketch() {
+        let path = TempPath::new("search_no_sketch.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Recall benchmark ground truth."]);
+
+        let mut totals = Vec::new();
+        for no_sketch in [false, true] {
+            let request = format!(r#"{{"query": "benchmark", "no_sketch": {no_sketch}}}"#);
+            let request = CString::new(request).unwrap();
+            let result_ptr = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            totals.push(json["total_hits"].as_u64().unwrap());
+            unsafe { memvid_string_free(result_ptr) };
+        }
+        assert_eq!(totals, [1, 1]);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_titles_only() {
+        let path = TempPath::new("search_titles_only.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let content = b"Autocomplete candidates for the typeahead box.";
+        let options = CString::new(r#"{"title": "Typeahead"}"#).unwrap();
+        unsafe {
+            memvid_put_bytes_with_options(
+                handle,
+                content.as_ptr(),
+                content.len(),
+                options.as_ptr(),
+                &mut error,
+            )
+        };
+        unsafe { memvid_commit(handle, &mut error) };
 
-        unsafe { memvid_string_free(report_ptr) };
-        let _ = std::fs::remove_file(&path);
+        for request in [
+            r#"{"query": "typeahead", "titles_only": true}"#,
+            r#"{"query": "typeahead", "snippet_chars": 0}"#,
+        ] {
+            let request = CString::new(request).unwrap();
+            let result_ptr = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            let hit = &json["hits"][0];
+            assert_eq!(hit["title"], "Typeahead");
+            assert_eq!(hit["text"], "");
+            assert_eq!(hit["range"], serde_json::json!([0, 0]));
+            assert_eq!(json["context"], "");
+            unsafe { memvid_string_free(result_ptr) };
+        }
+
+        unsafe { memvid_close(handle) };
     }
 
     #[test]
-    fn test_ask() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_ask.mv2");
+    fn test_search_context_budget() {
+        let path = TempPath::new("search_context_budget.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
-        assert!(!handle.is_null());
-
-        // Add some content to search
-        let content1 = b"The capital of France is Paris. It is known for the Eiffel Tower.";
-        let content2 = b"Berlin is the capital of Germany. It has a famous wall.";
-        let content3 = b"Tokyo is the capital of Japan. It is a very large city.";
-        unsafe { memvid_put_bytes(handle, content1.as_ptr(), content1.len(), &mut error) };
-        unsafe { memvid_put_bytes(handle, content2.as_ptr(), content2.len(), &mut error) };
-        unsafe { memvid_put_bytes(handle, content3.as_ptr(), content3.len(), &mut error) };
+        for content in [
+            "Budget notes for the first quarter.",
+            "Budget review with the finance team.",
+            "Budget forecast for next year.",
+        ] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
         unsafe { memvid_commit(handle, &mut error) };
 
-        // Ask a question (context_only mode - no LLM synthesis)
-        let ask_json =
-            CString::new(r#"{"question": "What is the capital of France?", "top_k": 5}"#).unwrap();
-        let result_ptr = unsafe { memvid_ask(handle, ask_json.as_ptr(), &mut error) };
+        let search = |request: String| {
+            let request = CString::new(request).unwrap();
+            let mut error = MemvidError::ok();
+            let result_ptr = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            let json: serde_json::Value =
+                serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+            unsafe { memvid_string_free(result_ptr) };
+            json
+        };
 
-        if result_ptr.is_null() {
-            let msg = if !error.message.is_null() {
-                unsafe { std::ffi::CStr::from_ptr(error.message) }
-                    .to_str()
-                    .unwrap_or("unknown")
-            } else {
-                "no message"
-            };
-            panic!("memvid_ask failed: {:?} - {}", error.code, msg);
-        }
-        assert_eq!(error.code, MemvidErrorCode::Ok);
+        let full = search(r#"{"query": "budget"}"#.to_string());
+        assert_eq!(full["context_truncated"], false);
+        let first = full["hits"][0]["text"].as_str().unwrap().to_string();
+        let second = full["hits"][1]["text"].as_str().unwrap();
+        let budget = first.chars().count() + 2 + second.chars().count() - 1;
 
-        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
-        let json = result_str.to_str().unwrap();
+        let request = format!(r#"{{"query": "budget", "context_budget_chars": {budget}}}"#);
+        let json = search(request);
+        assert_eq!(json["context"], first.as_str());
+        assert_eq!(json["context_truncated"], true);
+        assert_eq!(json["hits"].as_array().unwrap().len(), 3);
 
-        // Verify response structure
-        assert!(json.contains("\"question\""));
-        assert!(json.contains("\"context_only\":true"));
-        assert!(json.contains("\"retrieval\""));
-        assert!(json.contains("\"stats\""));
-        assert!(json.contains("\"context_fragments\""));
+        let json = search(r#"{"query": "budget", "context_budget_chars": 6}"#.to_string());
+        assert_eq!(json["context"], first.chars().take(6).collect::<String>());
+        assert_eq!(json["context_truncated"], true);
+
+        let json = search(r#"{"query": "budget", "context_budget_chars": 100000}"#.to_string());
+        assert_eq!(json["context_truncated"], false);
 
-        unsafe { memvid_string_free(result_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn test_doctor() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_doctor.mv2");
+    fn test_search_language() {
+        let path = TempPath::new("search_language.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
+        let options = CString::new(r#"{"language": "de"}"#).unwrap();
+        let handle =
+            unsafe { memvid_create_with_options(path_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert!(handle.is_null());
+        assert_eq!(error.code, MemvidErrorCode::FeatureUnavailable);
+        unsafe { memvid_error_free(&mut error) };
 
-        // Create a valid memory file
-        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        let options = CString::new(r#"{"language": "en"}"#).unwrap();
+        let handle =
+            unsafe { memvid_create_with_options(path_cstr.as_ptr(), options.as_ptr(), &mut error) };
         assert!(!handle.is_null());
-        let content = b"Content for doctor test.";
+        let content = b"Quarterly planning meeting notes.";
         unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
         unsafe { memvid_commit(handle, &mut error) };
+
+        let request = CString::new(r#"{"query": "planning", "language": "en"}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        unsafe { memvid_string_free(result_ptr) };
+
+        // An analyzer the index was not built with is not silently replaced
+        let request = CString::new(r#"{"query": "planning", "language": "de"}"#).unwrap();
+        let result_ptr = unsafe { memvid_search(handle, request.as_ptr(), &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        let message = unsafe { std::ffi::CStr::from_ptr(error.message) };
+        assert!(message.to_str().unwrap().contains("available: en"));
+        unsafe { memvid_error_free(&mut error) };
+
         unsafe { memvid_close(handle) };
+    }
 
-        // Run doctor (should report clean)
-        let report_ptr = unsafe { memvid_doctor(path_cstr.as_ptr(), std::ptr::null(), &mut error) };
-        assert!(!report_ptr.is_null());
+    #[test]
+    fn test_search_multi() {
+        let path = TempPath::new("search_multi.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Quarterly revenue forecast for the sales team."]);
+
+        let requests = CString::new(
+            r#"[{"query": "revenue"}, {"query": "revenue", "compress_context": true},
+                {"top_k": 3}, {"query": "sales"}]"#,
+        )
+        .unwrap();
+        let result_ptr = unsafe { memvid_search_multi(handle, requests.as_ptr(), &mut error) };
         assert_eq!(error.code, MemvidErrorCode::Ok);
+        let result_str = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+        let slots: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+        let slots = slots.as_array().unwrap();
+        assert_eq!(slots.len(), 4);
+        assert_eq!(slots[0]["query"], "revenue");
+        assert_eq!(slots[0]["total_hits"], 1);
+        assert_eq!(slots[1]["error"]["name"], "InvalidQuery");
+        assert_eq!(slots[2]["error"]["name"], "JsonParse");
+        assert_eq!(slots[3]["total_hits"], 1);
+        unsafe { memvid_string_free(result_ptr) };
 
-        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
-        let json = report_str.to_str().unwrap();
-        assert!(json.contains("\"status\""));
-        assert!(json.contains("\"plan\""));
-        assert!(json.contains("\"metrics\""));
+        // Only a non-array fails the whole call
+        let not_array = CString::new(r#"{"query": "revenue"}"#).unwrap();
+        let result_ptr = unsafe { memvid_search_multi(handle, not_array.as_ptr(), &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::JsonParse);
+        unsafe { memvid_error_free(&mut error) };
 
-        unsafe { memvid_string_free(report_ptr) };
-        let _ = std::fs::remove_file(&path);
+        unsafe { memvid_close(handle) };
     }
 
     #[test]
-    fn test_doctor_plan() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_doctor_plan.mv2");
+    fn test_find_near_duplicates() {
+        let path = TempPath::new("near_duplicates.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Frame stored without an embedding."]);
+
+        // Out-of-range thresholds are rejected
+        let result_ptr = unsafe { memvid_find_near_duplicates(handle, 0, 1.5, &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+
+        let result_ptr = unsafe { memvid_find_near_duplicates(handle, 0, 0.9, &mut error) };
+        assert!(result_ptr.is_null());
+        #[cfg(not(feature = "vec"))]
+        assert_eq!(error.code, MemvidErrorCode::VecNotEnabled);
+        #[cfg(feature = "vec")]
+        assert_eq!(error.code, MemvidErrorCode::InvalidFrame);
+        unsafe { memvid_error_free(&mut error) };
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_contains_hash() {
+        let path = TempPath::new("contains_hash.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
-
-        // Create a valid memory file
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
-        assert!(!handle.is_null());
-        let content = b"Content for doctor plan test.";
-        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        let stored = b"Already stored content.";
+        let staged = b"Staged after the index was built.";
+        let absent = b"Never stored.";
+        let stored_id =
+            unsafe { memvid_put_bytes(handle, stored.as_ptr(), stored.len(), &mut error) };
         unsafe { memvid_commit(handle, &mut error) };
-        unsafe { memvid_close(handle) };
 
-        // Get doctor plan
-        let plan_ptr =
-            unsafe { memvid_doctor_plan(path_cstr.as_ptr(), std::ptr::null(), &mut error) };
-        assert!(!plan_ptr.is_null());
+        let contains = |data: &[u8], error: &mut MemvidError| unsafe {
+            memvid_contains_hash(handle, data.as_ptr(), data.len(), error)
+        };
+        assert_eq!(contains(stored, &mut error), 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(contains(absent, &mut error), 0);
         assert_eq!(error.code, MemvidErrorCode::Ok);
 
-        let plan_str = unsafe { std::ffi::CStr::from_ptr(plan_ptr) };
-        let json = plan_str.to_str().unwrap();
-        assert!(json.contains("\"version\""));
-        assert!(json.contains("\"file_path\""));
-        assert!(json.contains("\"phases\""));
+        unsafe { memvid_put_bytes(handle, staged.as_ptr(), staged.len(), &mut error) };
+        assert_eq!(contains(staged, &mut error), 1);
 
-        unsafe { memvid_string_free(plan_ptr) };
-        let _ = std::fs::remove_file(&path);
-    }
+        // Deleted content is no longer found
+        unsafe { memvid_delete_frame(handle, stored_id, &mut error) };
+        assert_eq!(contains(stored, &mut error), 0);
 
-    // ==========================================================================
-    // Edge Case Tests
-    // ==========================================================================
+        // The hash survives a reopen
+        unsafe { memvid_commit(handle, &mut error) };
+        unsafe { memvid_close(handle) };
+        let handle = unsafe { memvid_open(path_cstr.as_ptr(), &mut error) };
+        let contains = |data: &[u8], error: &mut MemvidError| unsafe {
+            memvid_contains_hash(handle, data.as_ptr(), data.len(), error)
+        };
+        assert_eq!(contains(staged, &mut error), 1);
+        assert_eq!(contains(stored, &mut error), 0);
+
+        unsafe { memvid_close(handle) };
+    }
 
     #[test]
-    fn test_empty_content() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_empty_content.mv2");
+    fn test_put_image() {
+        let path = TempPath::new("put_image.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
-        assert!(!handle.is_null());
+        let null = std::ptr::null();
 
-        // Put empty content
-        let content = b"";
+        let frame_id = unsafe { memvid_put_image(handle, std::ptr::null(), 0, null, &mut error) };
+        assert_eq!(frame_id, 0);
+        assert_eq!(error.code, MemvidErrorCode::NullPointer);
+        unsafe { memvid_error_free(&mut error) };
+
+        let not_an_image = b"plain text, not an image";
+        let len = not_an_image.len();
         let frame_id =
-            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
-        // Should succeed even with empty content
-        assert!(frame_id > 0 || error.code == MemvidErrorCode::Ok);
+            unsafe { memvid_put_image(handle, not_an_image.as_ptr(), len, null, &mut error) };
+        assert_eq!(frame_id, 0);
+        #[cfg(not(feature = "clip"))]
+        assert_eq!(error.code, MemvidErrorCode::ClipNotEnabled);
+        #[cfg(feature = "clip")]
+        assert_eq!(error.code, MemvidErrorCode::ExtractionFailed);
+        unsafe { memvid_error_free(&mut error) };
+        assert_eq!(unsafe { memvid_frame_count(handle, &mut error) }, 0);
 
-        unsafe { memvid_commit(handle, &mut error) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn test_unicode_content() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_unicode.mv2");
+    fn test_clip_search() {
+        let path = TempPath::new("clip_search.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
         assert!(!handle.is_null());
 
-        // Put unicode content
-        let content = "Hello 世界! 🎉 Привет мир! مرحبا بالعالم";
-        let content_bytes = content.as_bytes();
-        let _frame_id = unsafe {
-            memvid_put_bytes(handle, content_bytes.as_ptr(), content_bytes.len(), &mut error)
-        };
-        unsafe { memvid_commit(handle, &mut error) };
+        let result_ptr = unsafe { memvid_clip_search(handle, std::ptr::null(), 0, 5, &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::NullPointer);
+        unsafe { memvid_error_free(&mut error) };
+
+        let not_an_image = b"plain text, not an image";
+        let len = not_an_image.len();
+        let result_ptr =
+            unsafe { memvid_clip_search(handle, not_an_image.as_ptr(), len, 5, &mut error) };
+        assert!(result_ptr.is_null());
+        #[cfg(not(feature = "clip"))]
+        assert_eq!(error.code, MemvidErrorCode::ClipNotEnabled);
+        #[cfg(feature = "clip")]
+        assert_eq!(error.code, MemvidErrorCode::ExtractionFailed);
+        unsafe { memvid_error_free(&mut error) };
+
         unsafe { memvid_close(handle) };
+    }
 
-        // Reopen and verify content
-        let handle = unsafe { memvid_open(path_cstr.as_ptr(), &mut error) };
-        let content_ptr = unsafe { memvid_frame_content(handle, 0, &mut error) };
-        assert!(!content_ptr.is_null());
+    #[test]
+    fn test_similar_frames() {
+        let path = TempPath::new("similar_frames.mv2");
+        let mut error = MemvidError::ok();
+        let handle = create_with(&path, &[b"Frame stored without an embedding."]);
 
-        let content_str = unsafe { std::ffi::CStr::from_ptr(content_ptr) };
-        let retrieved = content_str.to_str().unwrap();
-        assert!(retrieved.contains("世界"));
-        assert!(retrieved.contains("🎉"));
+        let result_ptr = unsafe { memvid_similar_frames(handle, 0, 0, &mut error) };
+        assert!(result_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        unsafe { memvid_error_free(&mut error) };
+
+        let result_ptr = unsafe { memvid_similar_frames(handle, 0, 5, &mut error) };
+        #[cfg(not(feature = "vec"))]
+        {
+            assert!(result_ptr.is_null());
+            assert_eq!(error.code, MemvidErrorCode::VecNotEnabled);
+            unsafe { memvid_error_free(&mut error) };
+        }
+        #[cfg(feature = "vec")]
+        {
+            // No embedding: empty result, not an error
+            assert_eq!(error.code, MemvidErrorCode::Ok);
+            let result = unsafe { std::ffi::CStr::from_ptr(result_ptr) };
+            assert_eq!(result.to_str().unwrap(), "[]");
+            unsafe { memvid_string_free(result_ptr) };
+        }
 
-        unsafe { memvid_string_free(content_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
+    }
+
+    extern "C" fn stop_after_first_hit(
+        hit_json: *const c_char,
+        user_data: *mut std::ffi::c_void,
+    ) -> i32 {
+        let hit = unsafe { std::ffi::CStr::from_ptr(hit_json) };
+        let json: serde_json::Value = serde_json::from_str(hit.to_str().unwrap()).unwrap();
+        assert!(json["frame_id"].is_u64());
+        unsafe { *(user_data as *mut u32) += 1 };
+        1
     }
 
     #[test]
-    fn test_search_no_results() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_search_no_results.mv2");
+    fn test_search_stream() {
+        let path = TempPath::new("search_stream.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
         let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
 
-        let content = b"The quick brown fox jumps over the lazy dog.";
-        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        for content in [&b"Streaming hit one."[..], b"Streaming hit two."] {
+            unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        }
         unsafe { memvid_commit(handle, &mut error) };
 
+        let mut calls: u32 = 0;
+        let request = CString::new(r#"{"query": "streaming", "top_k": 10}"#).unwrap();
+        let result = unsafe {
+            memvid_search_stream(
+                handle,
+                request.as_ptr(),
+                Some(stop_after_first_hit),
+                &mut calls as *mut u32 as *mut std::ffi::c_void,
+                &mut error,
+            )
+        };
+        assert_eq!(result, 1);
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+        assert_eq!(calls, 1);
+
+        unsafe { memvid_close(handle) };
+    }
+
+    #[test]
+    fn test_search_no_results() {
+        let path = TempPath::new("search_no_results.mv2");
+        let mut error = MemvidError::ok();
+        let content = b"The quick brown fox jumps over the lazy dog.";
+        let handle = create_with(&path, &[content]);
+
         // Search for something not in the content
         let search_json = CString::new(r#"{"query": "elephant zebra giraffe", "top_k": 5}"#).unwrap();
         let result_ptr = unsafe { memvid_search(handle, search_json.as_ptr(), &mut error) };
@@ -768,13 +4480,11 @@ mod tests {
 
         unsafe { memvid_string_free(result_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
     fn test_search_empty_memory() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_search_empty.mv2");
+        let path = TempPath::new("search_empty.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -792,7 +4502,6 @@ mod tests {
 
         unsafe { memvid_string_free(result_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
@@ -818,10 +4527,46 @@ mod tests {
     // Note: test_invalid_handle removed - using garbage pointers causes undefined behavior
     // The FFI layer validates handles but can't protect against completely invalid memory
 
+    #[cfg(windows)]
+    #[test]
+    fn test_wide_paths() {
+        use std::os::windows::ffi::OsStrExt;
+
+        let path = TempPath::new("wide_\u{00e9}\u{6f22}.mv2");
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain([0]).collect();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create_w(wide.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        unsafe { memvid_close(handle) };
+
+        let handle = unsafe { memvid_open_w(wide.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        unsafe { memvid_close(handle) };
+
+        let report_ptr = unsafe { memvid_verify_w(wide.as_ptr(), 2, &mut error) };
+        assert!(!report_ptr.is_null());
+        unsafe { memvid_string_free(report_ptr) };
+    }
+
+    #[test]
+    fn test_handle_is_valid() {
+        let path = TempPath::new("handle_is_valid.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(unsafe { memvid_handle_is_valid(std::ptr::null()) }, 0);
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        assert_eq!(unsafe { memvid_handle_is_valid(handle) }, 1);
+
+        unsafe { memvid_close(handle) };
+    }
+
     #[test]
     fn test_persistence_round_trip() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_round_trip.mv2");
+        let path = TempPath::new("round_trip.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -864,13 +4609,61 @@ mod tests {
 
         unsafe { memvid_string_free(result_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
+    }
+
+    extern "C" fn count_watch_events(event_json: *const c_char, user_data: *mut std::ffi::c_void) {
+        let event = unsafe { std::ffi::CStr::from_ptr(event_json) };
+        let json: serde_json::Value = serde_json::from_str(event.to_str().unwrap()).unwrap();
+        let seen = unsafe { &*(user_data as *const std::sync::atomic::AtomicU64) };
+        seen.store(
+            json["frame_count"].as_u64().unwrap(),
+            std::sync::atomic::Ordering::SeqCst,
+        );
+    }
+
+    #[test]
+    fn test_watch() {
+        let path = TempPath::new("watch.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+
+        let seen = std::sync::atomic::AtomicU64::new(0);
+        let watcher = unsafe {
+            memvid_watch(
+                handle,
+                Some(count_watch_events),
+                &seen as *const _ as *mut std::ffi::c_void,
+                &mut error,
+            )
+        };
+        assert!(!watcher.is_null());
+        assert_eq!(error.code, MemvidErrorCode::Ok);
+
+        let content = b"Watched document";
+        unsafe { memvid_put_bytes(handle, content.as_ptr(), content.len(), &mut error) };
+        unsafe { memvid_commit(handle, &mut error) };
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while seen.load(std::sync::atomic::Ordering::SeqCst) == 0
+            && std::time::Instant::now() < deadline
+        {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        unsafe { memvid_watch_stop(watcher) };
+        assert_eq!(seen.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // NULL is safe
+        unsafe { memvid_watch_stop(std::ptr::null_mut()) };
+
+        unsafe { memvid_close(handle) };
     }
 
     #[test]
     fn test_many_frames() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_many_frames.mv2");
+        let path = TempPath::new("many_frames.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -898,13 +4691,11 @@ mod tests {
 
         unsafe { memvid_string_free(result_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
     fn test_timeline_empty_memory() {
-        let temp_dir = std::env::temp_dir();
-        let path = temp_dir.join("test_ffi_timeline_empty.mv2");
+        let path = TempPath::new("timeline_empty.mv2");
         let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
 
         let mut error = MemvidError::ok();
@@ -920,7 +4711,46 @@ mod tests {
 
         unsafe { memvid_string_free(timeline_ptr) };
         unsafe { memvid_close(handle) };
-        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_verify_with_options() {
+        let path = TempPath::new("verify_options.mv2");
+        let path_cstr = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut error = MemvidError::ok();
+        let handle = unsafe { memvid_create(path_cstr.as_ptr(), &mut error) };
+        assert!(!handle.is_null());
+        unsafe { memvid_close(handle) };
+
+        // Only the selected groups are reported
+        let options = CString::new(r#"{"checks": ["header", "lex_index"]}"#).unwrap();
+        let report_ptr =
+            unsafe { memvid_verify_with_options(path_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert!(!report_ptr.is_null());
+        let report_str = unsafe { std::ffi::CStr::from_ptr(report_ptr) };
+        let json: serde_json::Value = serde_json::from_str(report_str.to_str().unwrap()).unwrap();
+        assert_eq!(json["overall_status"], "passed");
+        let checks = json["checks"].as_array().unwrap();
+        assert_eq!(checks[0]["name"], "HeaderMagic");
+        assert!(checks.iter().all(|c| {
+            let name = c["name"].as_str().unwrap();
+            name.starts_with("Header")
+                || name == "ManifestChecksum"
+                || name.contains("LexIndex")
+                || name == "lex_index"
+        }));
+        unsafe { memvid_string_free(report_ptr) };
+
+        // Unknown check names are rejected
+        let options = CString::new(r#"{"checks": ["header", "bogus"]}"#).unwrap();
+        let report_ptr =
+            unsafe { memvid_verify_with_options(path_cstr.as_ptr(), options.as_ptr(), &mut error) };
+        assert!(report_ptr.is_null());
+        assert_eq!(error.code, MemvidErrorCode::InvalidQuery);
+        let message = unsafe { std::ffi::CStr::from_ptr(error.message) };
+        assert!(message.to_str().unwrap().contains("bogus"));
+        unsafe { memvid_error_free(&mut error) };
     }
 
     #[test]
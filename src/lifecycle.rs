@@ -1,9 +1,15 @@
 //! Lifecycle management functions (create, open, close).
 
-use crate::error::MemvidError;
+use crate::error::{MemvidError, MemvidErrorCode};
 use crate::handle::MemvidHandle;
-use crate::util::{cstr_to_path, set_error_null, set_ok};
+use crate::header;
+use crate::search::check_language;
+use crate::util::{cstr_to_option_string, cstr_to_path, set_error, set_error_null, set_ok};
+use memvid_core::Memvid;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
 
 /// Create a new Memvid memory at the specified path.
 ///
@@ -35,16 +41,333 @@ pub unsafe extern "C" fn memvid_create(
         Err(e) => return unsafe { set_error_null(error, e) },
     };
 
-    // Create the memvid instance
+    unsafe { create_at(path, error) }
+}
+
+/// Create a memory at `path`, setting the error out-parameter.
+///
+/// # Safety
+///
+/// `error` must be a valid pointer or NULL.
+unsafe fn create_at(path: PathBuf, error: *mut MemvidError) -> *mut MemvidHandle {
     match memvid_core::Memvid::create(&path) {
         Ok(memvid) => {
             unsafe { set_ok(error) };
-            Box::into_raw(MemvidHandle::new(memvid))
+            Box::into_raw(MemvidHandle::new(memvid, path))
         }
         Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
     }
 }
 
+/// Create a new Memvid memory at a UTF-16 path (Windows only).
+///
+/// Same as `memvid_create`, but takes the path as null-terminated UTF-16,
+/// so Windows paths round-trip without a lossy conversion to UTF-8.
+///
+/// # Parameters
+///
+/// - `path`: Filesystem path for the memory (UTF-16 encoded, null-terminated)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Handle on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned handle. Must call `memvid_close()` to free.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-16 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[cfg(windows)]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_create_w(
+    path: *const u16,
+    error: *mut MemvidError,
+) -> *mut MemvidHandle {
+    match unsafe { crate::util::wstr_to_path(path) } {
+        Ok(path) => unsafe { create_at(path, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Create an empty memory at `path` matching the template's settings.
+///
+/// The template is only header-checked and opened read-only for its stats;
+/// its frames are never read. If the new memory cannot match the template,
+/// the new file is removed.
+fn create_from_template(path: &Path, template: &Path) -> Result<Memvid, MemvidError> {
+    let header = header::read_header(template).map_err(|e| {
+        MemvidError::new(
+            MemvidErrorCode::Io,
+            format!("cannot read template {}: {e}", template.display()),
+        )
+    })?;
+    if !header.compatible() {
+        return Err(MemvidError::new(
+            MemvidErrorCode::InvalidHeader,
+            format!(
+                "template {} is not a readable .mv2 file (format {})",
+                template.display(),
+                header.format_version()
+            ),
+        ));
+    }
+
+    let template_stats = Memvid::open_read_only(template)
+        .and_then(|memvid| memvid.stats())
+        .map_err(MemvidError::from_core_error)?;
+
+    let memvid = Memvid::create(path).map_err(MemvidError::from_core_error)?;
+    require_capacity(
+        memvid,
+        path,
+        template_stats.capacity_bytes,
+        "template capacity",
+    )
+}
+
+/// Check that a freshly created memory has `capacity_bytes`.
+///
+/// Capacity beyond the default tier is granted by signed tickets, which are
+/// bound to a single memory and cannot be set at create time. On mismatch
+/// the new file is removed.
+fn require_capacity(
+    memvid: Memvid,
+    path: &Path,
+    capacity_bytes: u64,
+    what: &str,
+) -> Result<Memvid, MemvidError> {
+    let stats = memvid.stats().map_err(MemvidError::from_core_error)?;
+    if stats.capacity_bytes != capacity_bytes {
+        drop(memvid);
+        let _ = std::fs::remove_file(path);
+        return Err(MemvidError::new(
+            MemvidErrorCode::TicketRequired,
+            format!(
+                "{what} {capacity_bytes} bytes differs from the default {} bytes; apply a ticket instead",
+                stats.capacity_bytes
+            ),
+        ));
+    }
+    Ok(memvid)
+}
+
+/// Tier every new memory starts on; others are granted by tickets.
+const DEFAULT_TIER: &str = "free";
+
+/// Tiers memvid-core knows about.
+const TIERS: &[&str] = &["free", "dev", "enterprise"];
+
+/// JSON options for `memvid_create_with_options`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateOptionsJson {
+    capacity_bytes: Option<u64>,
+    encryption_key: Option<String>,
+    tier: Option<String>,
+    language: Option<String>,
+}
+
+/// JSON options for `memvid_open_with_options`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OpenOptionsJson {
+    encryption_key: Option<String>,
+}
+
+/// Parse an optional JSON options string; NULL or blank gives the defaults.
+///
+/// # Safety
+///
+/// `ptr` must be a valid null-terminated string or NULL.
+unsafe fn parse_options<T: DeserializeOwned + Default>(
+    ptr: *const c_char,
+    param_name: &str,
+) -> Result<T, MemvidError> {
+    match unsafe { cstr_to_option_string(ptr, param_name) }? {
+        Some(json) if !json.trim().is_empty() => {
+            serde_json::from_str(&json).map_err(MemvidError::json_parse)
+        }
+        _ => Ok(T::default()),
+    }
+}
+
+/// Reject an encryption key: memvid-core has no encrypted create or open path.
+fn check_encryption_key(key: Option<&str>) -> Result<(), MemvidError> {
+    match key {
+        Some(_) => Err(MemvidError::new(
+            MemvidErrorCode::FeatureUnavailable,
+            "memvid-core cannot create or open encrypted memories",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Create a memory at `path`, applying `options`.
+fn create_with_options(path: &Path, options: &CreateOptionsJson) -> Result<Memvid, MemvidError> {
+    check_encryption_key(options.encryption_key.as_deref())?;
+    check_language(
+        options.language.as_deref(),
+        MemvidErrorCode::FeatureUnavailable,
+    )?;
+    if let Some(tier) = options.tier.as_deref() {
+        if !TIERS.contains(&tier) {
+            return Err(MemvidError::new(
+                MemvidErrorCode::InvalidTier,
+                format!("unknown tier: {tier}"),
+            ));
+        }
+        if tier != DEFAULT_TIER {
+            return Err(MemvidError::new(
+                MemvidErrorCode::TicketRequired,
+                format!("tier {tier} is granted by a ticket, not at create time"),
+            ));
+        }
+    }
+
+    let memvid = Memvid::create(path).map_err(MemvidError::from_core_error)?;
+    match options.capacity_bytes {
+        Some(capacity) => require_capacity(memvid, path, capacity, "capacity"),
+        None => Ok(memvid),
+    }
+}
+
+/// Create a new Memvid memory with options (JSON configuration).
+///
+/// NULL or empty `options_json` behaves exactly like `memvid_create`.
+///
+/// memvid-core's create path takes no configuration, so the options are
+/// checked against what it produces rather than passed through: capacity
+/// and tier above the default come from signed tickets bound to one memory
+/// (apply one after creating), encrypted memories are not supported, and
+/// `language` must name the lexical analyzer core builds (see
+/// `memvid_search`).
+///
+/// # Parameters
+///
+/// - `path`: Filesystem path for the memory (UTF-8 encoded, null-terminated)
+/// - `options_json`: JSON options (see below), or NULL
+/// - `error`: Out-parameter for error information
+///
+/// # Options JSON Schema
+///
+/// ```json
+/// {
+///   "capacity_bytes": 1073741824,  // optional, must match the default
+///   "tier": "free",                // optional: "free", "dev", "enterprise"
+///   "language": "en",              // optional, default lexical analyzer
+///   "encryption_key": "base64..."  // optional, not supported
+/// }
+/// ```
+///
+/// # Returns
+///
+/// Handle on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned handle. Must call `memvid_close()` to free.
+///
+/// # Errors
+///
+/// - `JsonParse` if the options are malformed or contain an unknown field
+/// - `InvalidTier` if `tier` is not a known tier
+/// - `TicketRequired` if `capacity_bytes` or `tier` differs from the default
+/// - `FeatureUnavailable` if `encryption_key` is set, or `language` names
+///   an analyzer core does not build
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string or NULL
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_create_with_options(
+    path: *const c_char,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> *mut MemvidHandle {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+    let options: CreateOptionsJson = match unsafe { parse_options(options_json, "options_json") } {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    match create_with_options(&path, &options) {
+        Ok(memvid) => {
+            unsafe { set_ok(error) };
+            Box::into_raw(MemvidHandle::new(memvid, path))
+        }
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Create a new, empty Memvid memory with the settings of an existing one.
+///
+/// Reads only the template's header and stats, never its frames. The new
+/// memory gets the template's format and capacity; indexes are created by
+/// content as usual, so the same features are available in both.
+///
+/// memvid-core's create path takes no configuration: codec and index
+/// settings are fixed by the library build, and capacity above the default
+/// tier comes from signed tickets bound to one memory. A template whose
+/// capacity differs from a fresh memory's is therefore rejected with
+/// `TicketRequired` rather than silently producing a smaller memory.
+///
+/// # Parameters
+///
+/// - `path`: Filesystem path for the new memory (UTF-8 encoded, null-terminated)
+/// - `template_path`: Path to an existing memory to copy settings from
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Handle on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned handle. Must call `memvid_close()` to free.
+///
+/// # Errors
+///
+/// - `Io` if the template cannot be read
+/// - `InvalidHeader` if the template is not a compatible .mv2 file
+/// - `TicketRequired` if the template's capacity cannot be reproduced
+///
+/// # Safety
+///
+/// - `path` and `template_path` must be valid null-terminated UTF-8 strings or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_create_from_template(
+    path: *const c_char,
+    template_path: *const c_char,
+    error: *mut MemvidError,
+) -> *mut MemvidHandle {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+    let template_path = match unsafe { cstr_to_path(template_path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    match create_from_template(&path, &template_path) {
+        Ok(memvid) => {
+            unsafe { set_ok(error) };
+            Box::into_raw(MemvidHandle::new(memvid, path))
+        }
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
 /// Open an existing Memvid memory.
 ///
 /// # Parameters
@@ -75,19 +398,297 @@ pub unsafe extern "C" fn memvid_open(
         Err(e) => return unsafe { set_error_null(error, e) },
     };
 
-    // Open the memvid instance
+    unsafe { open_at(path, error) }
+}
+
+/// Open the memory at `path`, setting the error out-parameter.
+///
+/// # Safety
+///
+/// `error` must be a valid pointer or NULL.
+unsafe fn open_at(path: PathBuf, error: *mut MemvidError) -> *mut MemvidHandle {
     match memvid_core::Memvid::open(&path) {
         Ok(memvid) => {
             unsafe { set_ok(error) };
-            Box::into_raw(MemvidHandle::new(memvid))
+            Box::into_raw(MemvidHandle::new(memvid, path))
+        }
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Open an existing Memvid memory at a UTF-16 path (Windows only).
+///
+/// Same as `memvid_open`, but takes the path as null-terminated UTF-16,
+/// so Windows paths round-trip without a lossy conversion to UTF-8.
+///
+/// # Parameters
+///
+/// - `path`: Filesystem path to existing memory (UTF-16 encoded, null-terminated)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Handle on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned handle. Must call `memvid_close()` to free.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-16 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[cfg(windows)]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_open_w(
+    path: *const u16,
+    error: *mut MemvidError,
+) -> *mut MemvidHandle {
+    match unsafe { crate::util::wstr_to_path(path) } {
+        Ok(path) => unsafe { open_at(path, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Open an existing Memvid memory with options (JSON configuration).
+///
+/// NULL or empty `options_json` behaves exactly like `memvid_open`. The
+/// `encryption_key` accepted by `memvid_create_with_options` is accepted
+/// here too, and likewise rejected: memvid-core has no encrypted open path.
+///
+/// # Parameters
+///
+/// - `path`: Filesystem path to existing memory (UTF-8 encoded, null-terminated)
+/// - `options_json`: JSON options (see below), or NULL
+/// - `error`: Out-parameter for error information
+///
+/// # Options JSON Schema
+///
+/// ```json
+/// {
+///   "encryption_key": "base64..."  // optional, not supported
+/// }
+/// ```
+///
+/// # Returns
+///
+/// Handle on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned handle. Must call `memvid_close()` to free.
+///
+/// # Errors
+///
+/// - `JsonParse` if the options are malformed or contain an unknown field
+/// - `FeatureUnavailable` if `encryption_key` is set
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string or NULL
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_open_with_options(
+    path: *const c_char,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> *mut MemvidHandle {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+    let options: OpenOptionsJson = match unsafe { parse_options(options_json, "options_json") } {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+    if let Err(e) = check_encryption_key(options.encryption_key.as_deref()) {
+        return unsafe { set_error_null(error, e) };
+    }
+
+    match Memvid::open(&path) {
+        Ok(memvid) => {
+            unsafe { set_ok(error) };
+            Box::into_raw(MemvidHandle::new(memvid, path))
+        }
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Open an existing Memvid memory for reading only.
+///
+/// Uses memvid-core's read-only open, which does not take the write lock,
+/// so several processes can read a memory while another writes to it.
+/// Search, stats, timeline, and frame retrieval work as usual; puts,
+/// commits, deletes, restores, and frame updates fail with `ReadOnly`.
+///
+/// # Parameters
+///
+/// - `path`: Filesystem path to existing memory (UTF-8 encoded, null-terminated)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Handle on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned handle. Must call `memvid_close()` to free.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_open_readonly(
+    path: *const c_char,
+    error: *mut MemvidError,
+) -> *mut MemvidHandle {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    match Memvid::open_read_only(&path) {
+        Ok(memvid) => {
+            unsafe { set_ok(error) };
+            Box::into_raw(MemvidHandle::new_read_only(memvid, path))
         }
         Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
     }
 }
 
+/// Reload a memory from disk on an existing handle.
+///
+/// Reopens the file behind `handle`, so a long-lived reader picks up
+/// frames committed by other processes since it was opened, without
+/// closing and reopening the handle itself. A writable handle commits its
+/// staged changes first. The handle's options, read-only mode, and
+/// registered embedder are kept.
+///
+/// Consistency: after a successful refresh the handle sees every commit
+/// that completed before the call, and nothing committed while the call
+/// runs is seen partially. Between refreshes the handle keeps reading the
+/// snapshot it last loaded.
+///
+/// Frame cursors (`memvid_frames_iter_new`) and search `next_cursor`
+/// tokens issued before the refresh are invalidated: using them fails with
+/// `InvalidCursor`, and the search or iteration must be restarted.
+/// Timeline cursors name a position rather than index state and stay
+/// valid.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Errors
+///
+/// - `Locked` if a writable handle cannot take the write lock back because
+///   another writer acquired it
+///
+/// If the file cannot be reopened, the handle fails every later call with
+/// `InvalidHandle` and must be closed.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_refresh(handle: *mut MemvidHandle, error: *mut MemvidError) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if handle.has_pending_changes() {
+        if let Err(e) = handle.commit() {
+            return unsafe { set_error(error, MemvidError::from_core_error(e)) };
+        }
+    }
+
+    match handle.reopen_after(|_| Ok(())) {
+        Ok(()) => {
+            unsafe { set_ok(error) };
+            1
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
+
+/// Lock or unlock a handle against writes.
+///
+/// A lock on this handle only, not a memvid-core state: memvid-core has no
+/// public seal operation, and locking does not satisfy a core operation
+/// that fails with `RequiresSealed`.
+///
+/// Locking commits everything staged and syncs the file to disk, leaving
+/// nothing pending in the write-ahead log. While locked, every put
+/// (`memvid_put_bytes` and its variants, `memvid_put_batch`,
+/// `memvid_put_file`, `memvid_put_image`), delete, restore, and update,
+/// `memvid_import_jsonl`, `memvid_commit` and its variants, and
+/// `memvid_vacuum` fail with `ReadOnly`. Reads, search, `memvid_refresh`,
+/// and `memvid_close` are unaffected. Locking a locked handle or unlocking
+/// an unlocked one succeeds and does nothing.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `locked`: Nonzero to lock, 0 to unlock
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Errors
+///
+/// - `ReadOnly` when locking a handle opened read-only
+/// - `Io` if the file cannot be synced
+///
+/// If the commit or sync fails, the handle stays unlocked.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_set_write_locked(
+    handle: *mut MemvidHandle,
+    locked: i32,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if locked != 0 && !handle.write_locked() {
+        if let Err(e) = handle.check_writable() {
+            return unsafe { set_error(error, e) };
+        }
+        if handle.has_pending_changes() {
+            if let Err(e) = handle.commit() {
+                return unsafe { set_error(error, MemvidError::from_core_error(e)) };
+            }
+        }
+        if let Err(e) = handle.fsync_written() {
+            return unsafe { set_error(error, MemvidError::io(e)) };
+        }
+    }
+    handle.set_write_locked(locked != 0);
+    unsafe { set_ok(error) };
+    1
+}
+
 /// Close and free a Memvid handle.
 ///
-/// After this call, the handle is invalid and must not be used.
+/// After this call, the handle is invalid and must not be used. A handle
+/// that is not live (see `memvid_handle_is_valid`) is ignored.
 ///
 /// # Parameters
 ///
@@ -100,7 +701,7 @@ pub unsafe extern "C" fn memvid_open(
 /// - The handle must not be used after this call
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn memvid_close(handle: *mut MemvidHandle) {
-    if handle.is_null() {
+    if !unsafe { MemvidHandle::is_live(handle) } {
         return;
     }
 
@@ -109,3 +710,28 @@ pub unsafe extern "C" fn memvid_close(handle: *mut MemvidHandle) {
         drop(Box::from_raw(handle));
     }
 }
+
+/// Check whether a handle pointer looks usable.
+///
+/// Returns 0 for NULL and for a handle that has been closed; every FFI call
+/// taking a handle makes the same check and fails with `InvalidHandle`. The
+/// check reads a tag stored in the handle, so it catches reuse after close
+/// only while the freed memory has not been reused, and cannot recognize
+/// arbitrary garbage pointers. It is a debugging aid for bindings, not a
+/// guarantee.
+///
+/// # Parameters
+///
+/// - `handle`: Handle to check (may be NULL)
+///
+/// # Returns
+///
+/// 1 if the handle is live, 0 otherwise.
+///
+/// # Safety
+///
+/// - `handle` must be NULL or point to memory readable as a handle
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_handle_is_valid(handle: *const MemvidHandle) -> i32 {
+    unsafe { MemvidHandle::is_live(handle) as i32 }
+}
@@ -0,0 +1,165 @@
+//! Forwarding of memvid-core diagnostics to a host log callback.
+
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::util::{set_error, set_ok};
+use std::ffi::{c_void, CString};
+use std::fmt::{self, Write};
+use std::os::raw::c_char;
+use std::sync::{OnceLock, PoisonError, RwLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+/// Log callback type.
+///
+/// Receives the record's level (1 = error, 2 = warn, 3 = info, 4 = debug,
+/// 5 = trace) and its formatted message. The message is only valid for the
+/// duration of the call.
+pub type MemvidLogFn = extern "C" fn(level: u32, msg: *const c_char, user_data: *mut c_void);
+
+/// Callback and user data records are forwarded to.
+#[derive(Clone, Copy)]
+struct LogSink {
+    callback: MemvidLogFn,
+    user_data: *mut c_void,
+}
+
+// SAFETY: the caller guarantees the callback and user data may be used from
+// any thread (documented on `memvid_set_log_callback`).
+unsafe impl Send for LogSink {}
+unsafe impl Sync for LogSink {}
+
+static LOG_SINK: RwLock<Option<LogSink>> = RwLock::new(None);
+
+/// Outcome of installing the forwarding subscriber, attempted once.
+static SUBSCRIBER: OnceLock<Result<(), String>> = OnceLock::new();
+
+/// Map a tracing level to the callback's level number.
+fn level_number(level: Level) -> u32 {
+    match level {
+        Level::ERROR => 1,
+        Level::WARN => 2,
+        Level::INFO => 3,
+        Level::DEBUG => 4,
+        Level::TRACE => 5,
+    }
+}
+
+/// Formats an event as `message key=value ...`.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => {
+                let _ = write!(self.message, "{value:?}");
+            }
+            // Metadata of records bridged from the `log` crate
+            name if name.starts_with("log.") => {}
+            name => {
+                let _ = write!(self.fields, " {name}={value:?}");
+            }
+        }
+    }
+}
+
+/// Layer forwarding every event to the installed sink.
+struct HostLogLayer;
+
+impl<S: Subscriber> Layer<S> for HostLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        // Copy the sink out so the callback can replace it without deadlock.
+        let Some(sink) = *LOG_SINK.read().unwrap_or_else(PoisonError::into_inner) else {
+            return;
+        };
+
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let target = metadata.target();
+        let msg = format!("{target}: {}{}", visitor.message, visitor.fields);
+
+        let level = level_number(*metadata.level());
+        if let Ok(msg) = CString::new(msg.replace('\0', "")) {
+            (sink.callback)(level, msg.as_ptr(), sink.user_data);
+        }
+    }
+}
+
+/// Install the forwarding subscriber and the `log` bridge, once.
+fn install_subscriber() -> Result<(), MemvidError> {
+    let installed = SUBSCRIBER.get_or_init(|| {
+        let subscriber = tracing_subscriber::registry().with(HostLogLayer);
+        tracing::subscriber::set_global_default(subscriber).map_err(|e| e.to_string())?;
+        // A `log` logger set by the host takes precedence; records from
+        // `log` then go there instead.
+        let _ = tracing_log::LogTracer::init();
+        Ok(())
+    });
+    installed.clone().map_err(|e| {
+        MemvidError::new(
+            MemvidErrorCode::FeatureUnavailable,
+            format!("cannot install log forwarding: {e}"),
+        )
+    })
+}
+
+/// Forward memvid-core's log records to a host callback.
+///
+/// Installs a process-global `tracing` subscriber, bridging records from
+/// the `log` crate as well, that formats each record as
+/// `target: message key=value ...` and passes it to `callback` with its
+/// level (1 = error, 2 = warn, 3 = info, 4 = debug, 5 = trace).
+///
+/// Call it once at startup. Later calls replace the callback and user
+/// data; passing NULL stops forwarding. The subscriber itself stays
+/// installed for the life of the process.
+///
+/// # Parameters
+///
+/// - `callback`: Log callback (see `MemvidLogFn`), or NULL to stop forwarding
+/// - `user_data`: Opaque pointer passed through to `callback`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Errors
+///
+/// - `FeatureUnavailable` if another global `tracing` subscriber was
+///   installed first
+///
+/// # Safety
+///
+/// - `callback` must be a valid function pointer or NULL
+/// - Records are emitted on whichever thread produces them, so `callback`
+///   and `user_data` must be safe to use from any thread, concurrently
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_set_log_callback(
+    callback: Option<MemvidLogFn>,
+    user_data: *mut c_void,
+    error: *mut MemvidError,
+) -> i32 {
+    let sink = callback.map(|callback| LogSink {
+        callback,
+        user_data,
+    });
+
+    if sink.is_some() {
+        if let Err(e) = install_subscriber() {
+            return unsafe { set_error(error, e) };
+        }
+    }
+
+    *LOG_SINK.write().unwrap_or_else(PoisonError::into_inner) = sink;
+    unsafe { set_ok(error) };
+    1
+}
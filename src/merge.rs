@@ -0,0 +1,218 @@
+//! Merging one memory file into another.
+
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::handle::MemvidHandle;
+use crate::mutation::{put_with_options, PutOptionsJson};
+use crate::util::{cstr_to_option_json, cstr_to_path, set_error, set_ok};
+use memvid_core::types::FrameStatus;
+use memvid_core::Memvid;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::os::raw::c_char;
+use std::path::Path;
+
+/// What to do with a source frame whose URI is already in the destination.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum UriConflict {
+    /// Leave the destination frame and drop the source frame
+    Skip,
+    /// Delete the destination frames with the URI, then add the source frame
+    Replace,
+    /// Add the source frame alongside the destination frames
+    #[default]
+    KeepBoth,
+}
+
+/// Options for `memvid_merge`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MergeOptionsJson {
+    #[serde(default)]
+    on_uri_conflict: UriConflict,
+}
+
+/// Active frames of `dest` by URI, each with its chunk frames.
+fn frames_by_uri(dest: &mut MemvidHandle) -> HashMap<String, Vec<u64>> {
+    let active: Vec<memvid_core::Frame> = dest
+        .frames()
+        .filter(|f| matches!(f.status, FrameStatus::Active))
+        .collect();
+    let uris: HashMap<u64, String> = active
+        .iter()
+        .filter_map(|f| Some((f.id, f.uri.clone()?)))
+        .collect();
+
+    let mut by_uri: HashMap<String, Vec<u64>> = HashMap::new();
+    for frame in &active {
+        // Chunks are listed under their parent's URI.
+        let uri = match frame.parent_id {
+            Some(parent) => uris.get(&parent),
+            None => frame.uri.as_ref(),
+        };
+        if let Some(uri) = uri {
+            by_uri.entry(uri.clone()).or_default().push(frame.id);
+        }
+    }
+    by_uri
+}
+
+/// Put every active document frame of `src` into `dest` and commit.
+fn merge_frames(
+    dest: &mut MemvidHandle,
+    src: &mut MemvidHandle,
+    options: &MergeOptionsJson,
+) -> Result<u64, MemvidError> {
+    let mut existing = match options.on_uri_conflict {
+        UriConflict::KeepBoth => HashMap::new(),
+        UriConflict::Skip | UriConflict::Replace => frames_by_uri(dest),
+    };
+
+    let frames: Vec<memvid_core::Frame> = src
+        .frames()
+        .filter(|f| matches!(f.status, FrameStatus::Active))
+        .collect();
+    let mut merged = 0;
+    for frame in frames {
+        // Chunks are re-created when their parent document is put.
+        if frame.parent_id.is_some() {
+            continue;
+        }
+
+        if let Some(uri) = &frame.uri {
+            match options.on_uri_conflict {
+                UriConflict::Skip if existing.contains_key(uri) => continue,
+                UriConflict::Replace => {
+                    for id in existing.remove(uri).unwrap_or_default() {
+                        let seq = dest
+                            .as_mut()
+                            .delete_frame(id)
+                            .map_err(MemvidError::from_core_error)?;
+                        dest.record_delete(id);
+                        dest.record_sequence(seq);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let payload = src
+            .as_mut()
+            .frame_canonical_payload(frame.id)
+            .map_err(MemvidError::from_core_error)?;
+        let (_, deduplicated) =
+            put_with_options(dest, &payload, PutOptionsJson::from_frame(frame))?;
+        if !deduplicated {
+            merged += 1;
+        }
+    }
+
+    dest.commit().map_err(MemvidError::from_core_error)?;
+    Ok(merged)
+}
+
+/// Open both files and merge `src` into `dest`.
+fn merge_paths(
+    dest_path: &Path,
+    src_path: &Path,
+    options: &MergeOptionsJson,
+) -> Result<u64, MemvidError> {
+    let same_file = match (dest_path.canonicalize(), src_path.canonicalize()) {
+        (Ok(dest), Ok(src)) => dest == src,
+        _ => false,
+    };
+    if same_file {
+        return Err(MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            "cannot merge a memory into itself",
+        ));
+    }
+
+    let src = Memvid::open_read_only(src_path).map_err(MemvidError::from_core_error)?;
+    let mut src = MemvidHandle::new_read_only(src, src_path.to_path_buf());
+    let dest = Memvid::open(dest_path).map_err(MemvidError::from_core_error)?;
+    let mut dest = MemvidHandle::new(dest, dest_path.to_path_buf());
+    merge_frames(&mut dest, &mut src, options)
+}
+
+/// Merge the active frames of one memory file into another.
+///
+/// Opens `dest` for writing and `src` read-only, puts each active document
+/// frame of `src` into `dest` with its payload and metadata (URI, title,
+/// timestamp, track, kind, tags, labels, extra metadata), then commits
+/// `dest`. Core indexes the new frames on commit and rebuilds their chunks,
+/// so frame IDs in `dest` are assigned anew; deleted source frames are not
+/// carried over. Frames whose idempotency key already exists in `dest` are
+/// not merged again.
+///
+/// Neither file may be open elsewhere, including through a handle in this
+/// process.
+///
+/// # Parameters
+///
+/// - `dest_path`: Memory to merge into (UTF-8 encoded, null-terminated)
+/// - `src_path`: Memory to merge from (UTF-8 encoded, null-terminated)
+/// - `options_json`: JSON options (may be NULL for defaults)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Number of frames merged on success, 0 on failure (check `error`).
+///
+/// # Options JSON Schema
+///
+/// ```json
+/// {
+///   "on_uri_conflict": "keep_both"  // "skip", "replace", or "keep_both"
+/// }
+/// ```
+///
+/// A conflict is a source frame whose URI matches an active frame in
+/// `dest`. `"skip"` keeps the destination frame, `"replace"` deletes every
+/// destination frame with that URI before adding the source frame, and
+/// `"keep_both"` (the default) adds it alongside them.
+///
+/// # Errors
+///
+/// - `InvalidQuery` if both paths name the same file
+/// - `Locked` if either file is open elsewhere
+///
+/// If a frame fails to merge, nothing is committed; frames staged before
+/// it stay in `dest`'s WAL, as with any uncommitted put.
+///
+/// # Safety
+///
+/// - `dest_path` and `src_path` must be valid null-terminated UTF-8 strings
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_merge(
+    dest_path: *const c_char,
+    src_path: *const c_char,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let dest_path = match unsafe { cstr_to_path(dest_path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let src_path = match unsafe { cstr_to_path(src_path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let options: MergeOptionsJson =
+        match unsafe { cstr_to_option_json(options_json, "options_json") } {
+            Ok(o) => o,
+            Err(e) => return unsafe { set_error(error, e) },
+        };
+
+    match merge_paths(&dest_path, &src_path, &options) {
+        Ok(merged) => {
+            unsafe { set_ok(error) };
+            merged
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
@@ -1,19 +1,28 @@
 //! Content mutation functions (put, commit).
 
-use crate::error::MemvidError;
-use crate::handle::MemvidHandle;
-use crate::util::{cstr_to_option_string, set_error, set_ok};
+use crate::alloc::free_slice;
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::handle::{content_hash, MemvidHandle, CONTENT_HASH_KEY, IDEMPOTENCY_KEY, SIDECAR_KEY};
+use crate::util::{
+    cstr_to_json, cstr_to_option_json, cstr_to_path, json_to_cstr, set_error, set_error_null,
+    set_ok, u64s_to_raw,
+};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use libc::size_t;
+use memmap2::Mmap;
 use memvid_core::PutOptions;
-use serde::Deserialize;
-use std::os::raw::c_char;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::os::raw::{c_char, c_void};
 
 /// JSON schema for PutOptions.
 ///
 /// This allows callers to pass options as a JSON string rather than
 /// requiring complex struct marshalling.
-#[derive(Debug, Default, Deserialize)]
-struct PutOptionsJson {
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub(crate) struct PutOptionsJson {
     /// Document URI
     #[serde(default)]
     uri: Option<String>,
@@ -47,15 +56,132 @@ struct PutOptionsJson {
     /// Enable triplet extraction
     #[serde(default)]
     extract_triplets: Option<bool>,
+    /// Store the content as-is, without enrichment
+    #[serde(default)]
+    verbatim: bool,
     /// Skip storing raw content (hash only)
     #[serde(default)]
     no_raw: Option<bool>,
     /// Deduplicate by hash
     #[serde(default)]
     dedup: Option<bool>,
+    /// Retry-safe key; a repeated key returns the earlier put's result
+    #[serde(default)]
+    idempotency_key: Option<String>,
+    /// Opaque binary blob stored with the frame (base64)
+    #[serde(default)]
+    sidecar_base64: Option<String>,
+    /// Stored frame tags, set by `with_frame_metadata`
+    #[serde(skip)]
+    frame_tags: Vec<String>,
+    /// Stored extra metadata, set by `with_frame_metadata`
+    #[serde(skip)]
+    extra_metadata: BTreeMap<String, String>,
+}
+
+/// JSON Schema of the put options, for `memvid_request_schema`.
+pub(crate) fn request_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(PutOptionsJson)
 }
 
+/// Maximum decoded sidecar size, to keep frame metadata lean.
+const MAX_SIDECAR_BYTES: usize = 16 * 1024;
+
 impl PutOptionsJson {
+    /// Check fields that core does not validate.
+    fn validate(&self) -> Result<(), MemvidError> {
+        if self.verbatim {
+            let conflicts = [
+                ("auto_tag", self.auto_tag == Some(true)),
+                ("extract_dates", self.extract_dates == Some(true)),
+                ("extract_triplets", self.extract_triplets == Some(true)),
+                ("search_text", self.search_text.is_some()),
+            ];
+            if let Some((field, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(MemvidError::new(
+                    MemvidErrorCode::SchemaValidation,
+                    format!("verbatim cannot be combined with {field}"),
+                ));
+            }
+        }
+        if let Some(sidecar) = &self.sidecar_base64 {
+            let bytes = BASE64.decode(sidecar).map_err(|e| {
+                MemvidError::new(
+                    MemvidErrorCode::SchemaValidation,
+                    format!("sidecar_base64 is not valid base64: {e}"),
+                )
+            })?;
+            if bytes.len() > MAX_SIDECAR_BYTES {
+                return Err(MemvidError::new(
+                    MemvidErrorCode::SchemaValidation,
+                    format!(
+                        "sidecar is {} bytes, limit is {MAX_SIDECAR_BYTES}",
+                        bytes.len()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Carry over a stored frame's tags and extra metadata.
+    ///
+    /// Reserved metadata keys fill the matching options unless those are
+    /// already set.
+    pub(crate) fn with_frame_metadata(
+        mut self,
+        tags: Vec<String>,
+        mut extra_metadata: BTreeMap<String, String>,
+    ) -> Self {
+        if let Some(key) = extra_metadata.remove(IDEMPOTENCY_KEY) {
+            self.idempotency_key.get_or_insert(key);
+        }
+        if let Some(sidecar) = extra_metadata.remove(SIDECAR_KEY) {
+            self.sidecar_base64.get_or_insert(sidecar);
+        }
+        self.frame_tags = tags;
+        self.extra_metadata = extra_metadata;
+        self
+    }
+
+    /// Apply `verbatim` for `data`: turn enrichment off and use UTF-8
+    /// content as its own search text, bypassing text extraction.
+    fn apply_verbatim(&mut self, data: &[u8]) {
+        if !self.verbatim {
+            return;
+        }
+        self.auto_tag = Some(false);
+        self.extract_dates = Some(false);
+        self.extract_triplets = Some(false);
+        if let Ok(text) = std::str::from_utf8(data) {
+            self.search_text = Some(text.to_string());
+        }
+    }
+
+    /// Defaults for an image payload of type `mime`.
+    ///
+    /// Sets `kind` to `mime` and, unless a caption was given as
+    /// `search_text`, keeps the binary payload out of the lexical index.
+    pub(crate) fn for_image(mut self, mime: &str) -> Self {
+        self.kind.get_or_insert_with(|| mime.to_string());
+        self.search_text.get_or_insert_with(String::new);
+        self
+    }
+
+    /// Put options that recreate a stored frame's metadata.
+    pub(crate) fn from_frame(frame: memvid_core::Frame) -> Self {
+        let options = Self {
+            uri: frame.uri,
+            title: frame.title,
+            timestamp: Some(frame.timestamp),
+            track: frame.track,
+            kind: frame.kind,
+            labels: Some(frame.labels),
+            ..Self::default()
+        };
+        options.with_frame_metadata(frame.tags, frame.extra_metadata)
+    }
+
     fn into_put_options(self) -> PutOptions {
         let mut builder = PutOptions::builder();
 
@@ -103,8 +229,163 @@ impl PutOptionsJson {
             builder = builder.dedup(dedup);
         }
 
-        builder.build()
+        let mut options = builder.build();
+        options.tags.extend(self.frame_tags);
+        options.extra_metadata.extend(self.extra_metadata);
+        if let Some(key) = self.idempotency_key {
+            options
+                .extra_metadata
+                .insert(IDEMPOTENCY_KEY.to_string(), key);
+        }
+        if let Some(sidecar) = self.sidecar_base64 {
+            options
+                .extra_metadata
+                .insert(SIDECAR_KEY.to_string(), sidecar);
+        }
+        options
+    }
+}
+
+/// Stage a put, with an embedding from the registered embedder if any.
+///
+/// The embedding is of `search_text` when set, otherwise of the content
+/// decoded as UTF-8 (lossily). Without the `vec` feature nothing is
+/// embedded. The payload's hash is stored in the frame's metadata and
+/// recorded for `memvid_contains_hash`. A failed autocommit since the last
+/// put fails this put instead, staging nothing.
+pub(crate) fn stage_put(
+    handle: &mut MemvidHandle,
+    data: &[u8],
+    mut options: PutOptions,
+) -> Result<u64, MemvidError> {
+    if let Some(e) = handle.take_autocommit_error() {
+        return Err(MemvidError::from_core_error(e));
+    }
+    let hash = content_hash(data);
+    options
+        .extra_metadata
+        .insert(CONTENT_HASH_KEY.to_string(), hash.clone());
+    #[cfg(feature = "vec")]
+    let staged = match handle.embedder() {
+        Some(embedder) => {
+            let embedding = match &options.search_text {
+                Some(text) => embedder.embed(text)?,
+                None => embedder.embed(&String::from_utf8_lossy(data))?,
+            };
+            handle
+                .as_mut()
+                .put_with_embedding_and_options(data, embedding, options)
+        }
+        None => handle.as_mut().put_bytes_with_options(data, options),
+    };
+    #[cfg(not(feature = "vec"))]
+    let staged = handle.as_mut().put_bytes_with_options(data, options);
+    let id = staged.map_err(MemvidError::from_core_error)?;
+    handle.record_content(hash, id);
+    Ok(id)
+}
+
+/// Parse a put's options JSON over the handle's default put options.
+///
+/// Fields present in `options_json` replace the defaults' field by field
+/// (`null` clears one); NULL uses the defaults alone.
+///
+/// # Safety
+///
+/// `options_json` must be a valid null-terminated UTF-8 string or NULL.
+pub(crate) unsafe fn merged_put_options(
+    handle: &MemvidHandle,
+    options_json: *const c_char,
+) -> Result<PutOptionsJson, MemvidError> {
+    let Some(defaults) = handle.default_put_options() else {
+        return unsafe { cstr_to_option_json(options_json, "options_json") };
+    };
+    let overrides: serde_json::Map<String, serde_json::Value> =
+        unsafe { cstr_to_option_json(options_json, "options_json") }?;
+    let mut merged = defaults.clone();
+    merged.extend(overrides);
+    serde_json::from_value(serde_json::Value::Object(merged)).map_err(MemvidError::json_parse)
+}
+
+/// Put content with parsed options, honoring the idempotency key.
+///
+/// Returns the put result and whether it was deduplicated by key.
+pub(crate) fn put_with_options(
+    handle: &mut MemvidHandle,
+    data: &[u8],
+    mut options: PutOptionsJson,
+) -> Result<(u64, bool), MemvidError> {
+    options.validate()?;
+    options.apply_verbatim(data);
+
+    let key = options.idempotency_key.clone();
+    if let Some(key) = &key {
+        if let Some(id) = handle.idempotency_key(key) {
+            return Ok((id, true));
+        }
+    }
+
+    let id = stage_put(handle, data, options.into_put_options())?;
+    handle.record_put();
+    if let Some(key) = key {
+        handle.record_idempotency_key(key, id);
+    }
+    Ok((id, false))
+}
+
+/// Check whether content is already stored.
+///
+/// Looks for an active frame whose payload has the same BLAKE3 hash as
+/// `data`, so a host can skip a put of unchanged content. Every put stores
+/// its payload's hash in the frame's metadata, so no payload is read: the
+/// first call on a handle indexes the stored hashes, and later puts and
+/// deletes through the handle keep the index current. Staged puts count,
+/// including `no_raw` ones. Frames put by a version of this library that
+/// did not store hashes are never found. Chunk frames are not compared;
+/// their parent document is.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `data`: Pointer to content bytes
+/// - `len`: Length of content in bytes
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 if a frame with the same content exists, 0 if not or on failure
+/// (check `error`).
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `data` must point to at least `len` bytes, or be NULL if `len` is 0
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_contains_hash(
+    handle: *mut MemvidHandle,
+    data: *const u8,
+    len: size_t,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if data.is_null() && len > 0 {
+        return unsafe { set_error(error, MemvidError::null_pointer("data")) };
     }
+
+    let slice = if len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(data, len) }
+    };
+
+    let found = handle.find_content(&content_hash(slice)).is_some();
+    unsafe { set_ok(error) };
+    i32::from(found)
 }
 
 /// Add content to the memory.
@@ -137,6 +418,10 @@ pub unsafe extern "C" fn memvid_put_bytes(
         None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
     };
 
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
     if data.is_null() && len > 0 {
         return unsafe { set_error(error, MemvidError::null_pointer("data")) };
     }
@@ -147,12 +432,20 @@ pub unsafe extern "C" fn memvid_put_bytes(
         unsafe { std::slice::from_raw_parts(data, len) }
     };
 
-    match handle.as_mut().put_bytes(slice) {
+    let staged = if handle.default_put_options().is_some() {
+        unsafe { merged_put_options(handle, std::ptr::null()) }
+            .and_then(|options| put_with_options(handle, slice, options))
+            .map(|(frame_id, _)| frame_id)
+    } else {
+        stage_put(handle, slice, PutOptions::default()).inspect(|_| handle.record_put())
+    };
+
+    match staged {
         Ok(frame_id) => {
             unsafe { set_ok(error) };
             frame_id
         }
-        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+        Err(e) => unsafe { set_error(error, e) },
     }
 }
 
@@ -185,11 +478,37 @@ pub unsafe extern "C" fn memvid_put_bytes(
 ///   "auto_tag": true,
 ///   "extract_dates": true,
 ///   "extract_triplets": true,
+///   "verbatim": false,
 ///   "no_raw": false,
-///   "dedup": false
+///   "dedup": false,
+///   "idempotency_key": "ingest-42",
+///   "sidecar_base64": "iVBORw0KGgo="
 /// }
 /// ```
 ///
+/// `verbatim` stores the content without enrichment, so
+/// `memvid_frame_content` returns exactly what was put. It turns off
+/// auto-tagging (`auto_tag`), date extraction (`extract_dates`), and
+/// triplet extraction (`extract_triplets`), and, for UTF-8 content, uses
+/// the content itself as `search_text`, bypassing memvid-core's text
+/// extraction and the metadata it adds to the frame text. Content that is
+/// not UTF-8 still goes through extraction; read its exact bytes with
+/// `memvid_frame_payload`. Combining `verbatim` with any of those options
+/// set to enable enrichment, or with `search_text`, fails with
+/// `SchemaValidation`.
+///
+/// `sidecar_base64` attaches an opaque binary blob (e.g. a thumbnail) to
+/// the frame, readable with `memvid_frame_sidecar`. It is limited to 16 KiB
+/// decoded; invalid base64 or a larger blob fails with `SchemaValidation`.
+///
+/// Defaults set with `memvid_set_default_put_options` are merged under
+/// these options, field by field.
+///
+/// A put whose `idempotency_key` was already used in this memory creates
+/// nothing and returns the earlier result: the frame ID of the committed
+/// frame carrying the key, or the value returned by the original put if it
+/// has not been committed yet. Deleting that frame frees the key.
+///
 /// # Safety
 ///
 /// - `handle` must be a valid handle
@@ -209,6 +528,10 @@ pub unsafe extern "C" fn memvid_put_bytes_with_options(
         None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
     };
 
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
     if data.is_null() && len > 0 {
         return unsafe { set_error(error, MemvidError::null_pointer("data")) };
     }
@@ -220,51 +543,1023 @@ pub unsafe extern "C" fn memvid_put_bytes_with_options(
     };
 
     // Parse options JSON
-    let options = match unsafe { cstr_to_option_string(options_json, "options_json") } {
-        Ok(Some(json_str)) => match serde_json::from_str::<PutOptionsJson>(&json_str) {
-            Ok(opts) => opts.into_put_options(),
-            Err(e) => return unsafe { set_error(error, MemvidError::json_parse(e)) },
-        },
-        Ok(None) => PutOptions::default(),
+    let options = match unsafe { merged_put_options(handle, options_json) } {
+        Ok(o) => o,
         Err(e) => return unsafe { set_error(error, e) },
     };
 
-    match handle.as_mut().put_bytes_with_options(slice, options) {
-        Ok(frame_id) => {
+    match put_with_options(handle, slice, options) {
+        Ok((frame_id, _)) => {
             unsafe { set_ok(error) };
             frame_id
         }
-        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+        Err(e) => unsafe { set_error(error, e) },
     }
 }
 
-/// Commit pending changes to disk.
+/// Add content with options, reporting idempotency-key deduplication.
+///
+/// Same as `memvid_put_bytes_with_options`, but writes 1 to `deduplicated`
+/// when the options' `idempotency_key` was already used and no frame was
+/// created, 0 otherwise.
 ///
 /// # Parameters
 ///
 /// - `handle`: Valid Memvid handle
+/// - `data`: Pointer to content bytes
+/// - `len`: Length of content in bytes
+/// - `options_json`: JSON string with PutOptions (NULL for defaults)
+/// - `deduplicated`: Out-parameter for the deduplication flag (may be NULL)
 /// - `error`: Out-parameter for error information
 ///
 /// # Returns
 ///
-/// 1 on success, 0 on failure.
+/// Frame ID on success, 0 on failure.
 ///
 /// # Safety
 ///
 /// - `handle` must be a valid handle
+/// - `data` must point to at least `len` bytes
+/// - `options_json` must be a valid UTF-8 string or NULL
+/// - `deduplicated` must be a valid pointer or NULL
 /// - `error` must be a valid pointer or NULL
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn memvid_commit(handle: *mut MemvidHandle, error: *mut MemvidError) -> i32 {
+pub unsafe extern "C" fn memvid_put_bytes_idempotent(
+    handle: *mut MemvidHandle,
+    data: *const u8,
+    len: size_t,
+    options_json: *const c_char,
+    deduplicated: *mut i32,
+    error: *mut MemvidError,
+) -> u64 {
     let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
         Some(h) => h,
         None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
     };
 
-    match handle.as_mut().commit() {
-        Ok(()) => {
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    if data.is_null() && len > 0 {
+        return unsafe { set_error(error, MemvidError::null_pointer("data")) };
+    }
+
+    let slice = if len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(data, len) }
+    };
+
+    let options = match unsafe { merged_put_options(handle, options_json) } {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    match put_with_options(handle, slice, options) {
+        Ok((frame_id, dedup)) => {
+            if let Some(d) = unsafe { deduplicated.as_mut() } {
+                *d = dedup as i32;
+            }
             unsafe { set_ok(error) };
-            1
+            frame_id
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
+
+/// Frames created by one put, for `memvid_put_bytes_detailed`.
+#[derive(Debug, Serialize)]
+struct PutResultJson {
+    parent_id: u64,
+    chunk_ids: Vec<u64>,
+    chunk_count: usize,
+}
+
+/// Add content with options, reporting every frame the put created.
+///
+/// Same as `memvid_put_bytes_with_options`, but also writes a JSON object
+/// with the parent frame and the chunk frames memvid-core split it into
+/// (the frames whose `parent_id` is the parent, with `chunk_index` set).
+/// Content too small to chunk has no chunk frames. For a put deduplicated
+/// by `idempotency_key`, the earlier put's frames are reported.
+///
+/// memvid-core adds chunk frames to its frame table only on commit, so the
+/// put is committed before its chunks are listed. To avoid committing
+/// anything else, the call fails with `FeatureUnavailable`, staging
+/// nothing, while other changes are staged or indexing is paused.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `data`: Pointer to content bytes
+/// - `len`: Length of content in bytes
+/// - `options_json`: JSON string with PutOptions (NULL for defaults)
+/// - `out_result_json`: Out-parameter for the result JSON
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Parent frame ID on success, 0 on failure.
+///
+/// # Ownership
+///
+/// Caller owns `*out_result_json`. Must call `memvid_string_free()` to free.
+///
+/// # Result JSON Schema
+///
+/// ```json
+/// {
+///   "parent_id": 12,
+///   "chunk_ids": [13, 14, 15],
+///   "chunk_count": 3
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `data` must point to at least `len` bytes
+/// - `options_json` must be a valid UTF-8 string or NULL
+/// - `out_result_json` must be a valid pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_put_bytes_detailed(
+    handle: *mut MemvidHandle,
+    data: *const u8,
+    len: size_t,
+    options_json: *const c_char,
+    out_result_json: *mut *mut c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let out_result_json = match unsafe { out_result_json.as_mut() } {
+        Some(o) => o,
+        None => {
+            return unsafe { set_error(error, MemvidError::null_pointer("out_result_json")) };
+        }
+    };
+
+    if data.is_null() && len > 0 {
+        return unsafe { set_error(error, MemvidError::null_pointer("data")) };
+    }
+
+    let slice = if len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(data, len) }
+    };
+
+    let options = match unsafe { merged_put_options(handle, options_json) } {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let blocker = if handle.indexing_paused() {
+        Some("indexing is paused")
+    } else if handle.has_pending_changes() {
+        Some("other changes are staged; commit them first")
+    } else {
+        None
+    };
+    if let Some(reason) = blocker {
+        let err = MemvidError::new(
+            MemvidErrorCode::FeatureUnavailable,
+            format!("memvid_put_bytes_detailed commits its put, but {reason}"),
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    let parent_id = match put_with_options(handle, slice, options) {
+        Ok((frame_id, _)) => frame_id,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+    if handle.has_pending_changes() {
+        if let Err(e) = handle.commit() {
+            return unsafe { set_error(error, MemvidError::from_core_error(e)) };
         }
-        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
     }
+
+    let chunk_ids: Vec<u64> = handle.children(parent_id).iter().map(|f| f.id).collect();
+    let result = PutResultJson {
+        parent_id,
+        chunk_count: chunk_ids.len(),
+        chunk_ids,
+    };
+    let json = unsafe { json_to_cstr(&result, error) };
+    if json.is_null() {
+        return 0;
+    }
+    *out_result_json = json;
+    parent_id
+}
+
+/// Result of `memvid_estimate_put`.
+#[derive(Debug, Serialize)]
+struct PutEstimateJson {
+    estimated_frames: usize,
+    estimated_stored_bytes: u64,
+    would_exceed_capacity: bool,
+}
+
+/// Commit `data` to a scratch memory and measure what it adds, returning
+/// the frame count and the growth of core's `size_bytes`.
+///
+/// The scratch memory lives in its own temp directory, removed when the
+/// measurement ends, and uses the handle's embedder so vector data is
+/// sized too.
+fn measure_put(
+    handle: &MemvidHandle,
+    data: &[u8],
+    options: PutOptionsJson,
+) -> Result<(usize, u64), MemvidError> {
+    let scratch_dir = tempfile::Builder::new()
+        .prefix("memvid-estimate-")
+        .tempdir()
+        .map_err(MemvidError::io)?;
+    let scratch_path = scratch_dir.path().join("estimate.mv2");
+    let memvid =
+        memvid_core::Memvid::create(&scratch_path).map_err(MemvidError::from_core_error)?;
+    let mut scratch = *MemvidHandle::new(memvid, scratch_path);
+    scratch.set_embedder(handle.embedder());
+
+    let size_bytes = |scratch: &MemvidHandle| {
+        scratch
+            .as_ref()
+            .stats()
+            .map(|stats| stats.size_bytes)
+            .map_err(MemvidError::from_core_error)
+    };
+    let empty = size_bytes(&scratch)?;
+    let (parent_id, _) = put_with_options(&mut scratch, data, options)?;
+    scratch.commit().map_err(MemvidError::from_core_error)?;
+    let frames = scratch.children(parent_id).len() + 1;
+    Ok((frames, size_bytes(&scratch)?.saturating_sub(empty)))
+}
+
+/// Estimate what a put would add, without changing the memory.
+///
+/// The content is put with the same options (merged over the handle's
+/// default put options) and committed into a scratch memory in a fresh
+/// temp directory, removed afterwards, so memvid-core's own chunking,
+/// compression, and the handle's registered embedder decide the result.
+/// The memory's file and directory are never touched. A put that
+/// `idempotency_key` would deduplicate is estimated at zero frames. Works
+/// on read-only handles.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `data`: Pointer to content bytes
+/// - `len`: Length of content in bytes
+/// - `options_json`: JSON string with PutOptions (NULL for defaults)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with the estimate on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "estimated_frames": 4,
+///   "estimated_stored_bytes": 18432,
+///   "would_exceed_capacity": false
+/// }
+/// ```
+///
+/// `estimated_frames` counts the document frame and its chunk frames.
+/// `estimated_stored_bytes` is how much memvid-core's `size_bytes` (see
+/// `memvid_stats`) grew when the put was committed to the empty scratch
+/// memory: compressed payloads plus their index and manifest entries.
+/// `would_exceed_capacity` compares it with the memory's remaining
+/// capacity, so a put estimated just under the limit may still fail with
+/// `CapacityExceeded`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `data` must point to at least `len` bytes, or be NULL if `len` is 0
+/// - `options_json` must be a valid UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_estimate_put(
+    handle: *mut MemvidHandle,
+    data: *const u8,
+    len: size_t,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if data.is_null() && len > 0 {
+        return unsafe { set_error_null(error, MemvidError::null_pointer("data")) };
+    }
+
+    let slice = if len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(data, len) }
+    };
+
+    let options = match unsafe { merged_put_options(handle, options_json) } {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+    let deduplicated = options
+        .idempotency_key
+        .as_ref()
+        .is_some_and(|key| handle.idempotency_key(key).is_some());
+
+    let (estimated_frames, estimated_stored_bytes) = if deduplicated {
+        (0, 0)
+    } else {
+        match measure_put(handle, slice, options) {
+            Ok(measured) => measured,
+            Err(e) => return unsafe { set_error_null(error, e) },
+        }
+    };
+    let remaining = match handle.as_ref().stats() {
+        Ok(stats) => stats.remaining_capacity_bytes,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let estimate = PutEstimateJson {
+        estimated_frames,
+        estimated_stored_bytes,
+        would_exceed_capacity: estimated_stored_bytes > remaining,
+    };
+    unsafe { json_to_cstr(&estimate, error) }
+}
+
+/// Add the contents of a file with options (JSON configuration).
+///
+/// The file is memory-mapped rather than read into a buffer, so large files
+/// are paged in by the OS as they are consumed instead of being copied into
+/// host or library memory first. The file must not be modified while the
+/// call runs.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `path`: Path of the file to add (null-terminated UTF-8 string)
+/// - `options_json`: JSON string with PutOptions (NULL for defaults), as
+///   for `memvid_put_bytes_with_options`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Frame ID on success, 0 on failure.
+///
+/// # Errors
+///
+/// - `NullPointer` if `path` is NULL
+/// - `Io` if the file cannot be opened or mapped (with the OS error text)
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `path` must be a valid null-terminated UTF-8 string
+/// - `options_json` must be a valid UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_put_file(
+    handle: *mut MemvidHandle,
+    path: *const c_char,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let options = match unsafe { merged_put_options(handle, options_json) } {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let file = match std::fs::File::open(&path) {
+        Ok(f) => f,
+        Err(e) => return unsafe { set_error(error, MemvidError::io(e)) },
+    };
+    let len = match file.metadata() {
+        Ok(m) => m.len(),
+        Err(e) => return unsafe { set_error(error, MemvidError::io(e)) },
+    };
+    // Zero-length mappings are rejected on some platforms
+    let map = if len == 0 {
+        None
+    } else {
+        match unsafe { Mmap::map(&file) } {
+            Ok(m) => Some(m),
+            Err(e) => return unsafe { set_error(error, MemvidError::io(e)) },
+        }
+    };
+    let data = map.as_deref().unwrap_or(&[]);
+
+    match put_with_options(handle, data, options) {
+        Ok((frame_id, _)) => {
+            unsafe { set_ok(error) };
+            frame_id
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
+
+/// One item of a batch put.
+///
+/// Mirrors the arguments of `memvid_put_bytes_with_options`.
+#[repr(C)]
+pub struct MemvidPutItem {
+    /// Pointer to content bytes (may be NULL if `len` is 0)
+    pub data: *const u8,
+    /// Length of content in bytes
+    pub len: size_t,
+    /// JSON string with PutOptions, or NULL for a plain put
+    pub options_json: *const c_char,
+}
+
+/// Put one batch item.
+///
+/// # Safety
+///
+/// The item's pointers must satisfy `memvid_put_batch`'s requirements.
+unsafe fn put_item(handle: &mut MemvidHandle, item: &MemvidPutItem) -> Result<u64, MemvidError> {
+    if item.data.is_null() && item.len > 0 {
+        return Err(MemvidError::null_pointer("data"));
+    }
+    let slice = if item.len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(item.data, item.len) }
+    };
+
+    if item.options_json.is_null() && handle.default_put_options().is_none() {
+        let id = stage_put(handle, slice, PutOptions::default())?;
+        handle.record_put();
+        return Ok(id);
+    }
+    let options = unsafe { merged_put_options(handle, item.options_json) }?;
+    put_with_options(handle, slice, options).map(|(id, _)| id)
+}
+
+/// Add many items in one call.
+///
+/// Each item is put exactly as `memvid_put_bytes` (NULL `options_json`) or
+/// `memvid_put_bytes_with_options` would, in order. Nothing is committed.
+/// On the first failing item the error is set and the batch stops; items
+/// before it stay put (uncommitted) and are reported through `out_count`
+/// and the returned array, so the caller can retry from item `*out_count`.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `items`: Array of `count` items
+/// - `count`: Number of items
+/// - `out_count`: Out-parameter for the number of items put
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Array of `*out_count` frame IDs parallel to `items` (all of them on
+/// success), or NULL if no items were put.
+///
+/// # Ownership
+///
+/// Caller owns the returned array. Must call `memvid_u64_array_free()`
+/// with the length from `out_count`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `items` must point to `count` valid items, or be NULL if `count` is 0
+/// - Each item's `data` must point to at least `len` bytes, and its
+///   `options_json` must be a valid UTF-8 string or NULL
+/// - `out_count` must be a valid pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_put_batch(
+    handle: *mut MemvidHandle,
+    items: *const MemvidPutItem,
+    count: size_t,
+    out_count: *mut size_t,
+    error: *mut MemvidError,
+) -> *mut u64 {
+    let out_count = match unsafe { out_count.as_mut() } {
+        Some(c) => c,
+        None => return unsafe { set_error_null(error, MemvidError::null_pointer("out_count")) },
+    };
+    *out_count = 0;
+
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error_null(error, e) };
+    }
+
+    if items.is_null() && count > 0 {
+        return unsafe { set_error_null(error, MemvidError::null_pointer("items")) };
+    }
+    let items = if count == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(items, count) }
+    };
+
+    let mut ids = Vec::with_capacity(items.len());
+    let mut failure = None;
+    for item in items {
+        match unsafe { put_item(handle, item) } {
+            Ok(id) => ids.push(id),
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        }
+    }
+
+    let count = ids.len();
+    let ptr = match u64s_to_raw(ids) {
+        Ok(p) => p,
+        Err(e) => {
+            *out_count = 0;
+            return unsafe { set_error_null(error, e) };
+        }
+    };
+    *out_count = count;
+    match failure {
+        Some(e) => unsafe { set_error::<()>(error, e) },
+        None => unsafe { set_ok(error) },
+    }
+    ptr
+}
+
+/// Free an array of u64 values returned by the FFI layer.
+///
+/// # Safety
+///
+/// - `ptr` must be an array returned by an FFI function, or NULL
+/// - `len` must be the length reported alongside `ptr`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_u64_array_free(ptr: *mut u64, len: size_t) {
+    if !ptr.is_null() {
+        unsafe { free_slice(ptr, len) };
+    }
+}
+
+/// Commit pending changes to disk.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_commit(handle: *mut MemvidHandle, error: *mut MemvidError) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    match handle.commit() {
+        Ok(()) => {
+            unsafe { set_ok(error) };
+            1
+        }
+        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Outcome of a commit, for `memvid_commit_detailed`.
+#[derive(Debug, Serialize)]
+struct CommitResultJson {
+    frames_added: u64,
+    bytes_written: u64,
+    wal_truncated: bool,
+}
+
+/// Commit pending changes, reporting what the commit wrote.
+///
+/// Same as `memvid_commit`, but also writes a JSON object describing the
+/// commit, so a host can enforce a disk quota without calling
+/// `memvid_stats` before and after. memvid-core does not report the bytes
+/// a commit writes, so `bytes_written` is the growth of the file (0 if it
+/// did not grow); `wal_truncated` is true if the commit checkpointed the
+/// write-ahead log, shrinking it. `frames_added` counts the puts committed,
+/// not their chunk frames.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `out_result_json`: Out-parameter for the result JSON
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Ownership
+///
+/// Caller owns `*out_result_json`. Must call `memvid_string_free()` to free.
+///
+/// # Result JSON Schema
+///
+/// ```json
+/// {
+///   "frames_added": 3,
+///   "bytes_written": 65536,
+///   "wal_truncated": true
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `out_result_json` must be a valid pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_commit_detailed(
+    handle: *mut MemvidHandle,
+    out_result_json: *mut *mut c_char,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let out_result_json = match unsafe { out_result_json.as_mut() } {
+        Some(o) => o,
+        None => {
+            return unsafe { set_error(error, MemvidError::null_pointer("out_result_json")) };
+        }
+    };
+
+    let before = match handle.as_ref().stats() {
+        Ok(s) => s,
+        Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    };
+    let frames_added = handle.pending_puts();
+    if let Err(e) = handle.commit() {
+        return unsafe { set_error(error, MemvidError::from_core_error(e)) };
+    }
+    let after = match handle.as_ref().stats() {
+        Ok(s) => s,
+        Err(e) => return unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    };
+
+    let result = CommitResultJson {
+        frames_added,
+        bytes_written: after.size_bytes.saturating_sub(before.size_bytes),
+        wal_truncated: after.wal_bytes < before.wal_bytes,
+    };
+    let json = unsafe { json_to_cstr(&result, error) };
+    if json.is_null() {
+        return 0;
+    }
+    *out_result_json = json;
+    1
+}
+
+/// Fsync the bytes already written to the memory file, without committing.
+///
+/// memvid-core has no durability step short of a full commit and does not
+/// expose its WAL writer, so this cannot flush WAL entries core still
+/// buffers in memory. It only fsyncs what core has already written to the
+/// file, through a second descriptor; staged changes are durable only once
+/// `memvid_commit` returns.
+///
+/// Nothing is checkpointed: indexes and the manifest are unchanged, so
+/// staged puts stay invisible to search (and `memvid_stats` index counts)
+/// until `memvid_commit`. Flushing a read-only handle is a no-op.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure (`Io` if the file cannot be synced).
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_flush(handle: *mut MemvidHandle, error: *mut MemvidError) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if !handle.read_only() {
+        if let Err(e) = handle.fsync_written() {
+            return unsafe { set_error(error, MemvidError::io(e)) };
+        }
+    }
+
+    unsafe { set_ok(error) };
+    1
+}
+
+/// Commit progress phase: about to commit. Returning nonzero cancels.
+pub const COMMIT_PHASE_START: u32 = 0;
+
+/// Commit progress phase: commit finished. The return value is ignored.
+pub const COMMIT_PHASE_DONE: u32 = 1;
+
+/// Commit progress callback type.
+///
+/// Receives the phase (`COMMIT_PHASE_*`), progress as `done` of `total`
+/// staged puts, and the caller's `user_data`. Returning nonzero from
+/// `COMMIT_PHASE_START` aborts the commit with `Cancelled`.
+pub type MemvidCommitProgressFn =
+    extern "C" fn(phase: u32, done: u64, total: u64, user_data: *mut c_void) -> i32;
+
+/// Commit pending changes to disk, reporting progress to a callback.
+///
+/// memvid-core flushes the WAL, merges the index, and writes the manifest
+/// in a single call with no intermediate progress, so the callback brackets
+/// that call: `COMMIT_PHASE_START` with `done = 0` before it, and
+/// `COMMIT_PHASE_DONE` with `done = total` after it succeeds. `total` is
+/// the number of puts staged on this handle since the last commit.
+/// Cancelling at `COMMIT_PHASE_START` leaves the staged puts uncommitted.
+///
+/// The callback runs on the calling thread. Calls back into `handle` from
+/// the callback fail with `InvalidHandle`.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `progress_fn`: Progress callback (see `MemvidCommitProgressFn`)
+/// - `user_data`: Opaque pointer passed through to `progress_fn`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure or cancellation.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `progress_fn` must be a valid function pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_commit_with_progress(
+    handle: *mut MemvidHandle,
+    progress_fn: Option<MemvidCommitProgressFn>,
+    user_data: *mut c_void,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    let progress_fn = match progress_fn {
+        Some(f) => f,
+        None => return unsafe { set_error(error, MemvidError::null_pointer("progress_fn")) },
+    };
+
+    let total = handle.pending_puts();
+    let cancel = handle.run_callback(|| progress_fn(COMMIT_PHASE_START, 0, total, user_data));
+    if cancel != 0 {
+        let err = MemvidError::new(MemvidErrorCode::Cancelled, "commit cancelled by callback");
+        return unsafe { set_error(error, err) };
+    }
+
+    if let Err(e) = handle.commit() {
+        return unsafe { set_error(error, MemvidError::from_core_error(e)) };
+    }
+
+    handle.run_callback(|| progress_fn(COMMIT_PHASE_DONE, total, total, user_data));
+    unsafe { set_ok(error) };
+    1
+}
+
+/// Pause or resume indexing for a burst of writes.
+///
+/// memvid-core indexes frames when they are committed and has no separate
+/// indexing switch, so pausing holds back the commits this handle makes on
+/// its own: autocommit (`memvid_set_autocommit`) does not run while
+/// paused. Puts are staged in the write-ahead log as usual and, like any
+/// uncommitted put, are invisible to search, which reports
+/// `"index_stale": true`. Resuming commits the staged puts, building their
+/// index entries in one catch-up pass.
+///
+/// `memvid_commit` stays allowed while paused. Because memvid-core cannot
+/// commit without indexing, it commits and indexes everything staged so
+/// far; later puts are staged again until the next commit or resume.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `paused`: Nonzero to pause, 0 to resume
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure. If the catch-up commit fails, indexing
+/// stays paused.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_set_indexing_paused(
+    handle: *mut MemvidHandle,
+    paused: i32,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if paused == 0 && handle.indexing_paused() && handle.pending_puts() > 0 {
+        if let Err(e) = handle.commit() {
+            return unsafe { set_error(error, MemvidError::from_core_error(e)) };
+        }
+    }
+    handle.set_indexing_paused(paused != 0);
+    unsafe { set_ok(error) };
+    1
+}
+
+/// Commit automatically once enough puts are staged.
+///
+/// After a put brings the number of puts staged since the last commit to
+/// `every_n_puts`, the handle commits inline, as `memvid_commit` would.
+/// The threshold is checked on puts only, so it takes effect from the next
+/// put; staged deletes are committed along with the puts. While indexing
+/// is paused no autocommit runs, and resuming commits as usual.
+///
+/// A failed autocommit does not fail the put that triggered it: that put
+/// is staged and its result returned. The commit error is instead
+/// returned by the next put, which stages nothing. The threshold puts stay
+/// staged, so the put after that retries the commit, as does an explicit
+/// `memvid_commit`.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `every_n_puts`: Pending puts that trigger a commit; 0 disables
+///   autocommit (the default)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_set_autocommit(
+    handle: *mut MemvidHandle,
+    every_n_puts: u64,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    handle.set_autocommit(every_n_puts);
+    unsafe { set_ok(error) };
+    1
+}
+
+/// Set options applied to every put on the handle.
+///
+/// Stores a baseline options object that `memvid_put_bytes`,
+/// `memvid_put_bytes_with_options`, `memvid_put_bytes_idempotent`,
+/// `memvid_put_bytes_detailed`, `memvid_put_batch`, `memvid_put_file`,
+/// and `memvid_put_image` merge under their own options: a field set in a
+/// call's `options_json` replaces the default's, field by field, and an
+/// explicit `null` clears it. Merging happens on the top-level fields, so
+/// a call's `tags` or `labels` replace the defaults' rather than adding to
+/// them. Passing NULL clears the defaults.
+///
+/// The defaults are validated like a put's options, but take no
+/// `idempotency_key`, since every put would then share one key. JSONL
+/// import and merge recreate frames from their source and ignore the
+/// defaults.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `options_json`: JSON put options (see `memvid_put_bytes_with_options`),
+///   or NULL to clear
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure. On failure the previous defaults stay.
+///
+/// # Errors
+///
+/// - `JsonParse` if `options_json` is not a JSON object of put options
+/// - `SchemaValidation` for invalid options, including an
+///   `idempotency_key`
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_set_default_put_options(
+    handle: *mut MemvidHandle,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if options_json.is_null() {
+        handle.set_default_put_options(None);
+        unsafe { set_ok(error) };
+        return 1;
+    }
+
+    let defaults: serde_json::Map<String, serde_json::Value> =
+        match unsafe { cstr_to_json(options_json, "options_json") } {
+            Ok(d) => d,
+            Err(e) => return unsafe { set_error(error, e) },
+        };
+    let options: PutOptionsJson =
+        match serde_json::from_value(serde_json::Value::Object(defaults.clone())) {
+            Ok(o) => o,
+            Err(e) => return unsafe { set_error(error, MemvidError::json_parse(e)) },
+        };
+    if let Err(e) = options.validate() {
+        return unsafe { set_error(error, e) };
+    }
+    if options.idempotency_key.is_some() {
+        let err = MemvidError::new(
+            MemvidErrorCode::SchemaValidation,
+            "default put options cannot set idempotency_key",
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    handle.set_default_put_options(Some(defaults));
+    unsafe { set_ok(error) };
+    1
 }
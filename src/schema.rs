@@ -0,0 +1,53 @@
+//! JSON Schemas of request JSON accepted by the FFI.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::sync::OnceLock;
+
+/// Render a schema as a C string, once per process.
+fn cached(
+    cell: &'static OnceLock<CString>,
+    schema: fn() -> schemars::schema::RootSchema,
+) -> *const c_char {
+    cell.get_or_init(|| {
+        let json = serde_json::to_string(&schema()).unwrap_or_default();
+        CString::new(json).unwrap_or_default()
+    })
+    .as_ptr()
+}
+
+/// JSON Schema of a request type.
+///
+/// # Parameters
+///
+/// - `kind`: Request type: `"search"`, `"ask"`, `"put_options"`,
+///   `"timeline"`, or `"doctor"`
+///
+/// # Returns
+///
+/// Static JSON Schema string (draft-07), or NULL if `kind` is NULL, not
+/// UTF-8, or unknown. Do not free this string.
+///
+/// # Safety
+///
+/// `kind` must be a valid null-terminated string or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_request_schema(kind: *const c_char) -> *const c_char {
+    static SEARCH: OnceLock<CString> = OnceLock::new();
+    static ASK: OnceLock<CString> = OnceLock::new();
+    static PUT_OPTIONS: OnceLock<CString> = OnceLock::new();
+    static TIMELINE: OnceLock<CString> = OnceLock::new();
+    static DOCTOR: OnceLock<CString> = OnceLock::new();
+
+    if kind.is_null() {
+        return std::ptr::null();
+    }
+    match unsafe { CStr::from_ptr(kind) }.to_str() {
+        Ok("search") => cached(&SEARCH, crate::search::request_schema),
+        Ok("ask") => cached(&ASK, crate::ask::request_schema),
+        Ok("put_options") => cached(&PUT_OPTIONS, crate::mutation::request_schema),
+        Ok("timeline") => cached(&TIMELINE, crate::timeline::request_schema),
+        Ok("doctor") => cached(&DOCTOR, crate::doctor::request_schema),
+        _ => std::ptr::null(),
+    }
+}
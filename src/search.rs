@@ -1,13 +1,21 @@
 //! Search functions.
 
-use crate::error::MemvidError;
+use crate::alloc::{free_cstring, free_slice};
+use crate::error::{memvid_error_code_str, MemvidError, MemvidErrorCode};
 use crate::handle::MemvidHandle;
-use crate::util::{cstr_to_string, set_error_null, set_ok, string_to_cstr};
+use crate::util::{
+    bytes_to_raw, cstr_to_json, cstr_to_string, json_to_cstr, set_error, set_error_null, set_ok,
+    string_to_cstr,
+};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::{c_void, CString};
 use std::os::raw::c_char;
+use std::time::{Duration, Instant};
 
 /// JSON schema for SearchRequest input.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 struct SearchRequestJson {
     /// Search query string
     query: String,
@@ -26,8 +34,101 @@ struct SearchRequestJson {
     /// Pagination cursor
     #[serde(default)]
     cursor: Option<String>,
+    /// Per-field score multipliers (e.g. {"title": 3.0, "body": 1.0})
+    #[serde(default)]
+    field_boosts: Option<HashMap<SearchField, f32>>,
+    /// zstd-compress context and hit text (`memvid_search_bytes` only)
+    #[serde(default)]
+    compress_context: bool,
+    /// Time budget in milliseconds, checked between phases
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    /// Only return hits on these tracks
+    #[serde(default)]
+    include_tracks: Option<Vec<String>>,
+    /// Drop hits on these tracks
+    #[serde(default)]
+    exclude_tracks: Option<Vec<String>>,
+    /// Only return hits with all of these tags (as passed to put)
+    #[serde(default)]
+    tags: Option<HashMap<String, String>>,
+    /// Only return hits with at least one of these labels
+    #[serde(default)]
+    labels: Option<Vec<String>>,
+    /// Drop hits scoring below this
+    #[serde(default)]
+    min_score: Option<f32>,
+    /// Frames moved to the top of the results, in this order
+    #[serde(default)]
+    pinned_frames: Option<Vec<u64>>,
+    /// Show pinned frames even when they do not match the query
+    #[serde(default)]
+    pin_unmatched: bool,
+    /// Also report the hit total without uri/scope/track filters
+    #[serde(default)]
+    count_unfiltered: bool,
+    /// Map each hit's range to byte offsets into the stored payload
+    #[serde(default)]
+    byte_offsets: bool,
+    /// Retrieval engine (default: lex)
+    #[serde(default)]
+    mode: SearchModeJson,
+    /// Precomputed query embedding for the vector engine
+    #[serde(default)]
+    query_embedding: Option<Vec<f32>>,
+    /// Search as of this frame ID
+    #[serde(default)]
+    as_of_frame: Option<u64>,
+    /// Search as of this Unix timestamp (seconds)
+    #[serde(default)]
+    as_of_ts: Option<i64>,
+    /// Skip the sketch prefilter and score every candidate in the index
+    #[serde(default)]
+    no_sketch: bool,
+    /// Analyzer the query is meant for, checked against the index
+    #[serde(default)]
+    language: Option<String>,
+    /// Skip snippets, returning hits with empty `text`
+    #[serde(default)]
+    titles_only: bool,
+    /// Cap on the combined `context`, in characters
+    #[serde(default)]
+    context_budget_chars: Option<usize>,
+}
+
+/// JSON Schema of the search request, for `memvid_request_schema`.
+pub(crate) fn request_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(SearchRequestJson)
+}
+
+/// Search engine selection, as in `memvid_ask`'s `mode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum SearchModeJson {
+    #[default]
+    Lex,
+    Sem,
+    Hybrid,
 }
 
+/// Hit fields that can be boosted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SearchField {
+    Title,
+    Body,
+    Uri,
+    Tags,
+    Labels,
+}
+
+/// Candidate pool multiplier when hits are filtered or rescored here.
+const OVERFETCH: usize = 4;
+
+/// Rank offset for reciprocal rank fusion of lexical and vector hits.
+#[cfg(feature = "vec")]
+const RRF_K: f32 = 60.0;
+
 fn default_top_k() -> usize {
     10
 }
@@ -47,13 +148,37 @@ impl SearchRequestJson {
             cursor: self.cursor,
             #[cfg(feature = "temporal_track")]
             temporal: None,
-            as_of_frame: None,
-            as_of_ts: None,
-            no_sketch: false,
+            as_of_frame: self.as_of_frame,
+            as_of_ts: self.as_of_ts,
+            no_sketch: self.no_sketch,
         }
     }
 }
 
+/// Analyzers memvid-core's lexical index is built with.
+///
+/// Core tokenizes and stems with one fixed analyzer and takes no analyzer
+/// hint, so this is also the only language a memory can be created with.
+pub(crate) const LEX_ANALYZERS: &[&str] = &["en"];
+
+/// Check that `language` names an analyzer built into the lexical index,
+/// failing with `code` otherwise.
+pub(crate) fn check_language(
+    language: Option<&str>,
+    code: MemvidErrorCode,
+) -> Result<(), MemvidError> {
+    match language {
+        Some(language) if !LEX_ANALYZERS.contains(&language) => Err(MemvidError::new(
+            code,
+            format!(
+                "no {language} analyzer in the lexical index; available: {}",
+                LEX_ANALYZERS.join(", ")
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// JSON schema for SearchResponse output.
 #[derive(Debug, Serialize)]
 struct SearchResponseJson {
@@ -71,10 +196,18 @@ struct SearchResponseJson {
     next_cursor: Option<String>,
     /// Search engine used
     engine: String,
+    /// Whether the time budget ran out before post-processing
+    timed_out: bool,
+    /// Hit total without filters (only with `count_unfiltered`)
+    unfiltered_total: Option<usize>,
+    /// Whether puts are staged unindexed while indexing is paused
+    index_stale: bool,
+    /// Whether `context_budget_chars` left hit text out of `context`
+    context_truncated: bool,
 }
 
 /// JSON schema for individual search hit.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct SearchHitJson {
     /// Result rank (1-based)
     rank: usize,
@@ -88,22 +221,28 @@ struct SearchHitJson {
     text: String,
     /// Character range in document (start, end)
     range: (usize, usize),
+    /// Byte range in the stored payload (only with `byte_offsets`)
+    payload_range: Option<(usize, usize)>,
     /// Number of keyword matches
     matches: usize,
+    /// Character ranges of matched query terms within `text`
+    highlights: Vec<(usize, usize)>,
     /// Relevance score
     score: Option<f32>,
     /// Tags
     tags: Vec<String>,
     /// Labels
     labels: Vec<String>,
+    /// Track the frame belongs to
+    track: Option<String>,
 }
 
 impl From<&memvid_core::SearchHit> for SearchHitJson {
     fn from(hit: &memvid_core::SearchHit) -> Self {
-        let (tags, labels) = hit
+        let (tags, labels, track) = hit
             .metadata
             .as_ref()
-            .map(|m| (m.tags.clone(), m.labels.clone()))
+            .map(|m| (m.tags.clone(), m.labels.clone(), m.track.clone()))
             .unwrap_or_default();
 
         Self {
@@ -113,14 +252,305 @@ impl From<&memvid_core::SearchHit> for SearchHitJson {
             title: hit.title.clone(),
             text: hit.text.clone(),
             range: hit.range,
+            payload_range: None,
             matches: hit.matches,
+            highlights: Vec::new(),
             score: hit.score,
             tags,
             labels,
+            track,
+        }
+    }
+}
+
+/// Lowercased alphanumeric terms of a query.
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+impl SearchHitJson {
+    /// Set `highlights` to the words of `text` equal to a query term.
+    ///
+    /// Words are alphanumeric runs compared case-insensitively; offsets are
+    /// in characters, end exclusive.
+    fn highlight(&mut self, terms: &[String]) {
+        let mut highlights = Vec::new();
+        let mut word = String::new();
+        let mut start = 0;
+        for (i, c) in self.text.chars().chain([' ']).enumerate() {
+            if c.is_alphanumeric() {
+                if word.is_empty() {
+                    start = i;
+                }
+                word.push(c);
+            } else if !word.is_empty() {
+                if terms.contains(&word.to_lowercase()) {
+                    highlights.push((start, i));
+                }
+                word.clear();
+            }
+        }
+        self.highlights = highlights;
+    }
+
+    /// Whether every query term appears in the given field.
+    fn field_matches(&self, field: SearchField, terms: &[String]) -> bool {
+        let haystack = match field {
+            SearchField::Title => self.title.clone().unwrap_or_default(),
+            SearchField::Body => self.text.clone(),
+            SearchField::Uri => self.uri.clone(),
+            SearchField::Tags => self.tags.join(" "),
+            SearchField::Labels => self.labels.join(" "),
+        }
+        .to_lowercase();
+        !terms.is_empty() && terms.iter().all(|t| haystack.contains(t.as_str()))
+    }
+}
+
+impl SearchResponseJson {
+    /// Rescore hits by field boosts, best first.
+    ///
+    /// A hit's score is multiplied by the largest boost among the fields
+    /// containing every query term; hits without a score start from 1.0.
+    fn apply_field_boosts(&mut self, boosts: &HashMap<SearchField, f32>) {
+        let terms = query_terms(&self.query);
+
+        for hit in &mut self.hits {
+            let boost = boosts
+                .iter()
+                .filter(|(field, _)| hit.field_matches(**field, &terms))
+                .map(|(_, boost)| *boost)
+                .fold(None, |max: Option<f32>, b| {
+                    Some(max.map_or(b, |m| m.max(b)))
+                })
+                .unwrap_or(1.0);
+            hit.score = Some(hit.score.unwrap_or(1.0) * boost);
+        }
+
+        self.hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        for (i, hit) in self.hits.iter_mut().enumerate() {
+            hit.rank = i + 1;
+        }
+    }
+
+    /// Fill in `highlights` for hits with keyword matches.
+    ///
+    /// memvid-core does not expose match positions, so query terms are
+    /// located in each snippet here. Hits without keyword matches (vector
+    /// and pinned hits) keep no highlights.
+    fn highlight_terms(&mut self) {
+        let terms = query_terms(&self.query);
+        for hit in self.hits.iter_mut().filter(|hit| hit.matches > 0) {
+            hit.highlight(&terms);
+        }
+    }
+
+    /// Move pinned frames to the top, in pin order.
+    ///
+    /// Pinned frames missing from the hits are taken from `unmatched`, when
+    /// given; otherwise they are left out.
+    fn pin_frames(&mut self, pinned: &[u64], mut unmatched: Vec<SearchHitJson>) {
+        let mut top = Vec::with_capacity(pinned.len());
+        for id in pinned {
+            if let Some(pos) = self.hits.iter().position(|h| h.frame_id == *id) {
+                top.push(self.hits.remove(pos));
+            } else if let Some(pos) = unmatched.iter().position(|h| h.frame_id == *id) {
+                top.push(unmatched.swap_remove(pos));
+            }
+        }
+        self.hits.splice(0..0, top);
+        for (i, hit) in self.hits.iter_mut().enumerate() {
+            hit.rank = i + 1;
         }
     }
 }
 
+/// Hit for a frame that did not come from the lexical index.
+///
+/// The snippet is the start of the frame text; there are no matches or
+/// score. Frames that are missing or not active yield `None`.
+fn frame_hit(
+    handle: &mut MemvidHandle,
+    frame_id: u64,
+    snippet_chars: usize,
+) -> Option<SearchHitJson> {
+    let frame = handle.as_mut().frame_by_id(frame_id).ok()?;
+    if !matches!(frame.status, memvid_core::types::FrameStatus::Active) {
+        return None;
+    }
+    let text: String = if snippet_chars == 0 {
+        String::new()
+    } else {
+        handle
+            .as_mut()
+            .frame_text_by_id(frame_id)
+            .unwrap_or_default()
+            .chars()
+            .take(snippet_chars)
+            .collect()
+    };
+
+    Some(SearchHitJson {
+        rank: 0,
+        frame_id,
+        uri: frame.uri.clone().unwrap_or_default(),
+        title: frame.title.clone(),
+        range: (0, text.chars().count()),
+        payload_range: None,
+        text,
+        matches: 0,
+        highlights: Vec::new(),
+        score: None,
+        tags: frame.tags.clone(),
+        labels: frame.labels.clone(),
+        track: frame.track.clone(),
+    })
+}
+
+impl SearchResponseJson {
+    /// Fill in `payload_range` for hits whose payload is their frame text.
+    ///
+    /// A hit's character range maps to payload bytes only when the stored
+    /// payload is UTF-8 identical to the extracted frame text; otherwise
+    /// (binary formats such as PDF, or custom `search_text`) it stays null.
+    fn map_payload_ranges(&mut self, handle: &mut MemvidHandle) {
+        let mut texts: HashMap<u64, Option<String>> = HashMap::new();
+        for hit in &mut self.hits {
+            let text = texts.entry(hit.frame_id).or_insert_with(|| {
+                let payload = handle.as_mut().frame_canonical_payload(hit.frame_id).ok()?;
+                let text = handle.as_mut().frame_text_by_id(hit.frame_id).ok()?;
+                (payload == text.as_bytes()).then_some(text)
+            });
+            hit.payload_range = text.as_deref().map(|text| {
+                let byte_offset = |chars: usize| {
+                    text.char_indices()
+                        .nth(chars)
+                        .map_or(text.len(), |(offset, _)| offset)
+                };
+                (byte_offset(hit.range.0), byte_offset(hit.range.1))
+            });
+        }
+    }
+
+    /// Empty every hit's snippet and the combined context.
+    ///
+    /// Ranges become `(0, 0)` and highlights and payload ranges go with the
+    /// text.
+    fn drop_snippets(&mut self) {
+        self.context.clear();
+        for hit in &mut self.hits {
+            hit.text.clear();
+            hit.range = (0, 0);
+            hit.payload_range = None;
+            hit.highlights.clear();
+        }
+    }
+
+    /// Keep hits whose track passes the include/exclude lists.
+    ///
+    /// Hits without a track never match `include`. `total_hits` is
+    /// recomputed from the remaining hits.
+    fn filter_tracks(&mut self, include: Option<&[String]>, exclude: Option<&[String]>) {
+        self.hits.retain(|hit| {
+            let listed = |tracks: &[String]| hit.track.as_ref().is_some_and(|t| tracks.contains(t));
+            include.is_none_or(listed) && !exclude.is_some_and(listed)
+        });
+        self.refresh_filtered();
+    }
+
+    /// Keep hits with every tag in `tags` and any label in `labels`.
+    ///
+    /// `total_hits` is recomputed from the remaining hits.
+    fn filter_tags_labels(&mut self, tags: &[String], labels: Option<&[String]>) {
+        self.hits.retain(|hit| {
+            tags.iter().all(|t| hit.tags.contains(t))
+                && labels.is_none_or(|labels| labels.iter().any(|l| hit.labels.contains(l)))
+        });
+        self.refresh_filtered();
+    }
+
+    /// Drop hits scoring below `min_score`, keeping hits without a score.
+    fn filter_min_score(&mut self, min_score: f32) {
+        self.hits
+            .retain(|hit| hit.score.is_none_or(|score| score >= min_score));
+        self.refresh_filtered();
+    }
+
+    /// Recompute `total_hits` and ranks after dropping hits.
+    fn refresh_filtered(&mut self) {
+        self.total_hits = self.hits.len();
+        for (i, hit) in self.hits.iter_mut().enumerate() {
+            hit.rank = i + 1;
+        }
+    }
+
+    /// Keep the `top_k` hits after the first `offset`.
+    ///
+    /// `next_cursor` resumes after the kept hits while more remain, either
+    /// in the post-processed hits or beyond the pool core returned.
+    fn page_by_offset(&mut self, offset: usize, top_k: usize, generation: u64) {
+        let end = offset.saturating_add(top_k);
+        let more = self.hits.len() > end || self.next_cursor.is_some();
+        self.hits.drain(..offset.min(self.hits.len()));
+        self.hits.truncate(top_k);
+        self.next_cursor = more.then(|| format!("{generation}.@{end}"));
+    }
+
+    /// Rebuild `context` from the returned hits, once they are final.
+    fn rebuild_context(&mut self) {
+        self.context = self
+            .hits
+            .iter()
+            .map(|hit| hit.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+    }
+
+    /// Rebuild `context` from the hits within `budget` characters.
+    fn fit_context(&mut self, budget: usize) {
+        let texts = self.hits.iter().map(|hit| hit.text.as_str());
+        (self.context, self.context_truncated) = budget_context(texts, budget);
+    }
+}
+
+/// Join hit texts in rank order into a context of at most `budget` chars.
+///
+/// Whole texts are kept until the next one would overflow the budget; it
+/// and every lower-ranked text are left out. A top text longer than the
+/// budget is cut to it. Returns the context and whether anything was left
+/// out.
+pub(crate) fn budget_context<'a>(
+    texts: impl IntoIterator<Item = &'a str>,
+    budget: usize,
+) -> (String, bool) {
+    let mut context = String::new();
+    let mut used = 0;
+    for (i, text) in texts.into_iter().enumerate() {
+        let separator = if i == 0 { 0 } else { 2 };
+        let chars = text.chars().count();
+        if used + separator + chars > budget {
+            if i == 0 {
+                context.extend(text.chars().take(budget));
+            }
+            return (context, true);
+        }
+        if i > 0 {
+            context.push_str("\n\n");
+        }
+        context.push_str(text);
+        used += separator + chars;
+    }
+    (context, false)
+}
+
 impl From<&memvid_core::SearchResponse> for SearchResponseJson {
     fn from(resp: &memvid_core::SearchResponse) -> Self {
         Self {
@@ -131,10 +561,447 @@ impl From<&memvid_core::SearchResponse> for SearchResponseJson {
             context: resp.context.clone(),
             next_cursor: resp.next_cursor.clone(),
             engine: format!("{:?}", resp.engine),
+            timed_out: false,
+            unfiltered_total: None,
+            index_stale: false,
+            context_truncated: false,
         }
     }
 }
 
+/// Encoding of the payload section of a search buffer.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum PayloadCodec {
+    None,
+    Zstd,
+}
+
+/// Where the text moved out of the response lives in the decoded payload.
+#[derive(Debug, Serialize)]
+struct PayloadLayoutJson {
+    codec: PayloadCodec,
+    /// Length of the decoded payload in bytes
+    uncompressed_len: usize,
+    /// Byte (offset, len) of the combined context
+    context: (usize, usize),
+    /// Byte (offset, len) of each hit's text, in hit order
+    texts: Vec<(usize, usize)>,
+}
+
+/// Envelope JSON at the head of a search buffer.
+#[derive(Debug, Serialize)]
+struct SearchEnvelopeJson {
+    #[serde(flatten)]
+    response: SearchResponseJson,
+    payload: PayloadLayoutJson,
+}
+
+impl SearchResponseJson {
+    /// Encode as a search buffer, moving context and hit text into the payload.
+    ///
+    /// Layout: u32 LE envelope length, envelope JSON, then the payload
+    /// section through the end of the buffer.
+    fn into_buffer(mut self, compress: bool) -> Result<Vec<u8>, MemvidError> {
+        let mut text = Vec::new();
+        let mut push = |s: String| {
+            let range = (text.len(), s.len());
+            text.extend_from_slice(s.as_bytes());
+            range
+        };
+        let context = push(std::mem::take(&mut self.context));
+        let texts = self
+            .hits
+            .iter_mut()
+            .map(|hit| push(std::mem::take(&mut hit.text)))
+            .collect();
+
+        let uncompressed_len = text.len();
+        let (codec, payload) = if compress {
+            let compressed = zstd::bulk::compress(&text, zstd::DEFAULT_COMPRESSION_LEVEL)
+                .map_err(MemvidError::io)?;
+            (PayloadCodec::Zstd, compressed)
+        } else {
+            (PayloadCodec::None, text)
+        };
+
+        let envelope = SearchEnvelopeJson {
+            response: self,
+            payload: PayloadLayoutJson {
+                codec,
+                uncompressed_len,
+                context,
+                texts,
+            },
+        };
+        let envelope = serde_json::to_vec(&envelope).map_err(MemvidError::json_serialize)?;
+
+        let mut buffer = Vec::with_capacity(4 + envelope.len() + payload.len());
+        buffer.extend_from_slice(&(envelope.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(&envelope);
+        buffer.extend_from_slice(&payload);
+        Ok(buffer)
+    }
+}
+
+/// Nearest frames to `embedding`, honoring the request's uri/scope filters.
+///
+/// Hits carry the start of the frame text and a score of
+/// `1 / (1 + distance)`, so closer frames score higher.
+#[cfg(feature = "vec")]
+fn vector_search(
+    handle: &mut MemvidHandle,
+    search: &memvid_core::SearchRequest,
+    embedding: &[f32],
+) -> Result<SearchResponseJson, MemvidError> {
+    let started = Instant::now();
+    let filtered = search.uri.is_some() || search.scope.is_some();
+    let k = if filtered {
+        search.top_k.saturating_mul(OVERFETCH)
+    } else {
+        search.top_k
+    };
+    let nearest = handle
+        .as_mut()
+        .search_vec(embedding, k)
+        .map_err(MemvidError::from_core_error)?;
+
+    let mut hits = Vec::with_capacity(search.top_k);
+    for neighbor in nearest {
+        // The vector index has no history, so as-of bounds apply per frame.
+        if search
+            .as_of_frame
+            .is_some_and(|max| neighbor.frame_id > max)
+        {
+            continue;
+        }
+        if let Some(max) = search.as_of_ts {
+            let frame = handle.as_mut().frame_by_id(neighbor.frame_id);
+            if frame.is_ok_and(|frame| frame.timestamp > max) {
+                continue;
+            }
+        }
+        let Some(hit) = frame_hit(handle, neighbor.frame_id, search.snippet_chars) else {
+            continue;
+        };
+        let outside_uri = search.uri.as_ref().is_some_and(|uri| *uri != hit.uri);
+        let outside_scope = search
+            .scope
+            .as_deref()
+            .is_some_and(|scope| !hit.uri.starts_with(scope));
+        if outside_uri || outside_scope {
+            continue;
+        }
+        hits.push(SearchHitJson {
+            rank: hits.len() + 1,
+            score: Some(1.0 / (1.0 + neighbor.distance)),
+            ..hit
+        });
+        if hits.len() == search.top_k {
+            break;
+        }
+    }
+
+    Ok(SearchResponseJson {
+        query: search.query.clone(),
+        elapsed_ms: started.elapsed().as_millis(),
+        total_hits: hits.len(),
+        context: hits
+            .iter()
+            .map(|hit| hit.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        hits,
+        next_cursor: None,
+        engine: "Vector".to_string(),
+        timed_out: false,
+        unfiltered_total: None,
+        index_stale: false,
+        context_truncated: false,
+    })
+}
+
+#[cfg(not(feature = "vec"))]
+fn vector_search(
+    _handle: &mut MemvidHandle,
+    _search: &memvid_core::SearchRequest,
+    _embedding: &[f32],
+) -> Result<SearchResponseJson, MemvidError> {
+    Err(MemvidError::new(
+        MemvidErrorCode::VecNotEnabled,
+        "vector search requires the vec feature",
+    ))
+}
+
+impl SearchResponseJson {
+    /// Merge vector hits into lexical hits by reciprocal rank fusion.
+    ///
+    /// Each hit scores `1 / (60 + rank)` per list it appears in; a vector
+    /// hit on a frame already hit lexically adds to that frame's first
+    /// lexical hit. Hits are re-sorted by fused score.
+    #[cfg(feature = "vec")]
+    fn fuse_vector_hits(&mut self, vector: SearchResponseJson) {
+        let rrf = |rank: usize| 1.0 / (RRF_K + rank as f32);
+        let mut first_by_frame = HashMap::new();
+        let mut fused: Vec<(f32, SearchHitJson)> = Vec::new();
+        for (i, hit) in self.hits.drain(..).enumerate() {
+            first_by_frame.entry(hit.frame_id).or_insert(fused.len());
+            fused.push((rrf(i + 1), hit));
+        }
+        for (i, hit) in vector.hits.into_iter().enumerate() {
+            match first_by_frame.get(&hit.frame_id) {
+                Some(&pos) => fused[pos].0 += rrf(i + 1),
+                None => {
+                    first_by_frame.insert(hit.frame_id, fused.len());
+                    fused.push((rrf(i + 1), hit));
+                }
+            }
+        }
+        fused.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        self.hits = fused
+            .into_iter()
+            .enumerate()
+            .map(|(i, (score, hit))| SearchHitJson {
+                rank: i + 1,
+                score: Some(score),
+                ..hit
+            })
+            .collect();
+        self.total_hits = self.total_hits.max(self.hits.len());
+        self.context = self
+            .hits
+            .iter()
+            .map(|hit| hit.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        self.elapsed_ms += vector.elapsed_ms;
+        self.engine = format!("Hybrid({}+Vector)", self.engine);
+    }
+}
+
+/// Where a search `cursor` resumes.
+enum PageCursor {
+    /// memvid-core's own cursor
+    Core(String),
+    /// Number of post-processed hits already returned
+    Offset(usize),
+}
+
+/// Strip the handle generation from a `next_cursor` token.
+///
+/// Tokens are issued as `<generation>.<core cursor>`, or as
+/// `<generation>.@<offset>` for hits filtered or rescored here; a token
+/// from another generation was issued before a refresh and no longer
+/// applies.
+fn untag_cursor(cursor: &str, generation: u64) -> Result<PageCursor, MemvidError> {
+    let invalid = || {
+        MemvidError::new(
+            MemvidErrorCode::InvalidCursor,
+            "search cursor is malformed or was invalidated by memvid_refresh",
+        )
+    };
+    match cursor.split_once('.') {
+        Some((tag, inner)) if tag.parse::<u64>().ok() == Some(generation) => {
+            match inner.strip_prefix('@') {
+                Some(offset) => offset
+                    .parse()
+                    .map(PageCursor::Offset)
+                    .map_err(|_| invalid()),
+                None => Ok(PageCursor::Core(inner.to_string())),
+            }
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Run a search request and apply FFI-side post-processing.
+fn run_search(
+    handle: &mut MemvidHandle,
+    mut request: SearchRequestJson,
+) -> Result<SearchResponseJson, MemvidError> {
+    check_language(request.language.as_deref(), MemvidErrorCode::InvalidQuery)?;
+    let titles_only = request.titles_only || request.snippet_chars == 0;
+    if titles_only {
+        request.snippet_chars = 0;
+    }
+    let cursor = request
+        .cursor
+        .take()
+        .map(|cursor| untag_cursor(&cursor, handle.generation()))
+        .transpose()?;
+    let deadline = request
+        .timeout_ms
+        .map(|ms| Instant::now() + Duration::from_millis(ms));
+    let top_k = request.top_k;
+    let boosts = request.field_boosts.clone();
+    let include_tracks = request.include_tracks.clone();
+    let exclude_tracks = request.exclude_tracks.clone();
+    let track_filter = include_tracks.is_some() || exclude_tracks.is_some();
+    // Tags as put stores them, so the filter matches frames put with these tags
+    let required_tags = request.tags.as_ref().map(|tags| {
+        tags.iter()
+            .fold(memvid_core::PutOptions::builder(), |b, (k, v)| b.tag(k, v))
+            .build()
+            .tags
+    });
+    let labels = request.labels.clone();
+    let min_score = request.min_score;
+    let metadata_filter = required_tags.is_some() || labels.is_some();
+    let pinned = request.pinned_frames.clone();
+    let pin_unmatched = request.pin_unmatched;
+    let snippet_chars = request.snippet_chars;
+    let byte_offsets = request.byte_offsets;
+    let context_budget = request.context_budget_chars.filter(|_| !titles_only);
+    let mode = request.mode;
+    let mut query_embedding = request.query_embedding.clone();
+    if mode != SearchModeJson::Lex && !cfg!(feature = "vec") {
+        return Err(MemvidError::new(
+            MemvidErrorCode::VecNotEnabled,
+            "sem and hybrid search require the vec feature",
+        ));
+    }
+    if mode != SearchModeJson::Lex && query_embedding.is_none() {
+        if let Some(embedder) = handle.embedder() {
+            query_embedding = Some(embedder.embed(&request.query)?);
+        }
+    }
+    if mode == SearchModeJson::Sem && query_embedding.is_none() {
+        return Err(MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            "sem search requires query_embedding or a registered embedder",
+        ));
+    }
+    // Hits filtered or rescored here come from an overfetched pool, so core's
+    // cursor would resume after the whole pool; they are paged by offset
+    // into the post-processed hits instead.
+    let overfetch = boosts.is_some() || track_filter || metadata_filter || pinned.is_some();
+    let offset = match cursor {
+        Some(PageCursor::Offset(offset)) => Some(offset),
+        Some(PageCursor::Core(_)) if overfetch => {
+            return Err(MemvidError::new(
+                MemvidErrorCode::InvalidCursor,
+                "search cursor was issued for a request without filters or boosts",
+            ));
+        }
+        Some(PageCursor::Core(cursor)) => {
+            request.cursor = Some(cursor);
+            None
+        }
+        None => overfetch.then_some(0),
+    };
+    let filtered =
+        request.uri.is_some() || request.scope.is_some() || track_filter || metadata_filter;
+    let unfiltered = (request.count_unfiltered && filtered).then(|| memvid_core::SearchRequest {
+        uri: None,
+        scope: None,
+        cursor: None,
+        ..request.clone().into_search_request()
+    });
+
+    let mut search = request.into_search_request();
+    if let Some(offset) = offset {
+        let pool = offset.saturating_add(top_k);
+        search.top_k = if overfetch {
+            pool.saturating_mul(OVERFETCH)
+        } else {
+            pool
+        };
+    }
+
+    let mut response_json = match (mode, query_embedding) {
+        (SearchModeJson::Sem, Some(embedding)) => vector_search(handle, &search, &embedding)?,
+        #[cfg(feature = "vec")]
+        (SearchModeJson::Hybrid, Some(embedding)) => {
+            let vector = vector_search(handle, &search, &embedding)?;
+            let lexical = handle
+                .as_mut()
+                .search(search)
+                .map_err(MemvidError::from_core_error)?;
+            let mut response_json = SearchResponseJson::from(&lexical);
+            response_json.fuse_vector_hits(vector);
+            response_json
+        }
+        _ => {
+            let response = handle
+                .as_mut()
+                .search(search)
+                .map_err(MemvidError::from_core_error)?;
+            SearchResponseJson::from(&response)
+        }
+    };
+    response_json.index_stale = handle.index_stale();
+    let generation = handle.generation();
+    if offset.is_none() {
+        response_json.next_cursor = response_json
+            .next_cursor
+            .take()
+            .map(|cursor| format!("{generation}.{cursor}"));
+    }
+
+    if track_filter {
+        response_json.filter_tracks(include_tracks.as_deref(), exclude_tracks.as_deref());
+    }
+    if metadata_filter {
+        let tags = required_tags.unwrap_or_default();
+        response_json.filter_tags_labels(&tags, labels.as_deref());
+    }
+
+    // Retrieval cannot be interrupted, so the deadline is checked after it.
+    // Filters still run on expiry, since they decide which hits may be
+    // returned; only optional work is skipped: the unfiltered count,
+    // boosts, highlights, and byte offsets.
+    let timed_out = deadline.is_some_and(|d| Instant::now() >= d);
+    response_json.timed_out = timed_out;
+    if !timed_out {
+        if let Some(unfiltered) = unfiltered {
+            let total = handle
+                .as_mut()
+                .search(unfiltered)
+                .map_err(MemvidError::from_core_error)?
+                .total_hits;
+            response_json.unfiltered_total = Some(total);
+        }
+        if let Some(boosts) = boosts {
+            response_json.apply_field_boosts(&boosts);
+        }
+    }
+    if let Some(min_score) = min_score {
+        response_json.filter_min_score(min_score);
+    }
+    if let Some(pinned) = pinned {
+        let unmatched = if pin_unmatched {
+            pinned
+                .iter()
+                .filter(|id| !response_json.hits.iter().any(|h| h.frame_id == **id))
+                .filter_map(|id| frame_hit(handle, *id, snippet_chars))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        response_json.pin_frames(&pinned, unmatched);
+    }
+    match offset {
+        Some(offset) => response_json.page_by_offset(offset, top_k, generation),
+        None => response_json.hits.truncate(top_k),
+    }
+    if offset.is_some() || min_score.is_some() {
+        response_json.rebuild_context();
+    }
+    if titles_only {
+        response_json.drop_snippets();
+    } else if !timed_out {
+        response_json.highlight_terms();
+        if byte_offsets {
+            response_json.map_payload_ranges(handle);
+        }
+    }
+    if let Some(budget) = context_budget {
+        response_json.fit_context(budget);
+    }
+    Ok(response_json)
+}
+
 /// Search the memory.
 ///
 /// # Parameters
@@ -160,10 +1027,149 @@ impl From<&memvid_core::SearchResponse> for SearchResponseJson {
 ///   "snippet_chars": 200,
 ///   "uri": "mv2://optional/filter",
 ///   "scope": "mv2://scope/prefix",
-///   "cursor": "pagination_token"
+///   "cursor": "pagination_token",
+///   "field_boosts": {"title": 3.0, "body": 1.0},
+///   "compress_context": false,
+///   "timeout_ms": 500,
+///   "include_tracks": ["email", "chat"],
+///   "exclude_tracks": ["logs"],
+///   "tags": {"topic": "rust"},
+///   "labels": ["reviewed"],
+///   "min_score": 0.2,
+///   "pinned_frames": [12, 7],
+///   "pin_unmatched": false,
+///   "count_unfiltered": false,
+///   "byte_offsets": false,
+///   "mode": "lex",
+///   "query_embedding": [0.12, -0.03, 0.88],
+///   "as_of_frame": null,
+///   "as_of_ts": null,
+///   "no_sketch": false,
+///   "language": "en",
+///   "titles_only": false,
+///   "context_budget_chars": 8000
 /// }
 /// ```
 ///
+/// `titles_only` (or `"snippet_chars": 0`) is a fast path for typeahead:
+/// memvid-core is asked for no snippet text, pinned and vector hits skip
+/// reading their frame text, and every hit is returned with an empty
+/// `text`, a `range` of `[0, 0]`, no `highlights`, and a null
+/// `payload_range`; `context` is empty. IDs, titles, URIs, scores, and
+/// metadata are unaffected. A `body` field boost has no text to match.
+///
+/// `language` names the analyzer the query is meant for. memvid-core
+/// builds its lexical index with one fixed analyzer (English, `"en"`) and
+/// cannot switch analyzers per query or per memory, so any other language
+/// fails with `InvalidQuery`, listing the available analyzers, rather than
+/// silently searching with the default. Omitting it uses the index's
+/// analyzer.
+///
+/// `context_budget_chars` caps the combined `context` at that many
+/// characters. Hit texts are joined in rank order and cut on hit
+/// boundaries: the first hit that would overflow the budget and every
+/// lower-ranked hit are left out of `context` (they stay in `hits`). Only
+/// a top hit longer than the whole budget is cut mid-text.
+/// `context_truncated` reports whether anything was left out.
+///
+/// `as_of_frame` and `as_of_ts` run the search against the memory as it was
+/// at that frame ID or Unix timestamp (seconds), as in `memvid_ask`, e.g.
+/// to reproduce the results of an earlier query. Vector hits are limited
+/// to frames with an ID and timestamp up to the bounds.
+///
+/// `no_sketch` disables memvid-core's sketch-based candidate prefilter, so
+/// lexical retrieval scores the full index. It is slower and meant for
+/// ground-truth comparisons, e.g. when benchmarking recall.
+///
+/// `mode` selects the engine: `"lex"` (default) uses the lexical index;
+/// `"sem"` and `"hybrid"` need the `vec` feature and fail with
+/// `VecNotEnabled` without it. Without `query_embedding` (a vector with the
+/// index's dimension), the query is embedded by the embedder registered
+/// with `memvid_register_embedder`; with neither, `"sem"` fails with
+/// `InvalidQuery`. `"sem"` returns the nearest active frames with the
+/// start of the frame text as `text`, `matches: 0`, a score of
+/// `1 / (1 + distance)`, and no `next_cursor`. `"hybrid"` with an
+/// embedding merges lexical and vector hits by reciprocal rank fusion
+/// (each hit scores `1 / (60 + rank)` per engine); without one it runs
+/// lexically. `engine` reports what ran: the lexical engine's name,
+/// `"Vector"`, or `"Hybrid(<lexical>+Vector)"`. `count_unfiltered` always
+/// counts lexical hits.
+///
+/// `include_tracks` keeps only hits on the listed tracks (hits without a
+/// track are dropped); `exclude_tracks` drops hits on the listed tracks.
+/// Both compose with `uri`/`scope`. Tracks are filtered here over a pool
+/// of `top_k * 4` hits, so `total_hits` counts matching hits within that
+/// pool and `context` is rebuilt from the kept hits.
+///
+/// `tags` keeps only hits carrying all of the given tags, taking the same
+/// `{"key": "value"}` form as put options and matching the tags a put with
+/// them stores. `labels` keeps only hits with at least one of the listed
+/// labels. memvid-core's search has no metadata filters, so like tracks
+/// these are applied here over the `top_k * 4` pool, and compose with the
+/// other filters.
+///
+/// `min_score` drops hits scoring below the threshold; hits without a
+/// score are kept. It applies to final scores (after `field_boosts`) and
+/// filters after ranking, without retrieving a larger pool, so fewer than
+/// `top_k` hits may be returned. A pinned frame below the threshold is
+/// only shown with `pin_unmatched`.
+///
+/// `pinned_frames` moves the listed frames to the top of the hits, in list
+/// order, with the remaining slots filled normally. By default a pinned
+/// frame is only shown if it matches the query (within the `top_k * 4`
+/// pool, after track filters). With `"pin_unmatched": true` every active
+/// pinned frame is shown regardless of the query and filters; unmatched
+/// ones carry the start of the frame text as `text`, `matches: 0`, and a
+/// null `score`. Pinned hits count toward `top_k`.
+///
+/// `count_unfiltered` runs the query a second time without `uri`, `scope`,
+/// track, tag, and label filters and reports that run's `total_hits` as
+/// `unfiltered_total`, e.g. to show "12 of 340 matches". It is null unless
+/// requested and at least one of those filters is set, and is skipped when
+/// `timeout_ms` expires. It costs a second retrieval.
+///
+/// `byte_offsets` adds `payload_range`, the hit's `range` as `[start, end)`
+/// byte offsets into the stored payload. memvid-core keeps no mapping from
+/// extracted text back to the original bytes, so this is only available
+/// when the payload is UTF-8 text identical to the frame text; for other
+/// payloads (e.g. PDF) and without `byte_offsets`, `payload_range` is null.
+/// It is skipped when `timeout_ms` expires.
+///
+/// `index_stale` is true while puts are staged on this handle since its
+/// last commit, paused indexing (`memvid_set_indexing_paused`) or not;
+/// those puts are missing from the hits.
+///
+/// `timeout_ms` is checked cooperatively: memvid-core's retrieval cannot be
+/// interrupted, so the deadline is tested once retrieval returns. If it has
+/// passed, `timed_out` is true and optional post-processing is skipped:
+/// `field_boosts`, `highlights`, `count_unfiltered`, and `byte_offsets`.
+/// Track, tag, and label filters, `min_score` (against unboosted scores),
+/// and `pinned_frames` still apply. A single slow retrieval can therefore still
+/// overrun the budget.
+///
+/// `compress_context` is only supported by `memvid_search_bytes`; setting
+/// it here fails with `InvalidQuery`.
+///
+/// `field_boosts` keys are `title`, `body`, `uri`, `tags`, and `labels`.
+/// memvid-core's lexical index has no per-field query boosts, so they are
+/// applied here: a pool of `top_k * 4` hits is retrieved, each hit's score
+/// is multiplied by the largest boost among the fields containing every
+/// query term (`body` is the snippet text), and the best `top_k` are
+/// returned. Omitting `field_boosts` preserves index ordering.
+///
+/// `highlights` lists `[start, end)` character offsets within the hit's
+/// `text` of words equal to a query term, ignoring case. memvid-core does
+/// not expose its match positions, so terms are located in the snippet
+/// here: stemmed or fuzzy matches are not highlighted. Hits without
+/// keyword matches (vector and pinned hits) have an empty array.
+///
+/// `cursor` takes the `next_cursor` of an earlier response on the same
+/// handle. Cursors are invalidated by `memvid_refresh` and `memvid_vacuum`;
+/// a stale or malformed cursor fails with `InvalidCursor`. With track, tag,
+/// or label filters, `field_boosts`, or `pinned_frames`, pages are cut
+/// here from a pool grown to cover every page so far, and `context` holds
+/// only the returned hits; keep those parameters the same across pages.
+///
 /// # Response JSON Schema
 ///
 /// ```json
@@ -179,15 +1185,22 @@ impl From<&memvid_core::SearchResponse> for SearchResponseJson {
 ///       "title": "Document Title",
 ///       "text": "...matching text...",
 ///       "range": [100, 150],
+///       "payload_range": null,
 ///       "matches": 3,
+///       "highlights": [[3, 11]],
 ///       "score": 0.95,
 ///       "tags": ["tag1"],
-///       "labels": ["label1"]
+///       "labels": ["label1"],
+///       "track": "email"
 ///     }
 ///   ],
 ///   "context": "combined context text",
 ///   "next_cursor": "token_or_null",
-///   "engine": "Tantivy"
+///   "engine": "Tantivy",
+///   "timed_out": false,
+///   "unfiltered_total": null,
+///   "index_stale": false,
+///   "context_truncated": false
 /// }
 /// ```
 ///
@@ -218,23 +1231,633 @@ pub unsafe extern "C" fn memvid_search(
         Err(e) => return unsafe { set_error_null(error, MemvidError::json_parse(e)) },
     };
 
+    if request.compress_context {
+        let err = MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            "compress_context requires memvid_search_bytes",
+        );
+        return unsafe { set_error_null(error, err) };
+    }
+
     // Perform search
-    let response = match handle.as_mut().search(request.into_search_request()) {
+    let response_json = match run_search(handle, request) {
         Ok(r) => r,
-        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+        Err(e) => return unsafe { set_error_null(error, e) },
     };
 
     // Serialize response to JSON
-    let response_json = SearchResponseJson::from(&response);
     match serde_json::to_string(&response_json) {
-        Ok(s) => {
+        Ok(s) => unsafe { string_to_cstr(s, None, error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
+    }
+}
+
+/// Search the memory, returning a binary buffer.
+///
+/// Accepts the same request as `memvid_search`. With `"compress_context":
+/// true` the combined context and every hit's `text` are zstd-compressed,
+/// which shrinks large result sets with long snippets considerably.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `request_json`: JSON string with SearchRequest
+/// - `out_len`: Out-parameter for the buffer length in bytes
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Buffer on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned buffer. Must call `memvid_bytes_free()` with
+/// the length from `out_len`.
+///
+/// # Buffer Format
+///
+/// | Offset    | Size     | Contents                                  |
+/// |-----------|----------|-------------------------------------------|
+/// | 0         | 4        | Envelope length `N` (u32, little-endian)  |
+/// | 4         | `N`      | Envelope JSON (UTF-8)                     |
+/// | 4 + `N`   | rest     | Payload                                   |
+///
+/// The envelope is the `memvid_search` response with `context` and each
+/// hit's `text` set to `""`, plus a `payload` object:
+///
+/// ```json
+/// {
+///   "codec": "zstd",
+///   "uncompressed_len": 5120,
+///   "context": [0, 2048],
+///   "texts": [[2048, 1536], [3584, 1536]]
+/// }
+/// ```
+///
+/// Decode the payload according to `codec`: `"zstd"` is a single zstd frame
+/// decompressing to exactly `uncompressed_len` bytes (e.g. `ZSTD_decompress`
+/// into a buffer of that size); `"none"` is the raw bytes. The `context` and
+/// `texts[i]` pairs are `[offset, len]` byte ranges of UTF-8 text within the
+/// decoded payload, with `texts[i]` belonging to `hits[i]`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `request_json` must be a valid UTF-8 string
+/// - `out_len` must be a valid pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_search_bytes(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    out_len: *mut usize,
+    error: *mut MemvidError,
+) -> *mut u8 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let out_len = match unsafe { out_len.as_mut() } {
+        Some(l) => l,
+        None => return unsafe { set_error_null(error, MemvidError::null_pointer("out_len")) },
+    };
+
+    let request: SearchRequestJson = match unsafe { cstr_to_json(request_json, "request_json") } {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let compress = request.compress_context;
+    let buffer = match run_search(handle, request).and_then(|r| r.into_buffer(compress)) {
+        Ok(b) => b,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    match bytes_to_raw(buffer, out_len) {
+        Ok(ptr) => {
             unsafe { set_ok(error) };
-            string_to_cstr(s)
+            ptr
+        }
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Search the memory, writing the response JSON into a caller buffer.
+///
+/// Accepts the same request and produces the same JSON as `memvid_search`,
+/// without allocating the result. The JSON is written null-terminated and
+/// `*needed_len` is set to its size including the terminator. If that
+/// exceeds `out_buf_len`, nothing is written and the call fails with
+/// `BufferTooSmall`; grow the buffer to `*needed_len` and call again. Pass
+/// a NULL `out_buf` with `out_buf_len` 0 to query the size. Each call runs
+/// the search anew.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `request_json`: JSON string with SearchRequest
+/// - `out_buf`: Caller-owned buffer for the response JSON (may be NULL if `out_buf_len` is 0)
+/// - `out_buf_len`: Size of `out_buf` in bytes
+/// - `needed_len`: Out-parameter for the response size in bytes, including the terminator
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure (`BufferTooSmall` if `out_buf` is too small).
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `request_json` must be a valid UTF-8 string
+/// - `out_buf` must point to at least `out_buf_len` writable bytes
+/// - `needed_len` must be a valid pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_search_into(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    out_buf: *mut c_char,
+    out_buf_len: usize,
+    needed_len: *mut usize,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let needed_len = match unsafe { needed_len.as_mut() } {
+        Some(l) => l,
+        None => return unsafe { set_error(error, MemvidError::null_pointer("needed_len")) },
+    };
+
+    if out_buf.is_null() && out_buf_len > 0 {
+        return unsafe { set_error(error, MemvidError::null_pointer("out_buf")) };
+    }
+
+    let request: SearchRequestJson = match unsafe { cstr_to_json(request_json, "request_json") } {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    if request.compress_context {
+        let err = MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            "compress_context requires memvid_search_bytes",
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    let json = match run_search(handle, request)
+        .and_then(|r| serde_json::to_vec(&r).map_err(MemvidError::json_serialize))
+    {
+        Ok(j) => j,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let needed = json.len() + 1;
+    *needed_len = needed;
+    if needed > out_buf_len {
+        let err = MemvidError::new(
+            MemvidErrorCode::BufferTooSmall,
+            format!("response needs {needed} bytes, buffer has {out_buf_len}"),
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    unsafe {
+        std::ptr::copy_nonoverlapping(json.as_ptr(), out_buf as *mut u8, json.len());
+        *out_buf.add(json.len()) = 0;
+    }
+    unsafe { set_ok(error) };
+    1
+}
+
+/// Search hit callback type.
+///
+/// Receives one hit as a JSON object (same shape as an element of
+/// `memvid_search`'s `hits`). The string is only valid during the call.
+/// Return 0 to continue, nonzero to stop.
+pub type MemvidSearchHitFn = extern "C" fn(hit_json: *const c_char, user_data: *mut c_void) -> i32;
+
+/// Search the memory, passing each hit to a callback.
+///
+/// Accepts the same request as `memvid_search`, but instead of building
+/// one response string, serializes hits one at a time and passes each to
+/// `hit_fn` in rank order, so a large result set can be processed and
+/// discarded incrementally. Returning nonzero from `hit_fn` stops the
+/// stream early; that is not an error.
+///
+/// `hit_fn` runs synchronously on the calling thread, before this function
+/// returns. It must not call back into this handle: such calls fail with
+/// `InvalidHandle`.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `request_json`: JSON string with SearchRequest
+/// - `hit_fn`: Callback invoked once per hit
+/// - `user_data`: Opaque pointer passed through to `hit_fn`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success (including an early stop), 0 on failure.
+///
+/// # Errors
+///
+/// - `InvalidQuery` if the request sets `compress_context`
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `request_json` must be a valid UTF-8 string
+/// - `hit_fn` must be a valid function pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_search_stream(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    hit_fn: Option<MemvidSearchHitFn>,
+    user_data: *mut c_void,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let hit_fn = match hit_fn {
+        Some(f) => f,
+        None => return unsafe { set_error(error, MemvidError::null_pointer("hit_fn")) },
+    };
+
+    let request: SearchRequestJson = match unsafe { cstr_to_json(request_json, "request_json") } {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    if request.compress_context {
+        let err = MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            "compress_context requires memvid_search_bytes",
+        );
+        return unsafe { set_error(error, err) };
+    }
+
+    let response = match run_search(handle, request) {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    for hit in &response.hits {
+        let json = match serde_json::to_string(hit) {
+            Ok(j) => j,
+            Err(e) => return unsafe { set_error(error, MemvidError::json_serialize(e)) },
+        };
+        // Serialized JSON escapes control characters, so it has no interior NUL
+        let json = CString::new(json).unwrap_or_default();
+        if handle.run_callback(|| hit_fn(json.as_ptr(), user_data)) != 0 {
+            break;
+        }
+    }
+
+    unsafe { set_ok(error) };
+    1
+}
+
+/// Point in time for an as-of search.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+struct AsOfJson {
+    /// Only consider frames up to this frame ID
+    #[serde(default)]
+    frame: Option<u64>,
+    /// Only consider frames up to this Unix timestamp (seconds)
+    #[serde(default)]
+    ts: Option<i64>,
+}
+
+/// JSON schema for a search diff request.
+#[derive(Debug, Deserialize)]
+struct SearchDiffRequestJson {
+    #[serde(flatten)]
+    search: SearchRequestJson,
+    /// Earlier point in time
+    as_of_a: AsOfJson,
+    /// Later point in time
+    as_of_b: AsOfJson,
+}
+
+/// Hit that matched at both points under the same URI but a different frame.
+#[derive(Debug, Serialize)]
+struct ChangedHitJson {
+    before: SearchHitJson,
+    after: SearchHitJson,
+}
+
+/// JSON schema for a search diff response.
+#[derive(Debug, Serialize)]
+struct SearchDiffResponseJson {
+    query: String,
+    as_of_a: AsOfJson,
+    as_of_b: AsOfJson,
+    added: Vec<SearchHitJson>,
+    removed: Vec<SearchHitJson>,
+    changed: Vec<ChangedHitJson>,
+    unchanged: Vec<u64>,
+}
+
+impl SearchDiffResponseJson {
+    /// Compute the set difference of two result lists keyed on frame ID.
+    ///
+    /// A hit present only in `b` whose URI matched a different frame in `a`
+    /// is reported as changed: upserts write a new frame that supersedes the
+    /// old one under the same URI. A document chunked into several frames
+    /// can have several hits per URI, so hits are paired in rank order and
+    /// each frame in `a` is paired at most once.
+    fn diff(
+        query: String,
+        as_of_a: AsOfJson,
+        as_of_b: AsOfJson,
+        a: &[SearchHitJson],
+        b: &[SearchHitJson],
+    ) -> Self {
+        let ids_a: HashSet<u64> = a.iter().map(|h| h.frame_id).collect();
+        let ids_b: HashSet<u64> = b.iter().map(|h| h.frame_id).collect();
+
+        let mut only_a: HashMap<&str, VecDeque<&SearchHitJson>> = HashMap::new();
+        for hit in a.iter().filter(|h| !ids_b.contains(&h.frame_id)) {
+            only_a.entry(hit.uri.as_str()).or_default().push_back(hit);
+        }
+
+        let mut paired = HashSet::new();
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        let mut unchanged = Vec::new();
+        for hit in b {
+            if ids_a.contains(&hit.frame_id) {
+                unchanged.push(hit.frame_id);
+                continue;
+            }
+            let before = only_a
+                .get_mut(hit.uri.as_str())
+                .and_then(VecDeque::pop_front);
+            match before {
+                Some(before) => {
+                    paired.insert(before.frame_id);
+                    changed.push(ChangedHitJson {
+                        before: before.clone(),
+                        after: hit.clone(),
+                    });
+                }
+                None => added.push(hit.clone()),
+            }
+        }
+
+        // Whatever was not paired with a newer version is gone
+        let removed = a
+            .iter()
+            .filter(|h| !ids_b.contains(&h.frame_id) && !paired.contains(&h.frame_id))
+            .cloned()
+            .collect();
+
+        Self {
+            query,
+            as_of_a,
+            as_of_b,
+            added,
+            removed,
+            changed,
+            unchanged,
+        }
+    }
+}
+
+/// Search at two points in time and return the difference.
+///
+/// Runs the same search as `memvid_search` twice, once as of `as_of_a` and
+/// once as of `as_of_b`, and compares the hit sets by frame ID. Only the
+/// top `top_k` hits of each run are compared; raise `top_k` for a complete
+/// diff.
+///
+/// Content changes are detected through supersession: updating a document
+/// writes a new frame under the same URI. A hit in `b` whose URI matched a
+/// different frame in `a` is reported in `changed` rather than as an
+/// add/remove pair.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `request_json`: JSON string with search parameters plus `as_of_a`/`as_of_b`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with the diff, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Request JSON Schema
+///
+/// ```json
+/// {
+///   "query": "search terms",
+///   "top_k": 100,
+///   "as_of_a": {"ts": 1700000000},
+///   "as_of_b": {"frame": 420}
+/// }
+/// ```
+///
+/// Both snapshots run through the same filtering, scoring and pinning as
+/// `memvid_search`, so its other request fields apply to each. `cursor`
+/// and `compress_context` fail with `InvalidQuery`: the diff is neither
+/// paged nor compressed.
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "query": "search terms",
+///   "as_of_a": {"frame": null, "ts": 1700000000},
+///   "as_of_b": {"frame": 420, "ts": null},
+///   "added": [hit, ...],
+///   "removed": [hit, ...],
+///   "changed": [{"before": hit, "after": hit}],
+///   "unchanged": [12, 40]
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `request_json` must be a valid UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_search_diff(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let request: SearchDiffRequestJson = match unsafe { cstr_to_json(request_json, "request_json") }
+    {
+        Ok(r) => r,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let unsupported = if request.search.cursor.is_some() {
+        Some("cursor")
+    } else if request.search.compress_context {
+        Some("compress_context")
+    } else {
+        None
+    };
+    if let Some(field) = unsupported {
+        let e = MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            format!("{field} is not supported by memvid_search_diff"),
+        );
+        return unsafe { set_error_null(error, e) };
+    }
+
+    let mut hits = Vec::with_capacity(2);
+    for as_of in [request.as_of_a, request.as_of_b] {
+        let mut search = request.search.clone();
+        search.as_of_frame = as_of.frame;
+        search.as_of_ts = as_of.ts;
+        match run_search(handle, search) {
+            Ok(r) => hits.push(r.hits),
+            Err(e) => return unsafe { set_error_null(error, e) },
         }
-        Err(e) => unsafe { set_error_null(error, MemvidError::json_parse(e)) },
+    }
+
+    let diff = SearchDiffResponseJson::diff(
+        request.search.query,
+        request.as_of_a,
+        request.as_of_b,
+        &hits[0],
+        &hits[1],
+    );
+    unsafe { json_to_cstr(&diff, error) }
+}
+
+/// Error in place of a failed sub-query of `memvid_search_multi`.
+#[derive(Debug, Serialize)]
+struct SearchSlotErrorJson {
+    code: i32,
+    name: &'static str,
+    message: String,
+}
+
+impl From<MemvidError> for SearchSlotErrorJson {
+    fn from(e: MemvidError) -> Self {
+        let code = e.code;
+        let name = unsafe { std::ffi::CStr::from_ptr(memvid_error_code_str(code)) };
+        Self {
+            code: code as i32,
+            name: name.to_str().unwrap_or("Unknown"),
+            message: e.into_message(),
+        }
+    }
+}
+
+/// One slot of a `memvid_search_multi` response.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum SearchSlotJson {
+    Response(SearchResponseJson),
+    Error { error: SearchSlotErrorJson },
+}
+
+/// Run one sub-query of `memvid_search_multi`.
+fn search_slot(handle: &mut MemvidHandle, request: serde_json::Value) -> SearchSlotJson {
+    let result = serde_json::from_value::<SearchRequestJson>(request)
+        .map_err(MemvidError::json_parse)
+        .and_then(|request| {
+            if request.compress_context {
+                return Err(MemvidError::new(
+                    MemvidErrorCode::InvalidQuery,
+                    "compress_context requires memvid_search_bytes",
+                ));
+            }
+            run_search(handle, request)
+        });
+    match result {
+        Ok(response) => SearchSlotJson::Response(response),
+        Err(e) => SearchSlotJson::Error { error: e.into() },
     }
 }
 
+/// Run several searches in one call.
+///
+/// Each element of `requests_json` is a `memvid_search` request, run
+/// independently and in order. The response array is parallel to it:
+/// each slot holds that request's `memvid_search` response, or, if the
+/// request failed (malformed, invalid, or rejected by memvid-core), an
+/// object with an `error` member describing it. A failed slot does not
+/// stop the others, so the call itself only fails if `requests_json` is
+/// not a JSON array.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `requests_json`: JSON array of search requests
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON array of responses on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// [
+///   { "query": "primary", "hits": [...], ... },
+///   { "error": {"code": 46, "name": "InvalidQuery", "message": "..."} }
+/// ]
+/// ```
+///
+/// `code` and `name` are the `MemvidErrorCode` the request would have
+/// failed with on its own (see `memvid_error_code_str`).
+///
+/// # Errors
+///
+/// - `JsonParse` if `requests_json` is not a JSON array
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `requests_json` must be a valid UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_search_multi(
+    handle: *mut MemvidHandle,
+    requests_json: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let requests: Vec<serde_json::Value> =
+        match unsafe { cstr_to_json(requests_json, "requests_json") } {
+            Ok(r) => r,
+            Err(e) => return unsafe { set_error_null(error, e) },
+        };
+
+    let slots: Vec<SearchSlotJson> = requests
+        .into_iter()
+        .map(|request| search_slot(handle, request))
+        .collect();
+    unsafe { json_to_cstr(&slots, error) }
+}
+
 /// Free a string returned by the FFI layer.
 ///
 /// # Safety
@@ -243,8 +1866,19 @@ pub unsafe extern "C" fn memvid_search(
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn memvid_string_free(str: *mut c_char) {
     if !str.is_null() {
-        unsafe {
-            drop(std::ffi::CString::from_raw(str));
-        }
+        unsafe { free_cstring(str) };
+    }
+}
+
+/// Free a byte buffer returned by the FFI layer.
+///
+/// # Safety
+///
+/// - `ptr` must be a buffer returned by an FFI function, or NULL
+/// - `len` must be the length reported alongside `ptr`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_bytes_free(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        unsafe { free_slice(ptr, len) };
     }
 }
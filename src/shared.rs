@@ -0,0 +1,239 @@
+//! Thread-safe shared handle for multi-threaded hosts.
+//!
+//! `MemvidSharedHandle` wraps a `MemvidHandle` in a `Mutex` so one open
+//! memory can be used from several threads without host-side locking.
+//! Every call takes the lock: memvid-core does not promise that even its
+//! `&self` API may run concurrently, so calls run one at a time.
+
+use crate::error::MemvidError;
+use crate::handle::MemvidHandle;
+use crate::lifecycle::memvid_open;
+use crate::mutation::{memvid_commit, memvid_put_bytes_with_options};
+use crate::search::memvid_search;
+use crate::state::MemvidStats;
+use crate::util::{set_error, set_error_null, set_ok};
+use libc::size_t;
+use std::os::raw::c_char;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Reference to a Memvid instance that is safe to share across threads.
+///
+/// Created by `memvid_open_shared()` and freed with `memvid_close_shared()`.
+pub struct MemvidSharedHandle {
+    inner: Mutex<MemvidHandle>,
+}
+
+// The mutex makes the shared handle `Sync` only while `MemvidHandle` is
+// `Send`; fail the build if a field ever stops it moving between threads.
+const _: fn() = || {
+    fn assert<T: Send + Sync>() {}
+    assert::<MemvidSharedHandle>();
+};
+
+impl MemvidSharedHandle {
+    /// Take the lock, ignoring poisoning.
+    fn lock(&self) -> MutexGuard<'_, MemvidHandle> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Convert a raw pointer to a shared reference.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid or null.
+    unsafe fn from_ptr<'a>(ptr: *const MemvidSharedHandle) -> Option<&'a Self> {
+        unsafe { ptr.as_ref() }
+    }
+}
+
+/// Open an existing Memvid memory as a thread-safe shared handle.
+///
+/// Every `memvid_shared_*` function may be called concurrently from any
+/// thread. Each call takes the handle's lock, so calls run one at a time,
+/// reads included: memvid-core does not promise its read API is safe to
+/// run in parallel, and its search needs mutable access anyway.
+///
+/// The plain `MemvidHandle` API is unaffected; a shared handle cannot be
+/// passed to it.
+///
+/// # Parameters
+///
+/// - `path`: Filesystem path to existing memory (UTF-8 encoded, null-terminated)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Shared handle on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned handle. Must call `memvid_close_shared()` to free.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_open_shared(
+    path: *const c_char,
+    error: *mut MemvidError,
+) -> *mut MemvidSharedHandle {
+    let handle = unsafe { memvid_open(path, error) };
+    if handle.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let shared = MemvidSharedHandle {
+        inner: Mutex::new(*unsafe { Box::from_raw(handle) }),
+    };
+    Box::into_raw(Box::new(shared))
+}
+
+/// Close and free a shared handle.
+///
+/// The caller must ensure no other thread is using the handle.
+///
+/// # Safety
+///
+/// - `shared` must be a valid handle returned by `memvid_open_shared`, or NULL
+/// - The handle must not be used after this call
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_close_shared(shared: *mut MemvidSharedHandle) {
+    if shared.is_null() {
+        return;
+    }
+
+    unsafe {
+        drop(Box::from_raw(shared));
+    }
+}
+
+/// Get memory statistics through a shared handle.
+///
+/// Same as `memvid_stats`.
+///
+/// # Safety
+///
+/// - `shared` must be a valid shared handle
+/// - `stats` must be a valid pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_shared_stats(
+    shared: *const MemvidSharedHandle,
+    stats: *mut MemvidStats,
+    error: *mut MemvidError,
+) -> i32 {
+    let shared = match unsafe { MemvidSharedHandle::from_ptr(shared) } {
+        Some(s) => s,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if stats.is_null() {
+        return unsafe { set_error(error, MemvidError::null_pointer("stats")) };
+    }
+
+    match shared.lock().as_ref().stats() {
+        Ok(s) => {
+            unsafe { *stats = MemvidStats::from(&s) };
+            unsafe { set_ok(error) };
+            1
+        }
+        Err(e) => unsafe { set_error(error, MemvidError::from_core_error(e)) },
+    }
+}
+
+/// Get the number of frames through a shared handle.
+///
+/// Same as `memvid_frame_count`.
+///
+/// # Safety
+///
+/// - `shared` must be a valid shared handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_shared_frame_count(
+    shared: *const MemvidSharedHandle,
+    error: *mut MemvidError,
+) -> u64 {
+    let shared = match unsafe { MemvidSharedHandle::from_ptr(shared) } {
+        Some(s) => s,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let count = shared.lock().as_ref().frame_count() as u64;
+    unsafe { set_ok(error) };
+    count
+}
+
+/// Search through a shared handle.
+///
+/// Same request and response as `memvid_search`.
+///
+/// # Safety
+///
+/// - `shared` must be a valid shared handle
+/// - `request_json` must be a valid UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_shared_search(
+    shared: *const MemvidSharedHandle,
+    request_json: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let shared = match unsafe { MemvidSharedHandle::from_ptr(shared) } {
+        Some(s) => s,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let mut handle = shared.lock();
+    unsafe { memvid_search(&mut *handle, request_json, error) }
+}
+
+/// Add content with options through a shared handle.
+///
+/// Same as `memvid_put_bytes_with_options`.
+///
+/// # Safety
+///
+/// - `shared` must be a valid shared handle
+/// - `data` must point to at least `len` bytes, or be NULL if `len` is 0
+/// - `options_json` must be a valid UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_shared_put_bytes_with_options(
+    shared: *const MemvidSharedHandle,
+    data: *const u8,
+    len: size_t,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let shared = match unsafe { MemvidSharedHandle::from_ptr(shared) } {
+        Some(s) => s,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let mut handle = shared.lock();
+    unsafe { memvid_put_bytes_with_options(&mut *handle, data, len, options_json, error) }
+}
+
+/// Commit pending changes through a shared handle.
+///
+/// Same as `memvid_commit`.
+///
+/// # Safety
+///
+/// - `shared` must be a valid shared handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_shared_commit(
+    shared: *const MemvidSharedHandle,
+    error: *mut MemvidError,
+) -> i32 {
+    let shared = match unsafe { MemvidSharedHandle::from_ptr(shared) } {
+        Some(s) => s,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let mut handle = shared.lock();
+    unsafe { memvid_commit(&mut *handle, error) }
+}
@@ -1,8 +1,13 @@
 //! State query functions (stats, frame_count).
 
-use crate::error::MemvidError;
+use crate::error::{MemvidError, MemvidErrorCode};
 use crate::handle::MemvidHandle;
-use crate::util::{set_error, set_ok};
+use crate::header;
+use crate::util::{json_to_cstr, set_error, set_error_null, set_ok};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::os::raw::c_char;
+use std::time::UNIX_EPOCH;
 
 /// Memory statistics.
 ///
@@ -124,6 +129,58 @@ pub unsafe extern "C" fn memvid_stats(
     }
 }
 
+/// Get memory statistics as JSON.
+///
+/// Serializes memvid-core's full `Stats`, including fields that are not
+/// mirrored in `MemvidStats`. New core metrics appear here without an ABI
+/// change, so prefer this unless the stats are read on a hot path.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON object on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// Keys are memvid-core's field names; those in `MemvidStats` have the
+/// same names. Callers should ignore keys they do not know.
+///
+/// ```json
+/// {
+///   "frame_count": 42,
+///   "active_frame_count": 40,
+///   "size_bytes": 1048576,
+///   "capacity_bytes": 1073741824,
+///   "has_lex_index": true,
+///   ...
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_stats_json(
+    handle: *mut MemvidHandle,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    match handle.as_ref().stats() {
+        Ok(s) => unsafe { json_to_cstr(&s, error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    }
+}
+
 /// Get the number of frames in the memory.
 ///
 /// # Parameters
@@ -134,7 +191,8 @@ pub unsafe extern "C" fn memvid_stats(
 /// # Returns
 ///
 /// Frame count on success, 0 on error (check error->code to distinguish
-/// from an empty memory).
+/// from an empty memory). Prefer `memvid_frame_count_checked`, whose
+/// return value is unambiguous.
 ///
 /// # Safety
 ///
@@ -153,3 +211,274 @@ pub unsafe extern "C" fn memvid_frame_count(
     unsafe { set_ok(error) };
     handle.as_ref().frame_count() as u64
 }
+
+/// Get the number of frames in the memory, with an explicit success flag.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `out_count`: Out-parameter for the frame count
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 on success, 0 on failure.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `out_count` must be a valid pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_count_checked(
+    handle: *mut MemvidHandle,
+    out_count: *mut u64,
+    error: *mut MemvidError,
+) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    let out_count = match unsafe { out_count.as_mut() } {
+        Some(c) => c,
+        None => return unsafe { set_error(error, MemvidError::null_pointer("out_count")) },
+    };
+
+    *out_count = handle.as_ref().frame_count() as u64;
+    unsafe { set_ok(error) };
+    1
+}
+
+/// Check that a handle can serve reads.
+///
+/// A cheap readiness probe: confirms the handle is open, then re-reads the
+/// file header from disk, so storage that became inaccessible (e.g. an
+/// unmounted volume or a deleted file) is detected. Unlike `memvid_stats`,
+/// it does not touch the indexes.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// 1 if the handle can serve reads, 0 otherwise (check `error`).
+///
+/// # Errors
+///
+/// - `InvalidHandle` if the handle is closed
+/// - `Io` if the file can no longer be read
+/// - `InvalidHeader` if the file no longer starts with a valid header
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_ping(handle: *mut MemvidHandle, error: *mut MemvidError) -> i32 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    match header::read_header(handle.path()) {
+        Ok(info) if info.compatible() => {
+            unsafe { set_ok(error) };
+            1
+        }
+        Ok(info) => {
+            let err = MemvidError::new(
+                MemvidErrorCode::InvalidHeader,
+                format!(
+                    "{} no longer has a valid header (format {})",
+                    handle.path().display(),
+                    info.format_version()
+                ),
+            );
+            unsafe { set_error(error, err) }
+        }
+        Err(e) => unsafe { set_error(error, MemvidError::io(e)) },
+    }
+}
+
+/// Get a change marker for the memory.
+///
+/// Returns the latest WAL sequence number seen on this handle: at least
+/// the highest value returned by a mutation through it (such as
+/// `memvid_delete_frame` or `memvid_update_frame`), and strictly greater
+/// after every later put, delete, update, refresh, or vacuum. Before any
+/// change it is the handle's generation at open, 0. Compare values across
+/// polls to tell whether anything changed, which is far cheaper than
+/// diffing `memvid_frame_count` or re-reading the timeline.
+///
+/// memvid-core does not expose its WAL position, so the marker only
+/// reflects changes this handle made or observed: writes by another
+/// process show up after `memvid_refresh()`. Values are per handle and
+/// are not comparable across handles.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Sequence number on success, 0 on failure (check `error`).
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_current_sequence(
+    handle: *mut MemvidHandle,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    unsafe { set_ok(error) };
+    handle.sequence()
+}
+
+/// Stats snapshot for JSON serialization.
+#[derive(Debug, Serialize)]
+struct StatsSnapshotJson {
+    seq: Option<u64>,
+    timestamp: Option<i64>,
+    frame_count: u64,
+    size_bytes: u64,
+}
+
+/// Get stats snapshots at commit points.
+///
+/// memvid-core does not retain commit history, so only the current
+/// snapshot is available: the result is always a single-element array
+/// describing the memory as of its last write. `seq` is null because core
+/// does not expose commit sequence numbers, and `timestamp` is the file's
+/// modification time (null if unavailable). `since_seq` is accepted so
+/// callers need not change if history becomes available; it does not
+/// filter the current snapshot.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `since_seq`: Only return snapshots after this sequence number
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON array of snapshots on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// [
+///   {"seq": null, "timestamp": 1700000000, "frame_count": 42, "size_bytes": 1048576}
+/// ]
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_stats_history(
+    handle: *mut MemvidHandle,
+    since_seq: u64,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+    let _ = since_seq;
+
+    let stats = match handle.as_ref().stats() {
+        Ok(s) => s,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+    };
+
+    let timestamp = std::fs::metadata(handle.path())
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64);
+
+    let history = [StatsSnapshotJson {
+        seq: None,
+        timestamp,
+        frame_count: stats.frame_count,
+        size_bytes: stats.size_bytes,
+    }];
+    unsafe { json_to_cstr(&history, error) }
+}
+
+/// Tag and label counts for JSON serialization.
+#[derive(Debug, Default, Serialize)]
+struct TagListJson {
+    tags: BTreeMap<String, u64>,
+    labels: BTreeMap<String, u64>,
+}
+
+/// List the tags and labels used by active frames.
+///
+/// Scans every frame and counts, for each tag and label, the active frames
+/// carrying it; deleted frames are skipped. Frames store tags as a flat
+/// list of names (as in `memvid_frame_by_id`), so tags are counted by name.
+/// Works on read-only handles.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON object on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// Names are sorted; values are frame counts.
+///
+/// ```json
+/// {
+///   "tags": {"ffi": 3, "rust": 7},
+///   "labels": {"draft": 2, "reviewed": 5}
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_list_tags(
+    handle: *mut MemvidHandle,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let mut list = TagListJson::default();
+    let active = handle
+        .frames()
+        .filter(|f| matches!(f.status, memvid_core::types::FrameStatus::Active));
+    for frame in active {
+        for tag in frame.tags {
+            *list.tags.entry(tag).or_default() += 1;
+        }
+        for label in frame.labels {
+            *list.labels.entry(label).or_default() += 1;
+        }
+    }
+    unsafe { json_to_cstr(&list, error) }
+}
@@ -1,14 +1,15 @@
 //! Timeline query functions.
 
-use crate::error::MemvidError;
+use crate::error::{MemvidError, MemvidErrorCode};
 use crate::handle::MemvidHandle;
-use crate::util::{cstr_to_option_string, set_error_null, set_ok, string_to_cstr};
+use crate::util::{cstr_to_option_string, set_error_null, string_to_cstr};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroU64;
 use std::os::raw::c_char;
 
 /// JSON schema for TimelineQuery.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 struct TimelineQueryJson {
     /// Maximum number of entries to return
     #[serde(default)]
@@ -22,13 +23,24 @@ struct TimelineQueryJson {
     /// Return in reverse chronological order
     #[serde(default)]
     reverse: bool,
+    /// Pagination cursor (`next_cursor` of the previous page)
+    #[serde(default)]
+    cursor: Option<String>,
+    /// Preview length in characters (core's default when omitted)
+    #[serde(default)]
+    preview_chars: Option<usize>,
+}
+
+/// JSON Schema of the timeline query, for `memvid_request_schema`.
+pub(crate) fn request_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(TimelineQueryJson)
 }
 
 impl TimelineQueryJson {
-    fn into_query(self) -> memvid_core::TimelineQuery {
+    fn to_query(&self, limit: Option<u64>) -> memvid_core::TimelineQuery {
         let mut builder = memvid_core::TimelineQueryBuilder::default();
 
-        if let Some(limit) = self.limit {
+        if let Some(limit) = limit {
             if let Some(nz) = NonZeroU64::new(limit) {
                 builder = builder.limit(nz);
             }
@@ -45,6 +57,118 @@ impl TimelineQueryJson {
     }
 }
 
+/// Position of the last entry of a page, encoded as `"<timestamp>:<frame_id>"`.
+#[derive(Debug, Clone, Copy)]
+struct TimelineCursor {
+    timestamp: i64,
+    frame_id: u64,
+}
+
+impl TimelineCursor {
+    fn parse(s: &str) -> Result<Self, MemvidError> {
+        let parsed = s.split_once(':').and_then(|(timestamp, frame_id)| {
+            Some(Self {
+                timestamp: timestamp.parse().ok()?,
+                frame_id: frame_id.parse().ok()?,
+            })
+        });
+        parsed.ok_or_else(|| {
+            MemvidError::new(
+                MemvidErrorCode::InvalidQuery,
+                format!("invalid timeline cursor: {s:?}"),
+            )
+        })
+    }
+
+    fn of(entry: &memvid_core::TimelineEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp,
+            frame_id: entry.frame_id,
+        }
+    }
+
+    fn key(&self) -> (i64, u64) {
+        (self.timestamp, self.frame_id)
+    }
+
+    fn encode(&self) -> String {
+        format!("{}:{}", self.timestamp, self.frame_id)
+    }
+}
+
+/// Fetch one page of the timeline and the cursor of the page after it.
+///
+/// Entries are ordered by `(timestamp, frame_id)`, descending if `reverse`
+/// is set. With a cursor, the time bound on the paging side is narrowed to
+/// the cursor's timestamp and entries up to the cursor are dropped; the
+/// fetch is widened by the number dropped until the page is full or the
+/// timeline is exhausted.
+fn timeline_page(
+    handle: &mut MemvidHandle,
+    mut query: TimelineQueryJson,
+) -> Result<(Vec<memvid_core::TimelineEntry>, Option<String>), MemvidError> {
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(TimelineCursor::parse)
+        .transpose()?;
+    if let Some(cursor) = cursor {
+        let bound = cursor.timestamp;
+        if query.reverse {
+            query.until = Some(query.until.map_or(bound, |u| u.min(bound)));
+        } else {
+            query.since = Some(query.since.map_or(bound, |s| s.max(bound)));
+        }
+    }
+
+    let page_size = query.limit.filter(|&l| l > 0);
+    let mut fetch_limit = page_size;
+    let mut entries = loop {
+        let mut fetched = handle
+            .as_mut()
+            .timeline(query.to_query(fetch_limit))
+            .map_err(MemvidError::from_core_error)?;
+        let exhausted = fetch_limit.is_none_or(|l| (fetched.len() as u64) < l);
+
+        fetched.sort_by_key(|e| TimelineCursor::of(e).key());
+        if query.reverse {
+            fetched.reverse();
+        }
+        let before = fetched.len();
+        if let Some(cursor) = cursor {
+            fetched.retain(|e| {
+                let key = TimelineCursor::of(e).key();
+                if query.reverse {
+                    key < cursor.key()
+                } else {
+                    key > cursor.key()
+                }
+            });
+        }
+        let skipped = (before - fetched.len()) as u64;
+
+        match (page_size, fetch_limit) {
+            (Some(size), Some(limit)) if !exhausted && (fetched.len() as u64) < size => {
+                fetch_limit = Some(limit + skipped.max(1));
+            }
+            _ => break fetched,
+        }
+    };
+
+    let next_cursor = match page_size {
+        Some(size) => {
+            entries.truncate(size as usize);
+            if entries.len() as u64 == size {
+                entries.last().map(|e| TimelineCursor::of(e).encode())
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
+    Ok((entries, next_cursor))
+}
+
 /// Timeline entry for JSON serialization.
 #[derive(Debug, Serialize)]
 struct TimelineEntryJson {
@@ -67,15 +191,47 @@ impl From<&memvid_core::TimelineEntry> for TimelineEntryJson {
     }
 }
 
+impl TimelineEntryJson {
+    /// Replace the preview with the first `chars` characters of the frame text.
+    ///
+    /// Core's timeline query takes no preview length. A preview at least
+    /// `chars` long is cut from core's; only a shorter one is rebuilt from
+    /// the frame text, which decodes the whole frame. Truncation counts
+    /// characters, so multibyte characters are never split. Frames whose
+    /// text cannot be extracted keep core's preview.
+    fn set_preview_chars(&mut self, handle: &mut MemvidHandle, chars: usize) {
+        if self.preview.chars().count() < chars {
+            if let Ok(text) = handle.as_mut().frame_text_by_id(self.frame_id) {
+                self.preview = text;
+            }
+        }
+        self.preview = self.preview.chars().take(chars).collect();
+    }
+}
+
 /// Timeline response for JSON serialization.
 #[derive(Debug, Serialize)]
 struct TimelineResponseJson {
     entries: Vec<TimelineEntryJson>,
     count: usize,
+    next_cursor: Option<String>,
 }
 
 /// Query the timeline (chronological frame list).
 ///
+/// Entries are ordered by timestamp, then frame ID. To page through a long
+/// history, set `limit` and pass each response's `next_cursor` back as
+/// `cursor` with otherwise identical query parameters. `next_cursor` is set
+/// whenever a page is full, so the last page may be empty; it is null on a
+/// page shorter than `limit` and when no `limit` is given.
+///
+/// `preview_chars` sets the preview length, cut at a character boundary;
+/// without it, previews are core's default. Core's timeline query has no
+/// preview length, so shorter previews are cut from core's, but each entry
+/// whose core preview is shorter than `preview_chars` decodes its frame's
+/// full text: asking for more than core's default costs a frame read per
+/// entry.
+///
 /// # Parameters
 ///
 /// - `handle`: Valid Memvid handle
@@ -94,7 +250,9 @@ struct TimelineResponseJson {
 ///   "limit": 100,
 ///   "since": 1234567890,
 ///   "until": 1234567899,
-///   "reverse": false
+///   "reverse": false,
+///   "cursor": "1234567890:42",  // next_cursor of the previous page
+///   "preview_chars": 40         // preview length in characters
 /// }
 /// ```
 ///
@@ -111,10 +269,15 @@ struct TimelineResponseJson {
 ///       "child_frames": [2, 3, 4]
 ///     }
 ///   ],
-///   "count": 1
+///   "count": 1,
+///   "next_cursor": "1234567890:1"  // or null
 /// }
 /// ```
 ///
+/// # Errors
+///
+/// - `InvalidQuery` if `cursor` is malformed
+///
 /// # Safety
 ///
 /// - `handle` must be a valid handle
@@ -134,27 +297,33 @@ pub unsafe extern "C" fn memvid_timeline(
     // Parse query JSON
     let query = match unsafe { cstr_to_option_string(query_json, "query_json") } {
         Ok(Some(json_str)) => match serde_json::from_str::<TimelineQueryJson>(&json_str) {
-            Ok(q) => q.into_query(),
+            Ok(q) => q,
             Err(e) => return unsafe { set_error_null(error, MemvidError::json_parse(e)) },
         },
-        Ok(None) => memvid_core::TimelineQuery::default(),
+        Ok(None) => TimelineQueryJson::default(),
         Err(e) => return unsafe { set_error_null(error, e) },
     };
 
-    match handle.as_mut().timeline(query) {
-        Ok(entries) => {
+    let preview_chars = query.preview_chars;
+    match timeline_page(handle, query) {
+        Ok((entries, next_cursor)) => {
+            let mut entries: Vec<TimelineEntryJson> =
+                entries.iter().map(TimelineEntryJson::from).collect();
+            if let Some(chars) = preview_chars {
+                for entry in &mut entries {
+                    entry.set_preview_chars(handle, chars);
+                }
+            }
             let response = TimelineResponseJson {
                 count: entries.len(),
-                entries: entries.iter().map(TimelineEntryJson::from).collect(),
+                entries,
+                next_cursor,
             };
             match serde_json::to_string(&response) {
-                Ok(json) => {
-                    unsafe { set_ok(error) };
-                    string_to_cstr(json)
-                }
+                Ok(json) => unsafe { string_to_cstr(json, None, error) },
                 Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
             }
         }
-        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
+        Err(e) => unsafe { set_error_null(error, e) },
     }
 }
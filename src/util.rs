@@ -1,6 +1,9 @@
 //! Utility functions for FFI operations.
 
-use crate::error::MemvidError;
+use crate::alloc::{cstring_into_raw, slice_into_raw};
+use crate::error::{memvid_error_free, record_last_error, MemvidError, MemvidErrorCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::path::PathBuf;
@@ -25,6 +28,31 @@ pub unsafe fn cstr_to_path(ptr: *const c_char) -> Result<PathBuf, MemvidError> {
     }
 }
 
+/// Convert a null-terminated UTF-16 string to a PathBuf.
+///
+/// The conversion is lossless: unpaired surrogates, which Windows allows
+/// in file names, are preserved.
+///
+/// # Safety
+///
+/// The caller must ensure `ptr` is either null or points to a valid
+/// null-terminated UTF-16 string.
+#[cfg(windows)]
+pub unsafe fn wstr_to_path(ptr: *const u16) -> Result<PathBuf, MemvidError> {
+    use std::os::windows::ffi::OsStringExt;
+
+    if ptr.is_null() {
+        return Err(MemvidError::null_pointer("path"));
+    }
+
+    let mut len = 0;
+    while unsafe { *ptr.add(len) } != 0 {
+        len += 1;
+    }
+    let wide = unsafe { std::slice::from_raw_parts(ptr, len) };
+    Ok(PathBuf::from(std::ffi::OsString::from_wide(wide)))
+}
+
 /// Convert a C string to a Rust String.
 ///
 /// Returns an error if the pointer is null or contains invalid UTF-8.
@@ -69,36 +97,140 @@ pub unsafe fn cstr_to_option_string(
     }
 }
 
-/// Convert a Rust string to a C string, returning an owned pointer.
+/// Parse a JSON C string into a value.
+///
+/// Returns an error if the pointer is null, contains invalid UTF-8, or
+/// does not match the expected schema.
+///
+/// # Safety
+///
+/// The caller must ensure `ptr` is either null or points to a valid
+/// null-terminated C string.
+pub unsafe fn cstr_to_json<T: DeserializeOwned>(
+    ptr: *const c_char,
+    param_name: &str,
+) -> Result<T, MemvidError> {
+    let json_str = unsafe { cstr_to_string(ptr, param_name) }?;
+    serde_json::from_str(&json_str).map_err(MemvidError::json_parse)
+}
+
+/// Parse an optional JSON C string, using the default value for NULL.
+///
+/// # Safety
+///
+/// The caller must ensure `ptr` is either null or points to a valid
+/// null-terminated C string.
+pub unsafe fn cstr_to_option_json<T: DeserializeOwned + Default>(
+    ptr: *const c_char,
+    param_name: &str,
+) -> Result<T, MemvidError> {
+    match unsafe { cstr_to_option_string(ptr, param_name) }? {
+        Some(json_str) => serde_json::from_str(&json_str).map_err(MemvidError::json_parse),
+        None => Ok(T::default()),
+    }
+}
+
+/// Serialize a value to a JSON C string, setting the error out-parameter.
+///
+/// Returns the owned string on success, or null with a serialization error.
+///
+/// # Safety
+///
+/// The caller must ensure `error` is either null or a valid pointer.
+pub unsafe fn json_to_cstr<T: Serialize>(value: &T, error: *mut MemvidError) -> *mut c_char {
+    match serde_json::to_string(value) {
+        Ok(json) => unsafe { string_to_cstr(json, None, error) },
+        Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
+    }
+}
+
+/// Convert a Rust string to a C string, setting the error out-parameter.
 ///
 /// The caller is responsible for freeing the returned pointer with `memvid_string_free`.
-/// Returns null if the string contains internal null bytes.
-pub fn string_to_cstr(s: String) -> *mut c_char {
-    CString::new(s)
-        .map(CString::into_raw)
-        .unwrap_or(std::ptr::null_mut())
+/// Returns null with `InteriorNul` if the string contains internal null
+/// bytes, with `nul_hint` (e.g. where to get the bytes instead) appended to
+/// the message, or with `AllocationFailed` if the host allocator fails.
+///
+/// # Safety
+///
+/// The caller must ensure `error` is either null or a valid pointer.
+pub unsafe fn string_to_cstr(
+    s: String,
+    nul_hint: Option<&str>,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let result = CString::new(s)
+        .map_err(|e| {
+            let mut message = format!("string contains a null byte at offset {}", e.nul_position());
+            if let Some(hint) = nul_hint {
+                message.push_str("; ");
+                message.push_str(hint);
+            }
+            MemvidError::new(MemvidErrorCode::InteriorNul, message)
+        })
+        .and_then(cstring_into_raw);
+    match result {
+        Ok(ptr) => {
+            unsafe { set_ok(error) };
+            ptr
+        }
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Convert a byte vector to an owned raw buffer, storing its length.
+///
+/// The caller is responsible for freeing the returned pointer with `memvid_bytes_free`.
+/// Fails with `AllocationFailed` if the host allocator fails, leaving
+/// `out_len` untouched.
+pub fn bytes_to_raw(bytes: Vec<u8>, out_len: &mut usize) -> Result<*mut u8, MemvidError> {
+    let len = bytes.len();
+    let ptr = slice_into_raw(bytes.into_boxed_slice())?;
+    *out_len = len;
+    Ok(ptr)
+}
+
+/// Convert a u64 vector to an owned raw array, or null if it is empty.
+///
+/// The caller is responsible for freeing the returned pointer with `memvid_u64_array_free`.
+/// Fails with `AllocationFailed` if the host allocator fails.
+pub fn u64s_to_raw(values: Vec<u64>) -> Result<*mut u64, MemvidError> {
+    if values.is_empty() {
+        return Ok(std::ptr::null_mut());
+    }
+    slice_into_raw(values.into_boxed_slice())
 }
 
 /// Set an error in the out-parameter and return a default value.
 ///
+/// The error is also recorded as the thread's last error; with a NULL
+/// out-parameter its message is freed.
+///
 /// # Safety
 ///
 /// The caller must ensure `error` is either null or a valid pointer.
-pub unsafe fn set_error<T: Default>(error: *mut MemvidError, err: MemvidError) -> T {
-    if let Some(e) = unsafe { error.as_mut() } {
-        *e = err;
+pub unsafe fn set_error<T: Default>(error: *mut MemvidError, mut err: MemvidError) -> T {
+    record_last_error(&err);
+    match unsafe { error.as_mut() } {
+        Some(e) => *e = err,
+        None => unsafe { memvid_error_free(&mut err) },
     }
     T::default()
 }
 
 /// Set an error in the out-parameter and return null.
 ///
+/// The error is also recorded as the thread's last error; with a NULL
+/// out-parameter its message is freed.
+///
 /// # Safety
 ///
 /// The caller must ensure `error` is either null or a valid pointer.
-pub unsafe fn set_error_null<T>(error: *mut MemvidError, err: MemvidError) -> *mut T {
-    if let Some(e) = unsafe { error.as_mut() } {
-        *e = err;
+pub unsafe fn set_error_null<T>(error: *mut MemvidError, mut err: MemvidError) -> *mut T {
+    record_last_error(&err);
+    match unsafe { error.as_mut() } {
+        Some(e) => *e = err,
+        None => unsafe { memvid_error_free(&mut err) },
     }
     std::ptr::null_mut()
 }
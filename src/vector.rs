@@ -0,0 +1,573 @@
+//! Vector similarity functions (require the `vec` feature).
+
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::handle::MemvidHandle;
+use crate::mutation::{merged_put_options, PutOptionsJson};
+use crate::util::{json_to_cstr, set_error, set_error_null, set_ok};
+use libc::size_t;
+use serde::Serialize;
+use std::os::raw::c_char;
+
+/// Initial candidate pool for threshold queries; doubled until exhausted.
+#[cfg(feature = "vec")]
+const INITIAL_CANDIDATES: usize = 64;
+
+/// Near-duplicate frame for JSON serialization.
+#[derive(Debug, Serialize)]
+#[cfg_attr(not(feature = "vec"), allow(dead_code))]
+struct NearDuplicateJson {
+    frame_id: u64,
+    uri: Option<String>,
+    title: Option<String>,
+    similarity: f32,
+}
+
+/// Near-duplicate report for JSON serialization.
+#[derive(Debug, Serialize)]
+struct NearDuplicatesJson {
+    source_frame_id: u64,
+    similarity_threshold: f32,
+    duplicates: Vec<NearDuplicateJson>,
+}
+
+/// Similar frame for JSON serialization.
+#[derive(Debug, Serialize)]
+#[cfg_attr(not(feature = "vec"), allow(dead_code))]
+struct SimilarFrameJson {
+    frame_id: u64,
+    score: f32,
+}
+
+/// CLIP image search hit for JSON serialization.
+#[derive(Debug, Serialize)]
+#[cfg_attr(not(feature = "clip"), allow(dead_code))]
+struct ClipHitJson {
+    frame_id: u64,
+    uri: Option<String>,
+    score: f32,
+}
+
+/// Cosine similarity of two vectors (0.0 if either is zero or lengths differ).
+#[cfg(feature = "vec")]
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Embedding stored for `frame_id`, or an error if it has none.
+#[cfg(feature = "vec")]
+fn frame_embedding(handle: &mut MemvidHandle, frame_id: u64) -> Result<Vec<f32>, MemvidError> {
+    handle
+        .as_mut()
+        .frame_embedding(frame_id)
+        .map_err(MemvidError::from_core_error)?
+        .ok_or_else(|| {
+            MemvidError::new(
+                MemvidErrorCode::InvalidFrame,
+                format!("frame {frame_id} has no embedding"),
+            )
+        })
+}
+
+/// Frames whose embedding has cosine similarity >= `threshold` with the source.
+#[cfg(feature = "vec")]
+fn find_near_duplicates(
+    handle: &mut MemvidHandle,
+    frame_id: u64,
+    threshold: f32,
+) -> Result<Vec<NearDuplicateJson>, MemvidError> {
+    let source = frame_embedding(handle, frame_id)?;
+    let frame_count = handle.as_ref().frame_count();
+
+    // The index returns nearest-first, so widen the pool until the farthest
+    // candidate falls below the threshold or every frame has been seen.
+    let mut k = INITIAL_CANDIDATES.min(frame_count.max(1));
+    let scored = loop {
+        let hits = handle
+            .as_mut()
+            .search_vec(&source, k)
+            .map_err(MemvidError::from_core_error)?;
+
+        let mut scored = Vec::with_capacity(hits.len());
+        for hit in &hits {
+            if hit.frame_id == frame_id {
+                continue;
+            }
+            let embedding = frame_embedding(handle, hit.frame_id)?;
+            scored.push((hit.frame_id, cosine_similarity(&source, &embedding)));
+        }
+
+        let exhausted = hits.len() < k || k >= frame_count;
+        let farthest_matches = scored.last().is_some_and(|(_, s)| *s >= threshold);
+        if exhausted || !farthest_matches {
+            break scored;
+        }
+        k = k.saturating_mul(2).min(frame_count);
+    };
+
+    let mut duplicates: Vec<NearDuplicateJson> = scored
+        .into_iter()
+        .filter(|(_, similarity)| *similarity >= threshold)
+        .map(|(id, similarity)| {
+            let frame = handle.as_mut().frame_by_id(id).ok();
+            NearDuplicateJson {
+                frame_id: id,
+                uri: frame.as_ref().and_then(|f| f.uri.clone()),
+                title: frame.as_ref().and_then(|f| f.title.clone()),
+                similarity,
+            }
+        })
+        .collect();
+    duplicates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    Ok(duplicates)
+}
+
+#[cfg(not(feature = "vec"))]
+fn find_near_duplicates(
+    _handle: &mut MemvidHandle,
+    _frame_id: u64,
+    _threshold: f32,
+) -> Result<Vec<NearDuplicateJson>, MemvidError> {
+    Err(MemvidError::new(
+        MemvidErrorCode::VecNotEnabled,
+        "near-duplicate detection requires the vec feature",
+    ))
+}
+
+/// Find frames whose content is near-identical to a given frame.
+///
+/// Compares the source frame's embedding against the vector index and
+/// returns every other frame with cosine similarity at or above
+/// `similarity_threshold`, most similar first. Unlike search there is no
+/// `top_k`: the candidate pool grows until the cutoff is reached.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Source frame (excluded from the results)
+/// - `similarity_threshold`: Minimum cosine similarity, in [-1.0, 1.0]
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with near-duplicates on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `VecNotEnabled` if built without the `vec` feature
+/// - `InvalidFrame` if the source frame has no embedding
+/// - `InvalidQuery` if the threshold is not in [-1.0, 1.0]
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "source_frame_id": 42,
+///   "similarity_threshold": 0.95,
+///   "duplicates": [
+///     {"frame_id": 57, "uri": "mv2://doc-v2.txt", "title": null, "similarity": 0.98}
+///   ]
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_find_near_duplicates(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    similarity_threshold: f32,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if !(-1.0..=1.0).contains(&similarity_threshold) {
+        let err = MemvidError::new(
+            MemvidErrorCode::InvalidQuery,
+            format!("similarity_threshold must be in [-1.0, 1.0], got {similarity_threshold}"),
+        );
+        return unsafe { set_error_null(error, err) };
+    }
+
+    match find_near_duplicates(handle, frame_id, similarity_threshold) {
+        Ok(duplicates) => {
+            let report = NearDuplicatesJson {
+                source_frame_id: frame_id,
+                similarity_threshold,
+                duplicates,
+            };
+            unsafe { json_to_cstr(&report, error) }
+        }
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// The `top_k` frames nearest the source frame's embedding, most similar first.
+#[cfg(feature = "vec")]
+fn similar_frames(
+    handle: &mut MemvidHandle,
+    frame_id: u64,
+    top_k: usize,
+) -> Result<Vec<SimilarFrameJson>, MemvidError> {
+    let source = match handle
+        .as_mut()
+        .frame_embedding(frame_id)
+        .map_err(MemvidError::from_core_error)?
+    {
+        Some(embedding) => embedding,
+        None => return Ok(Vec::new()),
+    };
+
+    // One extra neighbor, since the source frame is its own nearest.
+    let hits = handle
+        .as_mut()
+        .search_vec(&source, top_k.saturating_add(1))
+        .map_err(MemvidError::from_core_error)?;
+
+    let mut similar = Vec::with_capacity(hits.len());
+    for hit in hits.iter().filter(|h| h.frame_id != frame_id) {
+        let embedding = frame_embedding(handle, hit.frame_id)?;
+        similar.push(SimilarFrameJson {
+            frame_id: hit.frame_id,
+            score: cosine_similarity(&source, &embedding),
+        });
+    }
+    similar.sort_by(|a, b| b.score.total_cmp(&a.score));
+    similar.truncate(top_k);
+    Ok(similar)
+}
+
+#[cfg(not(feature = "vec"))]
+fn similar_frames(
+    _handle: &mut MemvidHandle,
+    _frame_id: u64,
+    _top_k: usize,
+) -> Result<Vec<SimilarFrameJson>, MemvidError> {
+    Err(MemvidError::new(
+        MemvidErrorCode::VecNotEnabled,
+        "similar-frame search requires the vec feature",
+    ))
+}
+
+/// Find the frames most similar to a given frame.
+///
+/// Looks up the source frame's stored embedding and returns its nearest
+/// neighbors in the vector index, scored by cosine similarity. No embedder
+/// is needed: only embeddings already stored with frames are used.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `frame_id`: Source frame (excluded from the results)
+/// - `top_k`: Maximum number of frames to return
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON array of similar frames on success (empty if the source frame has
+/// no embedding), NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `VecNotEnabled` if built without the `vec` feature
+/// - `InvalidQuery` if `top_k` is 0
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// [
+///   {"frame_id": 57, "score": 0.91},
+///   {"frame_id": 12, "score": 0.84}
+/// ]
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_similar_frames(
+    handle: *mut MemvidHandle,
+    frame_id: u64,
+    top_k: u32,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if top_k == 0 {
+        let err = MemvidError::new(MemvidErrorCode::InvalidQuery, "top_k must be at least 1");
+        return unsafe { set_error_null(error, err) };
+    }
+
+    match similar_frames(handle, frame_id, top_k as usize) {
+        Ok(similar) => unsafe { json_to_cstr(&similar, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// CLIP model, loaded on first image search and kept for the process.
+#[cfg(feature = "clip")]
+static CLIP_MODEL: std::sync::OnceLock<memvid_core::clip::ClipModel> = std::sync::OnceLock::new();
+
+/// The shared CLIP model, loading it if needed.
+#[cfg(feature = "clip")]
+fn clip_model() -> Result<&'static memvid_core::clip::ClipModel, MemvidError> {
+    if let Some(model) = CLIP_MODEL.get() {
+        return Ok(model);
+    }
+    let model = memvid_core::clip::ClipModel::new(memvid_core::clip::ClipConfig::default())
+        .map_err(MemvidError::from_core_error)?;
+    Ok(CLIP_MODEL.get_or_init(|| model))
+}
+
+/// Decode `image` and embed it with CLIP.
+#[cfg(feature = "clip")]
+fn clip_embedding(image: &[u8]) -> Result<Vec<f32>, MemvidError> {
+    let decoded = image::load_from_memory(image).map_err(|e| {
+        MemvidError::new(
+            MemvidErrorCode::ExtractionFailed,
+            format!("cannot decode image: {e}"),
+        )
+    })?;
+    clip_model()?
+        .encode_image(&decoded)
+        .map_err(MemvidError::from_core_error)
+}
+
+/// Decode `image`, embed it with CLIP, and rank frames in the CLIP index.
+#[cfg(feature = "clip")]
+fn clip_search(
+    handle: &mut MemvidHandle,
+    image: &[u8],
+    top_k: usize,
+) -> Result<Vec<ClipHitJson>, MemvidError> {
+    let embedding = clip_embedding(image)?;
+
+    let hits = handle
+        .as_mut()
+        .search_clip(&embedding, top_k)
+        .map_err(MemvidError::from_core_error)?;
+
+    Ok(hits
+        .into_iter()
+        .map(|hit| {
+            let frame = handle.as_mut().frame_by_id(hit.frame_id).ok();
+            ClipHitJson {
+                frame_id: hit.frame_id,
+                uri: frame.and_then(|f| f.uri),
+                score: 1.0 - hit.distance,
+            }
+        })
+        .collect())
+}
+
+#[cfg(not(feature = "clip"))]
+fn clip_search(
+    _handle: &mut MemvidHandle,
+    _image: &[u8],
+    _top_k: usize,
+) -> Result<Vec<ClipHitJson>, MemvidError> {
+    Err(MemvidError::new(
+        MemvidErrorCode::ClipNotEnabled,
+        "image search requires the clip feature",
+    ))
+}
+
+/// Search the CLIP index with an image.
+///
+/// Decodes the image (PNG or JPEG), embeds it with the CLIP model, and
+/// returns the nearest frames in the CLIP index. The model is loaded on the
+/// first call and kept for the life of the process; its files must already
+/// be downloaded.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `image_data`: Encoded image bytes
+/// - `image_len`: Length of `image_data`
+/// - `top_k`: Maximum number of frames to return
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON array of hits on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Errors
+///
+/// - `ClipNotEnabled` if built without the `clip` feature
+/// - `ExtractionFailed` if the image cannot be decoded
+/// - `InvalidQuery` if `top_k` is 0
+///
+/// # Response JSON Schema
+///
+/// `score` is cosine similarity between the query image and the frame.
+///
+/// ```json
+/// [
+///   {"frame_id": 7, "uri": "mv2://screenshots/login.png", "score": 0.83}
+/// ]
+/// ```
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `image_data` must point to at least `image_len` bytes
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_clip_search(
+    handle: *mut MemvidHandle,
+    image_data: *const u8,
+    image_len: size_t,
+    top_k: u32,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    if image_data.is_null() {
+        return unsafe { set_error_null(error, MemvidError::null_pointer("image_data")) };
+    }
+    if top_k == 0 {
+        let err = MemvidError::new(MemvidErrorCode::InvalidQuery, "top_k must be at least 1");
+        return unsafe { set_error_null(error, err) };
+    }
+
+    let image = unsafe { std::slice::from_raw_parts(image_data, image_len) };
+    match clip_search(handle, image, top_k as usize) {
+        Ok(hits) => unsafe { json_to_cstr(&hits, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Put `image` as a frame and add its CLIP embedding.
+///
+/// The embedding is computed first, so an undecodable image stages nothing.
+#[cfg(feature = "clip")]
+fn put_image(
+    handle: &mut MemvidHandle,
+    image: &[u8],
+    options: PutOptionsJson,
+) -> Result<u64, MemvidError> {
+    let embedding = clip_embedding(image)?;
+    let mime = image::guess_format(image)
+        .map(|format| format.to_mime_type())
+        .unwrap_or("application/octet-stream");
+
+    let (frame_id, deduplicated) =
+        crate::mutation::put_with_options(handle, image, options.for_image(mime))?;
+    if !deduplicated {
+        handle
+            .as_mut()
+            .add_clip_embedding(frame_id, embedding)
+            .map_err(MemvidError::from_core_error)?;
+    }
+    Ok(frame_id)
+}
+
+#[cfg(not(feature = "clip"))]
+fn put_image(
+    _handle: &mut MemvidHandle,
+    _image: &[u8],
+    _options: PutOptionsJson,
+) -> Result<u64, MemvidError> {
+    Err(MemvidError::new(
+        MemvidErrorCode::ClipNotEnabled,
+        "image puts require the clip feature",
+    ))
+}
+
+/// Add an image and index its CLIP embedding.
+///
+/// Decodes the image (PNG or JPEG), embeds it with the CLIP model (loaded
+/// as for `memvid_clip_search`), stores the encoded bytes as the frame's
+/// payload, and adds the embedding to the CLIP index, so the frame is
+/// found by `memvid_clip_search`.
+///
+/// Options are those of `memvid_put_bytes_with_options`. `kind` defaults
+/// to the image's MIME type. The payload is kept out of the lexical index
+/// unless `search_text` is given, e.g. a caption. A put deduplicated by
+/// `idempotency_key` returns the earlier frame and adds no embedding.
+///
+/// The frame is staged; call `memvid_commit()` to persist it.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `image_data`: Encoded image bytes
+/// - `image_len`: Length of `image_data`
+/// - `options_json`: JSON put options (may be NULL for defaults)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Frame ID on success, 0 on failure (check `error`).
+///
+/// # Errors
+///
+/// - `ClipNotEnabled` if built without the `clip` feature
+/// - `ExtractionFailed` if the image cannot be decoded
+/// - `ReadOnly` if the handle was opened read-only
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `image_data` must point to at least `image_len` bytes
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_put_image(
+    handle: *mut MemvidHandle,
+    image_data: *const u8,
+    image_len: size_t,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> u64 {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error(error, MemvidError::invalid_handle()) },
+    };
+
+    if let Err(e) = handle.check_writable() {
+        return unsafe { set_error(error, e) };
+    }
+
+    if image_data.is_null() {
+        return unsafe { set_error(error, MemvidError::null_pointer("image_data")) };
+    }
+
+    let options = match unsafe { merged_put_options(handle, options_json) } {
+        Ok(o) => o,
+        Err(e) => return unsafe { set_error(error, e) },
+    };
+
+    let image = unsafe { std::slice::from_raw_parts(image_data, image_len) };
+    match put_image(handle, image, options) {
+        Ok(frame_id) => {
+            unsafe { set_ok(error) };
+            frame_id
+        }
+        Err(e) => unsafe { set_error(error, e) },
+    }
+}
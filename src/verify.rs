@@ -1,14 +1,16 @@
 //! File verification functions.
 
-use crate::error::MemvidError;
-use crate::util::{cstr_to_path, set_error_null, set_ok, string_to_cstr};
-use serde::Serialize;
+use crate::error::{MemvidError, MemvidErrorCode};
+use crate::header;
+use crate::util::{cstr_to_option_json, cstr_to_path, json_to_cstr, set_error_null};
+use serde::{Deserialize, Serialize};
 use std::os::raw::c_char;
+use std::path::Path;
 
 /// Verification status for JSON serialization.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "lowercase")]
-enum VerificationStatusJson {
+pub(crate) enum VerificationStatusJson {
     Passed,
     Failed,
     Skipped,
@@ -26,9 +28,9 @@ impl From<&memvid_core::VerificationStatus> for VerificationStatusJson {
 
 /// Individual verification check for JSON serialization.
 #[derive(Debug, Serialize)]
-struct VerificationCheckJson {
-    name: String,
-    status: VerificationStatusJson,
+pub(crate) struct VerificationCheckJson {
+    pub(crate) name: String,
+    pub(crate) status: VerificationStatusJson,
     details: Option<String>,
 }
 
@@ -60,14 +62,137 @@ impl From<&memvid_core::VerificationReport> for VerificationReportJson {
     }
 }
 
+/// Verification thoroughness selected by `memvid_verify`'s `level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VerifyLevel {
+    /// Header checks only, without opening the file as a memory
+    Quick,
+    /// memvid-core shallow verification
+    Shallow,
+    /// memvid-core deep verification
+    Deep,
+}
+
+impl VerifyLevel {
+    /// Map a C level: 0 is shallow and 2 is quick; any other nonzero value
+    /// is deep, as when `level` was a boolean `deep` flag.
+    fn from_raw(level: i32) -> Self {
+        match level {
+            0 => Self::Shallow,
+            2 => Self::Quick,
+            _ => Self::Deep,
+        }
+    }
+}
+
+/// Header-only verification for `VerifyLevel::Quick`.
+fn quick_verify(path: &Path) -> Result<VerificationReportJson, MemvidError> {
+    let header = header::read_header(path).map_err(MemvidError::io)?;
+
+    let check = |name: &str, passed: bool, details: String| VerificationCheckJson {
+        name: name.to_string(),
+        status: if passed {
+            VerificationStatusJson::Passed
+        } else {
+            VerificationStatusJson::Failed
+        },
+        details: Some(details),
+    };
+    let mut checks = vec![check(
+        "HeaderMagic",
+        header.magic_ok,
+        if header.magic_ok {
+            "Magic bytes match".to_string()
+        } else {
+            "Magic bytes do not match".to_string()
+        },
+    )];
+    let skipped = |name: &str| VerificationCheckJson {
+        name: name.to_string(),
+        status: VerificationStatusJson::Skipped,
+        details: None,
+    };
+    if header.magic_ok {
+        checks.push(check(
+            "HeaderVersion",
+            header.compatible(),
+            format!(
+                "Format {}, reader {}",
+                header.format_version(),
+                header::reader_version()
+            ),
+        ));
+    } else {
+        checks.push(skipped("HeaderVersion"));
+    }
+    match header.footer_offset {
+        Some(offset) => {
+            let ok = header::manifest_checksum_ok(path, offset).map_err(MemvidError::io)?;
+            checks.push(check(
+                "ManifestChecksum",
+                ok,
+                if ok {
+                    format!("Manifest at offset {offset} matches its commit footer")
+                } else {
+                    format!("Manifest at offset {offset} does not match its commit footer")
+                },
+            ));
+        }
+        None => checks.push(skipped("ManifestChecksum")),
+    }
+
+    Ok(VerificationReportJson::from_checks(path, checks))
+}
+
+impl VerificationReportJson {
+    /// Build a report whose overall status fails if any check failed.
+    fn from_checks(path: &Path, checks: Vec<VerificationCheckJson>) -> Self {
+        let failed = checks
+            .iter()
+            .any(|c| matches!(c.status, VerificationStatusJson::Failed));
+        Self {
+            file_path: path.to_string_lossy().to_string(),
+            overall_status: if failed {
+                VerificationStatusJson::Failed
+            } else {
+                VerificationStatusJson::Passed
+            },
+            checks,
+        }
+    }
+}
+
 /// Verify file integrity.
 ///
 /// This is a static function that does not require an open handle.
 ///
+/// # Levels
+///
+/// - `0` (shallow): memvid-core's `verify(path, false)`. Opens the file
+///   as a memory and checks its structure and index metadata without
+///   decoding index contents; the checks are core's.
+/// - `1` (deep): memvid-core's `verify(path, true)`. The shallow checks
+///   plus a full decode of every index (`LexIndexDecode`,
+///   `TimeIndexEntryCount`, ...). Slowest.
+/// - `2` (quick): runs in this library without opening the file as a
+///   memory. Reads the header, the manifest (TOC) it points to, and the
+///   commit footer, and runs exactly three checks:
+///   - `HeaderMagic`: the magic bytes match.
+///   - `HeaderVersion`: core's header codec accepts the header, so this
+///     library can read the format. Skipped if the magic is wrong.
+///   - `ManifestChecksum`: the commit footer decodes, the manifest ends
+///     where the footer starts, and the manifest's hash matches the
+///     footer's. Skipped if the header could not be decoded.
+///
+///   Frame payloads and index contents are not read.
+///
+/// `0` and `1` keep their meaning from when this parameter was a boolean
+/// `deep` flag; other nonzero values also select deep.
+///
 /// # Parameters
 ///
 /// - `path`: Path to the .mv2 file (null-terminated UTF-8 string)
-/// - `deep`: Perform deep verification (more thorough but slower)
+/// - `level`: Verification level (see above)
 /// - `error`: Out-parameter for error information
 ///
 /// # Returns
@@ -105,7 +230,7 @@ impl From<&memvid_core::VerificationReport> for VerificationReportJson {
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn memvid_verify(
     path: *const c_char,
-    deep: i32,
+    level: i32,
     error: *mut MemvidError,
 ) -> *mut c_char {
     let path = match unsafe { cstr_to_path(path) } {
@@ -113,19 +238,434 @@ pub unsafe extern "C" fn memvid_verify(
         Err(e) => return unsafe { set_error_null(error, e) },
     };
 
-    let deep = deep != 0;
+    unsafe { verify_at(&path, level, error) }
+}
+
+/// Verify the file at `path`, setting the error out-parameter.
+///
+/// # Safety
+///
+/// `error` must be a valid pointer or NULL.
+unsafe fn verify_at(path: &Path, level: i32, error: *mut MemvidError) -> *mut c_char {
+    let report = match VerifyLevel::from_raw(level) {
+        VerifyLevel::Quick => quick_verify(path),
+        level => memvid_core::Memvid::verify(path, level == VerifyLevel::Deep)
+            .map(|report| VerificationReportJson::from(&report))
+            .map_err(MemvidError::from_core_error),
+    };
+
+    match report {
+        Ok(report) => unsafe { json_to_cstr(&report, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Verify file integrity at a UTF-16 path (Windows only).
+///
+/// Same as `memvid_verify`, but takes the path as null-terminated UTF-16,
+/// so Windows paths round-trip without a lossy conversion to UTF-8.
+///
+/// # Parameters
+///
+/// - `path`: Path to the .mv2 file (null-terminated UTF-16 string)
+/// - `level`: Verification level (as in `memvid_verify`)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with verification report on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-16 string
+/// - `error` must be a valid pointer or NULL
+#[cfg(windows)]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_verify_w(
+    path: *const u16,
+    level: i32,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    match unsafe { crate::util::wstr_to_path(path) } {
+        Ok(path) => unsafe { verify_at(&path, level, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// Check groups selectable by `memvid_verify_with_options`, each with the
+/// substring identifying its checks by name.
+const CHECK_GROUPS: &[(&str, &str)] = &[
+    ("header", "Header"),
+    ("checksum", "Checksum"),
+    ("toc", "Toc"),
+    ("lex_index", "LexIndex"),
+    ("vec_index", "VecIndex"),
+    ("time_index", "TimeIndex"),
+];
 
-    match memvid_core::Memvid::verify(&path, deep) {
-        Ok(report) => {
-            let json_report = VerificationReportJson::from(&report);
-            match serde_json::to_string(&json_report) {
-                Ok(json) => {
-                    unsafe { set_ok(error) };
-                    string_to_cstr(json)
+/// Options for `memvid_verify_with_options`.
+#[derive(Debug, Default, Deserialize)]
+struct VerifyOptionsJson {
+    /// Check groups to run (default: all)
+    #[serde(default)]
+    checks: Option<Vec<String>>,
+    /// Run memvid-core's deep verification for the non-header groups
+    #[serde(default)]
+    deep: bool,
+}
+
+/// Verify the check groups selected by `options`.
+fn verify_selected(
+    path: &Path,
+    options: VerifyOptionsJson,
+) -> Result<VerificationReportJson, MemvidError> {
+    let mut groups: Vec<(&str, &str)> = Vec::new();
+    match &options.checks {
+        None => groups.extend_from_slice(CHECK_GROUPS),
+        Some(names) => {
+            for name in names {
+                let group = CHECK_GROUPS
+                    .iter()
+                    .find(|(g, _)| *g == name.as_str())
+                    .ok_or_else(|| {
+                        MemvidError::new(
+                            MemvidErrorCode::InvalidQuery,
+                            format!("unknown verify check: {name}"),
+                        )
+                    })?;
+                if !groups.contains(group) {
+                    groups.push(*group);
                 }
-                Err(e) => unsafe { set_error_null(error, MemvidError::json_serialize(e)) },
             }
         }
-        Err(e) => unsafe { set_error_null(error, MemvidError::from_core_error(e)) },
     }
+
+    let mut checks = Vec::new();
+    if groups.iter().any(|(g, _)| *g == "header") {
+        checks.extend(quick_verify(path)?.checks);
+    }
+
+    let core_groups: Vec<(&str, &str)> = groups
+        .iter()
+        .filter(|(g, _)| *g != "header")
+        .copied()
+        .collect();
+    let mut core_checks = Vec::new();
+    if !core_groups.is_empty() {
+        let report = memvid_core::Memvid::verify(path, options.deep)
+            .map_err(MemvidError::from_core_error)?;
+        core_checks.extend(
+            report
+                .checks
+                .iter()
+                .filter(|c| core_groups.iter().any(|(_, p)| c.name.contains(p)))
+                .map(VerificationCheckJson::from),
+        );
+    }
+
+    // The header group always has its checks; the others only have those
+    // core ran at this depth.
+    for (group, pattern) in &core_groups {
+        if !core_checks.iter().any(|c| c.name.contains(pattern)) {
+            core_checks.push(VerificationCheckJson {
+                name: group.to_string(),
+                status: VerificationStatusJson::Skipped,
+                details: Some("No such check at this depth".to_string()),
+            });
+        }
+    }
+    checks.extend(core_checks);
+
+    Ok(VerificationReportJson::from_checks(path, checks))
+}
+
+/// Verify file integrity, running only selected checks.
+///
+/// This is a static function that does not require an open handle.
+///
+/// memvid-core runs its checks all at once, so checks are selected in
+/// groups matched by name:
+///
+/// - `"header"`: `HeaderMagic`, `HeaderVersion`, and `ManifestChecksum`,
+///   as in `memvid_verify` level 2. Selecting only this group never opens
+///   the file as a memory.
+/// - `"checksum"`, `"toc"`, `"lex_index"`, `"vec_index"`, `"time_index"`:
+///   memvid-core checks whose names contain `Checksum`, `Toc`, `LexIndex`,
+///   `VecIndex`, or `TimeIndex`. Any of these runs memvid-core's shallow
+///   verification, or deep with `"deep": true`.
+///
+/// The report lists only checks in the selected groups. A group with no
+/// matching check (e.g. index decodes without `deep`) appears once under
+/// its group name with status `"skipped"`.
+///
+/// # Parameters
+///
+/// - `path`: Path to the .mv2 file (null-terminated UTF-8 string)
+/// - `options_json`: JSON options (NULL for all groups, shallow)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with verification report (same schema as `memvid_verify`)
+/// on success, NULL on failure (`InvalidQuery` naming an unknown check).
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Options JSON Schema
+///
+/// ```json
+/// {
+///   "checks": ["header", "checksum", "lex_index"],
+///   "deep": false
+/// }
+/// ```
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string
+/// - `options_json` must be a valid null-terminated UTF-8 string or NULL
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_verify_with_options(
+    path: *const c_char,
+    options_json: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let options: VerifyOptionsJson =
+        match unsafe { cstr_to_option_json(options_json, "options_json") } {
+            Ok(o) => o,
+            Err(e) => return unsafe { set_error_null(error, e) },
+        };
+
+    match verify_selected(&path, options) {
+        Ok(report) => unsafe { json_to_cstr(&report, error) },
+        Err(e) => unsafe { set_error_null(error, e) },
+    }
+}
+
+/// File format information for JSON serialization.
+#[derive(Debug, Serialize)]
+struct FormatInfoJson {
+    file_path: String,
+    format_version: String,
+    min_reader_version: String,
+    reader_version: String,
+    compatible: bool,
+}
+
+/// Read the on-disk format version and check compatibility.
+///
+/// This is a static function that does not require an open handle.
+/// Only the file header is read, so it is cheap and works on files this
+/// library cannot open.
+///
+/// # Parameters
+///
+/// - `path`: Path to the .mv2 file (null-terminated UTF-8 string)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with format information on success, NULL on failure.
+/// Caller must free with `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "file_path": "/path/to/file.mv2",
+///   "format_version": "2.1",
+///   "min_reader_version": "2.0",
+///   "reader_version": "2.1",
+///   "compatible": true
+/// }
+/// ```
+///
+/// The header is decoded by memvid-core. `format_version` is read from
+/// the header; `min_reader_version` is not stored in the file but derived
+/// from it as `"<major>.0"`, since any reader of the same major version can
+/// open the file. `compatible` is false when core rejects the header (bad
+/// magic or an unsupported version); `format_version` and
+/// `min_reader_version` are then `"unknown"`.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_format_info(
+    path: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let header = match header::read_header(&path) {
+        Ok(h) => h,
+        Err(e) => return unsafe { set_error_null(error, MemvidError::io(e)) },
+    };
+
+    let info = FormatInfoJson {
+        file_path: path.to_string_lossy().to_string(),
+        format_version: header.format_version(),
+        min_reader_version: header.min_reader_version(),
+        reader_version: header::reader_version(),
+        compatible: header.compatible(),
+    };
+    unsafe { json_to_cstr(&info, error) }
+}
+
+/// Header fields of an inspected file.
+#[derive(Debug, Serialize)]
+struct InspectHeaderJson {
+    magic_ok: bool,
+    format_version: String,
+    compatible: bool,
+}
+
+/// Manifest summary of an inspected file.
+#[derive(Debug, Serialize)]
+struct InspectManifestJson {
+    frame_count: u64,
+    active_frame_count: u64,
+    capacity_bytes: u64,
+    has_lex_index: bool,
+    has_vec_index: bool,
+    has_clip_index: bool,
+    has_time_index: bool,
+}
+
+/// Result of `memvid_inspect`.
+#[derive(Debug, Serialize)]
+struct InspectJson {
+    file_path: String,
+    file_size: u64,
+    encrypted: bool,
+    header: Option<InspectHeaderJson>,
+    manifest: Option<InspectManifestJson>,
+    errors: Vec<String>,
+}
+
+/// Summarize a file's header and manifest for tooling.
+///
+/// This is a static function that does not require an open handle. The
+/// header is read as in `memvid_format_info`; the manifest is then read by
+/// opening the file read-only, without taking the write lock or running
+/// verification. A part that cannot be parsed is null, with the reason in
+/// `errors`, so a file too corrupt to open still reports its header.
+///
+/// # Parameters
+///
+/// - `path`: Path to the .mv2 file (null-terminated UTF-8 string)
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// JSON string with the summary on success, NULL on failure (only when the
+/// file cannot be read at all). Caller must free with
+/// `memvid_string_free()`.
+///
+/// # Response JSON Schema
+///
+/// ```json
+/// {
+///   "file_path": "/path/to/file.mv2",
+///   "file_size": 1048576,
+///   "encrypted": false,
+///   "header": {
+///     "magic_ok": true,
+///     "format_version": "2.1",
+///     "compatible": true
+///   },
+///   "manifest": {
+///     "frame_count": 120,
+///     "active_frame_count": 118,
+///     "capacity_bytes": 1073741824,
+///     "has_lex_index": true,
+///     "has_vec_index": false,
+///     "has_clip_index": false,
+///     "has_time_index": true
+///   },
+///   "errors": []
+/// }
+/// ```
+///
+/// memvid-core has no encrypted format, so `encrypted` is always false.
+/// The manifest is only read when the header is compatible.
+///
+/// # Safety
+///
+/// - `path` must be a valid null-terminated UTF-8 string
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_inspect(
+    path: *const c_char,
+    error: *mut MemvidError,
+) -> *mut c_char {
+    let path = match unsafe { cstr_to_path(path) } {
+        Ok(p) => p,
+        Err(e) => return unsafe { set_error_null(error, e) },
+    };
+
+    let file_size = match std::fs::metadata(&path) {
+        Ok(m) => m.len(),
+        Err(e) => return unsafe { set_error_null(error, MemvidError::io(e)) },
+    };
+
+    let mut errors = Vec::new();
+    let header = match header::read_header(&path) {
+        Ok(h) => Some(h),
+        Err(e) => {
+            errors.push(format!("header: {e}"));
+            None
+        }
+    };
+    let manifest = match header {
+        Some(h) if h.compatible() => {
+            match memvid_core::Memvid::open_read_only(&path).and_then(|memvid| memvid.stats()) {
+                Ok(stats) => Some(InspectManifestJson {
+                    frame_count: stats.frame_count,
+                    active_frame_count: stats.active_frame_count,
+                    capacity_bytes: stats.capacity_bytes,
+                    has_lex_index: stats.has_lex_index,
+                    has_vec_index: stats.has_vec_index,
+                    has_clip_index: stats.has_clip_index,
+                    has_time_index: stats.has_time_index,
+                }),
+                Err(e) => {
+                    errors.push(format!("manifest: {e}"));
+                    None
+                }
+            }
+        }
+        Some(h) => {
+            errors.push(format!(
+                "manifest: not read; format {} is not supported",
+                h.format_version()
+            ));
+            None
+        }
+        None => None,
+    };
+
+    let info = InspectJson {
+        file_path: path.to_string_lossy().to_string(),
+        file_size,
+        encrypted: false,
+        header: header.map(|h| InspectHeaderJson {
+            magic_ok: h.magic_ok,
+            format_version: h.format_version(),
+            compatible: h.compatible(),
+        }),
+        manifest,
+        errors,
+    };
+    unsafe { json_to_cstr(&info, error) }
 }
@@ -0,0 +1,211 @@
+//! Change notification for committed frames.
+
+use crate::error::MemvidError;
+use crate::handle::MemvidHandle;
+use crate::util::{set_error_null, set_ok};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::ffi::{c_void, CString};
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+/// Interval between file polls.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Watch callback type.
+///
+/// Receives a JSON event describing newly committed frames. The event
+/// string is only valid for the duration of the call.
+pub type MemvidWatchFn = extern "C" fn(event_json: *const c_char, user_data: *mut c_void);
+
+/// Watch event for JSON serialization.
+#[derive(Debug, Serialize)]
+struct WatchEventJson {
+    frame_ids: Vec<u64>,
+    frame_count: u64,
+}
+
+/// Callback and user data moved to the watcher thread.
+struct WatchSink {
+    callback: MemvidWatchFn,
+    user_data: *mut c_void,
+}
+
+// SAFETY: the caller guarantees the callback and user data may be used from
+// the watcher thread (documented on `memvid_watch`).
+unsafe impl Send for WatchSink {}
+
+/// Opaque handle to a running watcher.
+///
+/// Must be stopped and freed with `memvid_watch_stop()`.
+pub struct MemvidWatcher {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// File size and modification time, used to detect writes cheaply.
+fn file_stamp(path: &Path) -> Option<(u64, SystemTime)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.len(), meta.modified().ok()?))
+}
+
+/// IDs of every frame in core's frame table.
+fn frame_ids(memvid: &memvid_core::Memvid) -> HashSet<u64> {
+    memvid.toc().frames.iter().map(|f| f.id).collect()
+}
+
+/// Poll `path` until `stop` is set, reporting frames not in `known`.
+fn watch_loop(path: PathBuf, mut known: HashSet<u64>, sink: WatchSink, stop: Arc<AtomicBool>) {
+    let mut stamp = file_stamp(&path);
+
+    while !stop.load(Ordering::Acquire) {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let current = file_stamp(&path);
+        if current == stamp {
+            continue;
+        }
+
+        // Only advance the stamp once the new state has been read, so a
+        // transient failure (e.g. a commit in progress) is retried.
+        let (ids, count) = match memvid_core::Memvid::open_read_only(&path) {
+            Ok(memvid) => (frame_ids(&memvid), memvid.frame_count() as u64),
+            Err(_) => continue,
+        };
+        stamp = current;
+
+        let mut new_ids: Vec<u64> = ids.difference(&known).copied().collect();
+        known = ids;
+        if new_ids.is_empty() {
+            continue;
+        }
+        new_ids.sort_unstable();
+
+        let event = WatchEventJson {
+            frame_ids: new_ids,
+            frame_count: count,
+        };
+
+        if let Some(json) = serde_json::to_string(&event)
+            .ok()
+            .and_then(|s| CString::new(s).ok())
+        {
+            (sink.callback)(json.as_ptr(), sink.user_data);
+        }
+    }
+}
+
+/// Watch the memory file for commits.
+///
+/// Starts a background thread that checks the file's size and modification
+/// time every 250 ms, and reopens it read-only only when they change. When
+/// a commit (from this or another process) adds frames, `callback` is
+/// invoked with the IDs that were not in the frame table before, so the
+/// report stays exact after deletes or compaction:
+///
+/// ```json
+/// {"frame_ids": [12, 13], "frame_count": 14}
+/// ```
+///
+/// The watcher reads the file through its own read-only instance and never
+/// touches `handle` after this call returns, so the handle may be closed
+/// while the watcher runs.
+///
+/// # Parameters
+///
+/// - `handle`: Valid Memvid handle
+/// - `callback`: Function invoked for each batch of new frames
+/// - `user_data`: Opaque pointer passed through to `callback`
+/// - `error`: Out-parameter for error information
+///
+/// # Returns
+///
+/// Watcher on success, NULL on failure.
+///
+/// # Ownership
+///
+/// Caller owns the returned watcher. Must call `memvid_watch_stop()` to free.
+///
+/// # Threading
+///
+/// `callback` runs on the watcher thread, not the calling thread. It and
+/// `user_data` must be safe to use from that thread, and the callback must
+/// not call into `handle`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid handle
+/// - `callback` must be a valid function pointer
+/// - `error` must be a valid pointer or NULL
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_watch(
+    handle: *mut MemvidHandle,
+    callback: Option<MemvidWatchFn>,
+    user_data: *mut c_void,
+    error: *mut MemvidError,
+) -> *mut MemvidWatcher {
+    let handle = match unsafe { MemvidHandle::from_ptr_mut(handle) } {
+        Some(h) => h,
+        None => return unsafe { set_error_null(error, MemvidError::invalid_handle()) },
+    };
+
+    let callback = match callback {
+        Some(c) => c,
+        None => return unsafe { set_error_null(error, MemvidError::null_pointer("callback")) },
+    };
+
+    let path = handle.path().to_path_buf();
+    let known = frame_ids(handle.as_ref());
+    let sink = WatchSink {
+        callback,
+        user_data,
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let thread_stop = Arc::clone(&stop);
+    let thread = std::thread::Builder::new()
+        .name("memvid-watch".to_string())
+        .spawn(move || watch_loop(path, known, sink, thread_stop));
+
+    match thread {
+        Ok(thread) => {
+            unsafe { set_ok(error) };
+            Box::into_raw(Box::new(MemvidWatcher {
+                stop,
+                thread: Some(thread),
+            }))
+        }
+        Err(e) => unsafe { set_error_null(error, MemvidError::io(e)) },
+    }
+}
+
+/// Stop and free a watcher.
+///
+/// Blocks until the watcher thread exits; no callbacks run after this
+/// returns.
+///
+/// # Parameters
+///
+/// - `watcher`: Watcher to stop (safe to pass NULL)
+///
+/// # Safety
+///
+/// - `watcher` must be a watcher returned by `memvid_watch`, or NULL
+/// - The watcher must not be used after this call
+/// - Must not be called from inside the watch callback
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_watch_stop(watcher: *mut MemvidWatcher) {
+    if watcher.is_null() {
+        return;
+    }
+
+    let mut watcher = unsafe { Box::from_raw(watcher) };
+    watcher.stop.store(true, Ordering::Release);
+    if let Some(thread) = watcher.thread.take() {
+        let _ = thread.join();
+    }
+}